@@ -15,6 +15,12 @@ import type {
 } from "./types";
 
 const DISCORD_GATEWAY_URL = "https://discord.com/api/v10/gateway";
+const DISCORD_API_BASE = "https://discord.com/api/v10";
+
+// Discord interaction types / callback types
+const INTERACTION_TYPE_APPLICATION_COMMAND = 2;
+const INTERACTION_CALLBACK_CHANNEL_MESSAGE = 4;
+const MESSAGE_FLAG_EPHEMERAL = 1 << 6;
 
 // Discord Gateway Opcodes
 const OP = {
@@ -67,6 +73,13 @@ type DiscordAttachment = {
   duration?: number;
 };
 
+type DiscordUser = {
+  id: string;
+  username: string;
+  bot?: boolean;
+  discriminator?: string;
+};
+
 type GatewayState = {
   accountId: string | null;  // The name used to create this DO (e.g., "default")
   botToken: string | null;
@@ -341,6 +354,10 @@ export class DiscordGateway extends DurableObject<Env> {
         await this.handleMessageCreate(d);
         break;
 
+      case "INTERACTION_CREATE":
+        await this.handleInteractionCreate(d);
+        break;
+
       // Add more event handlers as needed
     }
   }
@@ -413,6 +430,93 @@ export class DiscordGateway extends DurableObject<Env> {
     }
   }
 
+  /**
+   * Handle a slash command registered by `gsv deploy up --discord-channel-guild-id`.
+   * The interaction is acknowledged immediately (Discord requires a response
+   * within 3 seconds) and the command is forwarded to the Gateway as
+   * `/<name> <args>` text, where the regular slash command handling replies
+   * in the channel.
+   */
+  private async handleInteractionCreate(data: Record<string, unknown>) {
+    if (data.type !== INTERACTION_TYPE_APPLICATION_COMMAND) return;
+
+    const interactionId = data.id as string;
+    const interactionToken = data.token as string;
+    const channelId = data.channel_id as string | undefined;
+    const guildId = data.guild_id as string | undefined;
+    const command = data.data as
+      | { name?: string; options?: Array<{ name: string; value?: unknown }> }
+      | undefined;
+    const member = data.member as { user?: DiscordUser } | undefined;
+    const user = member?.user ?? (data.user as DiscordUser | undefined);
+    if (!interactionId || !interactionToken || !channelId || !command?.name) return;
+
+    const args = (command.options ?? [])
+      .map((option) => (option.value === undefined ? "" : String(option.value)))
+      .filter((value) => value.length > 0)
+      .join(" ");
+    const text = args ? `/${command.name} ${args}` : `/${command.name}`;
+
+    try {
+      const response = await fetch(
+        `${DISCORD_API_BASE}/interactions/${interactionId}/${interactionToken}/callback`,
+        {
+          method: "POST",
+          headers: { "Content-Type": "application/json" },
+          body: JSON.stringify({
+            type: INTERACTION_CALLBACK_CHANNEL_MESSAGE,
+            data: { content: `Running \`${text}\``, flags: MESSAGE_FLAG_EPHEMERAL },
+          }),
+        },
+      );
+      if (!response.ok) {
+        console.warn(
+          `[DiscordGateway] Failed to acknowledge interaction ${interactionId}: HTTP ${response.status}`,
+        );
+      }
+    } catch (e) {
+      console.warn(`[DiscordGateway] Error acknowledging interaction ${interactionId}:`, e);
+    }
+
+    const message: ChannelInboundMessage = {
+      messageId: interactionId,
+      peer: {
+        kind: guildId ? "group" : "dm",
+        id: channelId,
+      },
+      sender: user ? {
+        id: user.id,
+        name: user.username,
+        handle: user.discriminator && user.discriminator !== "0"
+          ? `${user.username}#${user.discriminator}`
+          : user.username,
+      } : undefined,
+      text,
+      timestamp: Date.now(),
+      // Slash commands are addressed to the bot explicitly.
+      wasMentioned: true,
+    };
+
+    try {
+      const result = await this.env.GATEWAY.channelInbound(
+        "discord",
+        this.getAccountId(),
+        message,
+      );
+      if (!result.ok) {
+        console.error(
+          `[DiscordGateway] Interaction rejected by gateway: ${result.error ?? "unknown error"}`,
+        );
+        return;
+      }
+      console.log(
+        `[DiscordGateway] Delivered interaction ${interactionId} (${text}) from ${user?.username}`,
+      );
+    } catch (e) {
+      console.error("[DiscordGateway] Failed to deliver interaction via RPC:", e);
+    }
+  }
+
   private async notifyGatewayStatus(status: ChannelAccountStatus): Promise<void> {
     const accountId = this.getAccountId();
     try {
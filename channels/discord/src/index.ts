@@ -17,6 +17,7 @@ import type {
   StartResult,
   StopResult,
   SendResult,
+  ListGuildsResult,
 } from "./types";
 
 export { DiscordGateway } from "./discord-gateway";
@@ -110,18 +111,26 @@ export class DiscordChannel extends WorkerEntrypoint<Env> implements ChannelWork
       const body: Record<string, unknown> = {};
       const hasText = message.text.trim().length > 0;
       const media = message.media ?? [];
+      const embeds = message.embeds ?? [];
 
-      if (!hasText && media.length === 0) {
-        return { ok: false, error: "Discord messages require text or media" };
+      if (!hasText && media.length === 0 && embeds.length === 0) {
+        return { ok: false, error: "Discord messages require text, media or embeds" };
       }
 
       if (hasText) {
         body.content = message.text;
       }
 
+      if (embeds.length > 0) {
+        body.embeds = embeds;
+      }
+
       if (message.replyToId) {
         body.message_reference = {
           message_id: message.replyToId,
+          // Slash command interactions have no message to reply to; post
+          // the response as a regular message instead of failing.
+          fail_if_not_exists: false,
         };
       }
 
@@ -176,10 +185,96 @@ export class DiscordChannel extends WorkerEntrypoint<Env> implements ChannelWork
     });
   }
 
+  /**
+   * List the guilds the bot has joined.
+   */
+  async listGuilds(accountId: string): Promise<ListGuildsResult> {
+    const botToken = this.env.DISCORD_BOT_TOKEN;
+    if (!botToken) {
+      return { ok: false, error: "No bot token configured" };
+    }
+
+    try {
+      const response = await this.discordFetch("/users/@me/guilds?with_counts=true", {
+        method: "GET",
+        botToken,
+      });
+
+      if (!response.ok) {
+        const error = await response.text();
+        return { ok: false, error: `Discord API error: ${response.status} ${error}` };
+      }
+
+      const guilds = await response.json<
+        Array<{ id: string; name: string; approximate_member_count?: number }>
+      >();
+      const commandsRegistered = await this.resolveCommandsRegistered(
+        botToken,
+        guilds.map((guild) => guild.id),
+      );
+      return {
+        ok: true,
+        guilds: guilds.map((guild) => ({
+          id: guild.id,
+          name: guild.name,
+          memberCount: guild.approximate_member_count,
+          commandsRegistered: commandsRegistered.get(guild.id),
+        })),
+      };
+    } catch (e) {
+      return { ok: false, error: e instanceof Error ? e.message : String(e) };
+    }
+  }
+
   // ─────────────────────────────────────────────────────────
   // Private helpers
   // ─────────────────────────────────────────────────────────
 
+  /**
+   * Whether the application's slash commands are available in each guild:
+   * global commands cover every guild, otherwise the guild needs its own.
+   * Guilds whose commands could not be listed are left out of the map.
+   */
+  private async resolveCommandsRegistered(
+    botToken: string,
+    guildIds: string[],
+  ): Promise<Map<string, boolean>> {
+    const registered = new Map<string, boolean>();
+    const countCommands = async (path: string): Promise<number | undefined> => {
+      const response = await this.discordFetch(path, { method: "GET", botToken });
+      if (!response.ok) return undefined;
+      return (await response.json<unknown[]>()).length;
+    };
+
+    try {
+      const appResponse = await this.discordFetch("/oauth2/applications/@me", {
+        method: "GET",
+        botToken,
+      });
+      if (!appResponse.ok) return registered;
+      const { id: applicationId } = await appResponse.json<{ id: string }>();
+
+      const globalCount = await countCommands(`/applications/${applicationId}/commands`);
+      await Promise.all(
+        guildIds.map(async (guildId) => {
+          if (globalCount) {
+            registered.set(guildId, true);
+            return;
+          }
+          const count = await countCommands(
+            `/applications/${applicationId}/guilds/${guildId}/commands`,
+          );
+          if (count !== undefined) {
+            registered.set(guildId, count > 0);
+          }
+        }),
+      );
+    } catch (e) {
+      console.error("[DiscordChannel] Failed to list application commands:", e);
+    }
+    return registered;
+  }
+
   private getGatewayDO(accountId: string) {
     const id = this.env.DISCORD_GATEWAY.idFromName(accountId);
     return this.env.DISCORD_GATEWAY.get(id) as unknown as DiscordGatewayStub;
@@ -52,6 +52,7 @@ export type ChannelOutboundMessage = {
   peer: ChannelPeer;
   text: string;
   media?: ChannelMedia[];
+  embeds?: Record<string, unknown>[];
   replyToId?: string;
 };
 
@@ -86,6 +87,14 @@ export type SendResult = { ok: true; messageId?: string } | { ok: false; error:
 export type LoginResult = { ok: true; qrDataUrl?: string; message: string } | { ok: false; error: string };
 export type LogoutResult = { ok: true } | { ok: false; error: string };
 
+export type ChannelGuild = {
+  id: string;
+  name: string;
+  memberCount?: number;
+  commandsRegistered?: boolean;
+};
+export type ListGuildsResult = { ok: true; guilds: ChannelGuild[] } | { ok: false; error: string };
+
 // ============================================================================
 // Interface
 // ============================================================================
@@ -103,4 +112,5 @@ export interface ChannelWorkerInterface {
   
   login?(accountId: string, options?: { force?: boolean }): Promise<LoginResult>;
   logout?(accountId: string): Promise<LogoutResult>;
+  listGuilds?(accountId: string): Promise<ListGuildsResult>;
 }
@@ -16,6 +16,7 @@ import {
   DisconnectReason,
   extractMessageContent,
   getContentType,
+  type AnyMessageContent,
   type WASocket,
   type BaileysEventMap,
   type WAMessage,
@@ -299,9 +300,21 @@ export class WhatsAppAccount extends DurableObject<Env> {
     }
 
     try {
-      const sent = await this.sock.sendMessage(jid, { text: message.text });
+      const media = message.media?.find((m) => m.url);
+      let content: AnyMessageContent = { text: message.text };
+      if (media?.url && media.type === "image") {
+        content = { image: { url: media.url }, caption: message.text || undefined };
+      } else if (media?.url) {
+        content = {
+          document: { url: media.url },
+          mimetype: media.mimeType,
+          fileName: media.filename,
+          caption: message.text || undefined,
+        };
+      }
+      const sent = await this.sock.sendMessage(jid, content);
       console.log(`[WA] Sent to ${jid}: "${message.text.substring(0, 50)}..."`);
-      return Response.json({ messageId: sent?.key?.id });
+      return Response.json({ success: true, messageId: sent?.key?.id });
     } catch (e) {
       console.error(`[WA] Send failed:`, e);
       return Response.json({ error: String(e) }, { status: 500 });
@@ -0,0 +1,146 @@
+use crate::config::ReconnectConfig;
+use std::time::Duration;
+
+pub const RECONNECT_INITIAL_MS_ENV: &str = "GSV_RECONNECT_INITIAL_MS";
+pub const RECONNECT_MAX_MS_ENV: &str = "GSV_RECONNECT_MAX_MS";
+pub const RECONNECT_MULTIPLIER_ENV: &str = "GSV_RECONNECT_MULTIPLIER";
+pub const RECONNECT_JITTER_ENV: &str = "GSV_RECONNECT_JITTER";
+
+/// Exponential delay between gateway reconnect attempts: starts at
+/// `initial_delay_ms`, grows by `multiplier` per failure up to `max_delay_ms`,
+/// and goes back to the start after a successful connection.
+#[derive(Debug, Clone)]
+pub struct ReconnectBackoff {
+    config: ReconnectConfig,
+    current_ms: f64,
+}
+
+impl ReconnectBackoff {
+    pub fn new(config: ReconnectConfig) -> Self {
+        // A zero initial delay would stay zero however often it is multiplied.
+        let initial_delay_ms = config.initial_delay_ms.max(1);
+        let config = ReconnectConfig {
+            initial_delay_ms,
+            max_delay_ms: config.max_delay_ms.max(initial_delay_ms),
+            multiplier: if config.multiplier.is_finite() && config.multiplier >= 1.0 {
+                config.multiplier
+            } else {
+                1.0
+            },
+            ..config
+        };
+        let current_ms = config.initial_delay_ms as f64;
+        Self { config, current_ms }
+    }
+
+    /// `[node.reconnect]` with `GSV_RECONNECT_*` environment overrides applied.
+    pub fn from_config_and_env(config: &ReconnectConfig) -> Self {
+        Self::new(apply_env_overrides(config.clone(), |name| {
+            std::env::var(name).ok()
+        }))
+    }
+
+    /// Delay to wait before the next attempt; each call grows the following one.
+    pub fn next_delay(&mut self) -> Duration {
+        let base = self.current_ms;
+        self.current_ms = (base * self.config.multiplier).min(self.config.max_delay_ms as f64);
+        let delay = if self.config.jitter {
+            base * rand::random_range(0.9..=1.1)
+        } else {
+            base
+        };
+        Duration::from_millis(delay.round() as u64)
+    }
+
+    /// Start over from the initial delay (call after connecting).
+    pub fn reset(&mut self) {
+        self.current_ms = self.config.initial_delay_ms as f64;
+    }
+}
+
+fn apply_env_overrides(
+    mut config: ReconnectConfig,
+    var: impl Fn(&str) -> Option<String>,
+) -> ReconnectConfig {
+    let parse = |name: &str| var(name).map(|value| value.trim().to_string());
+    if let Some(value) = parse(RECONNECT_INITIAL_MS_ENV).and_then(|v| v.parse().ok()) {
+        config.initial_delay_ms = value;
+    }
+    if let Some(value) = parse(RECONNECT_MAX_MS_ENV).and_then(|v| v.parse().ok()) {
+        config.max_delay_ms = value;
+    }
+    if let Some(value) = parse(RECONNECT_MULTIPLIER_ENV).and_then(|v| v.parse().ok()) {
+        config.multiplier = value;
+    }
+    if let Some(value) = parse(RECONNECT_JITTER_ENV) {
+        match value.to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => config.jitter = true,
+            "0" | "false" | "no" | "off" => config.jitter = false,
+            _ => {}
+        }
+    }
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(jitter: bool) -> ReconnectConfig {
+        ReconnectConfig {
+            initial_delay_ms: 1_000,
+            max_delay_ms: 5_000,
+            multiplier: 2.0,
+            jitter,
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_caps_and_resets() {
+        let mut backoff = ReconnectBackoff::new(config(false));
+        let delays: Vec<u64> = (0..5)
+            .map(|_| backoff.next_delay().as_millis() as u64)
+            .collect();
+        assert_eq!(delays, vec![1_000, 2_000, 4_000, 5_000, 5_000]);
+
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), Duration::from_millis(1_000));
+    }
+
+    #[test]
+    fn test_backoff_zero_initial_delay_still_grows() {
+        let mut backoff = ReconnectBackoff::new(ReconnectConfig {
+            initial_delay_ms: 0,
+            ..config(false)
+        });
+        let delays: Vec<u64> = (0..3)
+            .map(|_| backoff.next_delay().as_millis() as u64)
+            .collect();
+        assert_eq!(delays, vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn test_backoff_jitter_stays_within_ten_percent() {
+        let mut backoff = ReconnectBackoff::new(config(true));
+        for _ in 0..50 {
+            backoff.reset();
+            let delay = backoff.next_delay().as_millis() as u64;
+            assert!((900..=1_100).contains(&delay), "delay {}", delay);
+        }
+    }
+
+    #[test]
+    fn test_env_overrides_replace_config_values() {
+        let config = apply_env_overrides(config(true), |name| match name {
+            RECONNECT_INITIAL_MS_ENV => Some("250".to_string()),
+            RECONNECT_MULTIPLIER_ENV => Some("1.5".to_string()),
+            RECONNECT_JITTER_ENV => Some("false".to_string()),
+            RECONNECT_MAX_MS_ENV => Some("not-a-number".to_string()),
+            _ => None,
+        });
+        assert_eq!(config.initial_delay_ms, 250);
+        assert_eq!(config.multiplier, 1.5);
+        assert!(!config.jitter);
+        assert_eq!(config.max_delay_ms, 5_000);
+    }
+}
@@ -1,6 +1,7 @@
-use std::io::{self, BufRead, Write};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::sync::{Arc, Mutex};
 
 use gsv::config;
 use gsv::connection::Connection;
@@ -9,10 +10,37 @@ use gsv::protocol::Frame;
 use serde_json::json;
 
 use crate::{
-    ChannelAction, ConfigAction, DiscordAction, HeartbeatAction, PairAction, SessionAction,
-    SkillsAction, ToolsAction, WhatsAppAction,
+    ChannelAction, ConfigAction, DiscordAction, HeartbeatAction, OutputFormat, PairAction,
+    SessionAction, SkillsAction, SlackAction, TelegramAction, ToolsAction, WhatsAppAction,
 };
 
+/// Shape of `--output json`: the gateway payload with `"ok": true` added
+/// (non-object payloads are wrapped as `result`).
+fn json_output(payload: serde_json::Value) -> serde_json::Value {
+    match payload {
+        serde_json::Value::Object(mut map) => {
+            map.insert("ok".to_string(), json!(true));
+            serde_json::Value::Object(map)
+        }
+        other => json!({ "ok": true, "result": other }),
+    }
+}
+
+fn print_json_output(payload: serde_json::Value) -> Result<(), serde_json::Error> {
+    println!("{}", serde_json::to_string_pretty(&json_output(payload))?);
+    Ok(())
+}
+
+/// Estimate how many trailing messages fit in `budget` tokens, assuming the
+/// session's token usage is spread evenly across its messages.
+fn keep_for_token_budget(message_count: i64, total_tokens: i64, budget: i64) -> i64 {
+    if message_count <= 0 || total_tokens <= budget {
+        return message_count.max(0);
+    }
+    let per_message = (total_tokens as f64 / message_count as f64).max(1.0);
+    ((budget as f64 / per_message).floor() as i64).clamp(1, message_count)
+}
+
 fn truncate_for_display(text: &str, max_bytes: usize) -> String {
     if text.len() <= max_bytes {
         return text.to_string();
@@ -26,91 +54,165 @@ fn truncate_for_display(text: &str, max_bytes: usize) -> String {
     format!("{}...", &text[..end])
 }
 
+const MAX_CHAT_ATTACHMENT_BYTES: u64 = 1024 * 1024;
+
+/// Reads `path` into a `chat.send` attachment, warning when the gateway can
+/// only pass a placeholder for it to the model.
+pub(crate) fn load_chat_attachment(
+    path: &std::path::Path,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let metadata = std::fs::metadata(path)
+        .map_err(|e| format!("Cannot read attachment {}: {}", path.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("Attachment {} is not a file", path.display()).into());
+    }
+    if metadata.len() > MAX_CHAT_ATTACHMENT_BYTES {
+        return Err(format!(
+            "Attachment {} is {} bytes; the limit is 1 MB",
+            path.display(),
+            metadata.len()
+        )
+        .into());
+    }
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "attachment".to_string());
+    let attachment = chat_attachment(&name, std::fs::read(path)?);
+
+    let content_type = attachment["content_type"].as_str().unwrap_or_default();
+    if !content_type.contains("charset=") && !content_type.starts_with("image/") {
+        eprintln!(
+            "Warning: {} is binary ({}); models only receive a placeholder for non-image files.",
+            name, content_type
+        );
+    }
+    Ok(attachment)
+}
+
+/// UTF-8 files are sent as-is with a `charset=utf-8` content type; anything
+/// else is base64-encoded with a sniffed MIME type.
+fn chat_attachment(name: &str, bytes: Vec<u8>) -> serde_json::Value {
+    use base64::Engine;
+
+    match String::from_utf8(bytes) {
+        Ok(text) if !text.contains('\0') => json!({
+            "name": name,
+            "content_type": "text/plain; charset=utf-8",
+            "data": text,
+        }),
+        result => {
+            let bytes = match result {
+                Ok(text) => text.into_bytes(),
+                Err(error) => error.into_bytes(),
+            };
+            let content_type = infer::get(&bytes)
+                .map(|kind| kind.mime_type())
+                .unwrap_or("application/octet-stream");
+            json!({
+                "name": name,
+                "content_type": content_type,
+                "data": base64::engine::general_purpose::STANDARD.encode(&bytes),
+            })
+        }
+    }
+}
+
 pub(crate) async fn run_client(
     url: &str,
     token: Option<String>,
     message: Option<String>,
+    attachment: Option<serde_json::Value>,
     session_key: &str,
+    timeout_secs: u64,
 ) -> Result<(), Box<dyn std::error::Error>> {
     println!("Connecting to {}...", url);
 
-    // Flag to track when we've received a final/error response
-    let response_received = Arc::new(AtomicBool::new(false));
-    let response_received_clone = response_received.clone();
+    // Runs that have seen a final/error event, and their `partial` text
+    let runs = Arc::new(Mutex::new(ChatRuns::default()));
+    let runs_clone = runs.clone();
     let session_key_owned = session_key.to_string();
+    let timeout = tokio::time::Duration::from_secs(timeout_secs);
+
+    let conn =
+        Connection::connect_with_options(url, "client", None, None, |_| {}, None, token).await?;
+    conn.subscribe("chat", move |frame| {
+        if let Frame::Evt(evt) = frame {
+            if let Some(payload) = evt.payload {
+                // Filter by sessionKey - ignore events for other sessions
+                if let Some(event_session) = payload.get("sessionKey").and_then(|s| s.as_str()) {
+                    if event_session != session_key_owned {
+                        return;
+                    }
+                }
 
-    let conn = Connection::connect_with_options(
-        url,
-        "client",
-        None,
-        None,
-        move |frame| {
-            // Handle incoming events
-            if let Frame::Evt(evt) = frame {
-                if evt.event == "chat" {
-                    if let Some(payload) = evt.payload {
-                        // Filter by sessionKey - ignore events for other sessions
-                        if let Some(event_session) =
-                            payload.get("sessionKey").and_then(|s| s.as_str())
-                        {
-                            if event_session != session_key_owned {
-                                return;
+                let run_id = payload
+                    .get("runId")
+                    .and_then(|r| r.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                if let Some(state) = payload.get("state").and_then(|s| s.as_str()) {
+                    match state {
+                        "delta" | "partial" => {
+                            if let Some(text) = payload.get("text").and_then(|t| t.as_str()) {
+                                print!("{}", text);
+                                let _ = io::stdout().flush();
+                            } else if let Some(content) =
+                                payload.get("message").and_then(|m| m.get("content"))
+                            {
+                                if let Ok(mut runs) = runs_clone.lock() {
+                                    runs.partial
+                                        .entry(run_id)
+                                        .or_default()
+                                        .push_str(&format_content(content));
+                                }
                             }
                         }
-
-                        if let Some(state) = payload.get("state").and_then(|s| s.as_str()) {
-                            match state {
-                                "delta" | "partial" => {
-                                    if let Some(text) = payload.get("text").and_then(|t| t.as_str())
-                                    {
-                                        print!("{}", text);
-                                        let _ = io::stdout().flush();
-                                    }
-                                }
-                                "final" => {
-                                    if let Some(msg) = payload.get("message") {
-                                        if let Some(content) = msg.get("content") {
-                                            println!("\nAssistant: {}", format_content(content));
-                                        }
-                                    }
-                                    response_received_clone.store(true, Ordering::SeqCst);
-                                }
-                                "error" => {
-                                    if let Some(err) = payload.get("error").and_then(|e| e.as_str())
-                                    {
-                                        eprintln!("\nError: {}", err);
-                                    }
-                                    response_received_clone.store(true, Ordering::SeqCst);
+                        "final" => {
+                            if let Some(msg) = payload.get("message") {
+                                if let Some(content) = msg.get("content") {
+                                    println!("\nAssistant: {}", format_content(content));
                                 }
-                                _ => {}
+                            }
+                            if let Ok(mut runs) = runs_clone.lock() {
+                                runs.finished.insert(run_id);
+                            }
+                        }
+                        "error" => {
+                            if let Some(err) = payload.get("error").and_then(|e| e.as_str()) {
+                                eprintln!("\nError: {}", err);
+                            }
+                            if let Ok(mut runs) = runs_clone.lock() {
+                                runs.finished.insert(run_id);
                             }
                         }
+                        _ => {}
                     }
                 }
             }
-        },
-        None,
-        token,
-    )
-    .await?;
+        }
+    })
+    .await;
     let gateway = GatewayClient::new(conn);
 
     if let Some(msg) = message {
         // One-shot mode: send message and wait for response
-        let was_command = send_chat(&gateway, session_key, &msg).await?;
+        let run_id = match attachment {
+            Some(attachment) => {
+                let payload = gateway
+                    .chat_send_with_attachment(session_key.to_string(), msg, attachment)
+                    .await?;
+                handle_chat_send_payload(&payload)
+            }
+            None => send_chat(&gateway, session_key, &msg).await?,
+        };
 
         // Only wait for chat event if this wasn't a command/directive
-        if !was_command {
-            // Wait for response (up to 120 seconds for LLM + tool execution)
-            let timeout = tokio::time::Duration::from_secs(120);
-            let start = tokio::time::Instant::now();
-
-            while !response_received.load(Ordering::SeqCst) {
-                if start.elapsed() > timeout {
-                    eprintln!("Timeout waiting for response");
-                    break;
-                }
-                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        if let Some(run_id) = run_id {
+            if let Err(e) = wait_for_chat_response(&runs, &run_id, timeout).await {
+                print_partial_response(&e.partial);
+                return Err(e.into());
             }
         }
     } else {
@@ -135,23 +237,15 @@ pub(crate) async fn run_client(
                 continue;
             }
 
-            // Reset response flag
-            response_received.store(false, Ordering::SeqCst);
-
-            let was_command = send_chat(&gateway, session_key, line).await?;
-
-            // Only wait for chat event if this wasn't a command/directive
-            if !was_command {
-                // Wait for response (up to 120 seconds)
-                let timeout = tokio::time::Duration::from_secs(120);
-                let start = tokio::time::Instant::now();
+            let run_id = send_chat(&gateway, session_key, line).await?;
 
-                while !response_received.load(Ordering::SeqCst) {
-                    if start.elapsed() > timeout {
-                        eprintln!("Timeout waiting for response");
-                        break;
-                    }
-                    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            // Only wait for chat event if this wasn't a command/directive.
+            // The timeout is per message, and a timeout only fails this
+            // message; the session stays open.
+            if let Some(run_id) = run_id {
+                if let Err(e) = wait_for_chat_response(&runs, &run_id, timeout).await {
+                    print_partial_response(&e.partial);
+                    eprintln!("Error: {}", e);
                 }
             }
 
@@ -163,16 +257,177 @@ pub(crate) async fn run_client(
     Ok(())
 }
 
+/// Chat events seen by `run_client`, keyed by run ID so a late `final` from
+/// a message that timed out cannot complete the next message's wait.
+#[derive(Default)]
+struct ChatRuns {
+    finished: HashSet<String>,
+    /// Text of `partial` messages (sent before tool calls), shown on timeout
+    partial: HashMap<String, String>,
+}
+
+#[derive(Debug)]
+struct ChatResponseTimeout {
+    timeout: tokio::time::Duration,
+    /// Partial text the run produced before the timeout
+    partial: String,
+}
+
+impl std::fmt::Display for ChatResponseTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Timed out after {}s waiting for a response (--timeout / GSV_CLIENT_TIMEOUT)",
+            self.timeout.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for ChatResponseTimeout {}
+
+/// Waits until the chat subscription sees a final/error event for `run_id`.
+async fn wait_for_chat_response(
+    runs: &Mutex<ChatRuns>,
+    run_id: &str,
+    timeout: tokio::time::Duration,
+) -> Result<(), ChatResponseTimeout> {
+    let start = tokio::time::Instant::now();
+    loop {
+        if let Ok(mut runs) = runs.lock() {
+            if runs.finished.remove(run_id) {
+                runs.partial.remove(run_id);
+                return Ok(());
+            }
+            if start.elapsed() > timeout {
+                return Err(ChatResponseTimeout {
+                    timeout,
+                    partial: runs.partial.remove(run_id).unwrap_or_default(),
+                });
+            }
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+}
+
+fn print_partial_response(partial: &str) {
+    if !partial.trim().is_empty() {
+        println!("\nPartial response: {}", partial);
+    }
+}
+
+/// Fields worth calling out when debugging delivery, shown even when absent.
+const HEARTBEAT_DETAIL_FIELDS: &[&str] = &[
+    "intervalMinutes",
+    "consecutiveFailures",
+    "deliveryChannel",
+    "deliveryTarget",
+    "lastHeartbeatPayload",
+];
+
+fn render_heartbeat_agent_detail(agent_id: &str, state: &serde_json::Value) -> String {
+    let mut out = format!("Agent: {}\n", agent_id);
+
+    let Some(fields) = state.as_object() else {
+        out.push_str(&format!(
+            "  {}\n",
+            truncate_for_display(&state.to_string(), 200)
+        ));
+        return out;
+    };
+
+    let mut keys: Vec<&String> = fields.keys().collect();
+    keys.sort();
+    for key in keys {
+        let value = &fields[key];
+        let rendered = match value {
+            serde_json::Value::Number(n) if key.ends_with("At") => n
+                .as_i64()
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .map(|dt| format!("{} ({})", dt.format("%Y-%m-%d %H:%M:%S"), n))
+                .unwrap_or_else(|| n.to_string()),
+            serde_json::Value::String(text) => truncate_for_display(text, 200),
+            serde_json::Value::Null => "-".to_string(),
+            other => truncate_for_display(&other.to_string(), 200),
+        };
+        out.push_str(&format!("  {}: {}\n", key, rendered));
+    }
+
+    for key in HEARTBEAT_DETAIL_FIELDS {
+        if !fields.contains_key(*key) {
+            out.push_str(&format!("  {}: (not reported by gateway)\n", key));
+        }
+    }
+    out
+}
+
+const MAX_DISCORD_GUILDS_SHOWN: usize = 20;
+
+fn print_discord_guilds(payload: &serde_json::Value) {
+    let guilds = payload
+        .get("guilds")
+        .and_then(|g| g.as_array())
+        .map(|g| g.as_slice())
+        .unwrap_or(&[]);
+
+    println!("\nGuilds ({}):", guilds.len());
+    if guilds.is_empty() {
+        println!("  (bot is not a member of any guild; check the invite URL)");
+        return;
+    }
+
+    for guild in guilds.iter().take(MAX_DISCORD_GUILDS_SHOWN) {
+        let name = guild.get("name").and_then(|n| n.as_str()).unwrap_or("?");
+        let id = guild.get("id").and_then(|i| i.as_str()).unwrap_or("?");
+        let members = guild
+            .get("memberCount")
+            .and_then(|m| m.as_u64())
+            .map(|m| m.to_string())
+            .unwrap_or_else(|| "?".to_string());
+        let commands = match guild.get("commandsRegistered").and_then(|c| c.as_bool()) {
+            Some(true) => "yes",
+            Some(false) => "no",
+            None => "unknown",
+        };
+        println!(
+            "  {} ({})  members: {}  slash commands: {}",
+            name, id, members, commands
+        );
+    }
+    if guilds.len() > MAX_DISCORD_GUILDS_SHOWN {
+        println!("  ... and {} more", guilds.len() - MAX_DISCORD_GUILDS_SHOWN);
+    }
+}
+
 pub(crate) async fn run_heartbeat(
     url: &str,
     token: Option<String>,
     action: HeartbeatAction,
+    output: OutputFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let client = GatewayClient::connect(url, token).await?;
 
     match action {
-        HeartbeatAction::Status => {
+        HeartbeatAction::Status {
+            agent_id: Some(agent_id),
+        } => {
+            let payload = client.heartbeat_status().await?;
+            let state = payload
+                .get("agents")
+                .and_then(|a| a.get(&agent_id))
+                .ok_or_else(|| format!("No heartbeat state for agent '{}'", agent_id))?;
+            if output == OutputFormat::Json {
+                print_json_output(json!({ "agents": { agent_id: state } }))?;
+            } else {
+                print!("{}", render_heartbeat_agent_detail(&agent_id, state));
+            }
+        }
+
+        HeartbeatAction::Status { agent_id: None } => {
             let payload = client.heartbeat_status().await?;
+            if output == OutputFormat::Json {
+                print_json_output(payload)?;
+                return Ok(());
+            }
 
             if let Some(agents) = payload.get("agents").and_then(|a| a.as_object()) {
                 if agents.is_empty() {
@@ -198,6 +453,17 @@ pub(crate) async fn run_heartbeat(
                             }
                         }
 
+                        if let Some(pinned) = state.get("pinnedDelivery") {
+                            let channel = pinned.get("channel").and_then(|c| c.as_str());
+                            let peer_id = pinned
+                                .get("peer")
+                                .and_then(|p| p.get("id"))
+                                .and_then(|i| i.as_str());
+                            if let (Some(channel), Some(peer_id)) = (channel, peer_id) {
+                                println!("    Pinned delivery: {} -> {}", channel, peer_id);
+                            }
+                        }
+
                         if let Some(last_active) = state.get("lastActive") {
                             if let Some(channel) =
                                 last_active.get("channel").and_then(|c| c.as_str())
@@ -262,6 +528,28 @@ pub(crate) async fn run_heartbeat(
                 println!("{}", msg);
             }
         }
+
+        HeartbeatAction::SetDelivery {
+            agent_id,
+            channel,
+            peer_id,
+            account_id,
+            peer_kind,
+        } => {
+            client
+                .heartbeat_set_delivery(
+                    agent_id.clone(),
+                    channel.clone(),
+                    account_id.clone(),
+                    peer_kind,
+                    peer_id.clone(),
+                )
+                .await?;
+            println!(
+                "Heartbeats for agent '{}' will be delivered to {}:{} (account: {})",
+                agent_id, channel, peer_id, account_id
+            );
+        }
     }
 
     Ok(())
@@ -271,12 +559,41 @@ pub(crate) async fn run_pair(
     url: &str,
     token: Option<String>,
     action: PairAction,
+    output: OutputFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let client = GatewayClient::connect(url, token).await?;
 
     match action {
         PairAction::List => {
             let payload = client.pair_list().await?;
+            if output == OutputFormat::Json {
+                print_json_output(payload)?;
+                return Ok(());
+            }
+            let auto_approve = payload
+                .get("autoApprove")
+                .and_then(|a| a.as_array())
+                .map(|channels| {
+                    channels
+                        .iter()
+                        .filter_map(|c| c.as_str())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            let badge = |channel: &str| {
+                if auto_approve.contains(&channel) {
+                    " [AUTO-APPROVE]"
+                } else {
+                    ""
+                }
+            };
+
+            if !auto_approve.is_empty() {
+                for channel in &auto_approve {
+                    println!("{}{}", channel, badge(channel));
+                }
+                println!();
+            }
 
             if let Some(pairs) = payload.get("pairs").and_then(|p| p.as_object()) {
                 if pairs.is_empty() {
@@ -305,7 +622,13 @@ pub(crate) async fn run_pair(
                         {
                             let dt = chrono::DateTime::from_timestamp_millis(requested_at);
                             if let Some(dt) = dt {
-                                println!("  {} ({}) via {}", sender_name, sender_id, channel);
+                                println!(
+                                    "  {} ({}) via {}{}",
+                                    sender_name,
+                                    sender_id,
+                                    channel,
+                                    badge(channel)
+                                );
                                 println!("    Requested: {}", dt.format("%Y-%m-%d %H:%M:%S"));
                                 if !first_msg.is_empty() {
                                     println!("    Message: \"{}\"", first_msg);
@@ -348,6 +671,76 @@ pub(crate) async fn run_pair(
             client.pair_reject(channel, sender_id).await?;
             println!("Rejected request removed");
         }
+
+        PairAction::AutoApprove {
+            channel,
+            pattern: None,
+            list: true,
+            ..
+        } => {
+            let payload = client.pair_auto_approve_list(channel.clone()).await?;
+            if output == OutputFormat::Json {
+                print_json_output(payload)?;
+                return Ok(());
+            }
+            let rules: Vec<&str> = payload
+                .pointer(&format!("/rules/{}", channel))
+                .and_then(|r| r.as_array())
+                .map(|rules| rules.iter().filter_map(|r| r.as_str()).collect())
+                .unwrap_or_default();
+            if rules.is_empty() {
+                println!("No auto-approve rules for {}", channel);
+            } else {
+                println!("Auto-approve rules for {}:", channel);
+                for rule in rules {
+                    println!("  {}", rule);
+                }
+            }
+        }
+
+        PairAction::AutoApprove {
+            channel,
+            pattern: Some(pattern),
+            remove,
+            ..
+        } => {
+            validate_auto_approve_pattern(&pattern)?;
+            if remove {
+                let payload = client
+                    .pair_auto_approve_remove(channel.clone(), pattern.clone())
+                    .await?;
+                if payload.get("removed").and_then(|r| r.as_bool()) == Some(true) {
+                    println!("Removed auto-approve rule {} for {}", pattern, channel);
+                } else {
+                    println!("No auto-approve rule {} for {}", pattern, channel);
+                }
+            } else {
+                client
+                    .pair_auto_approve_add(channel.clone(), pattern.clone())
+                    .await?;
+                println!(
+                    "Added auto-approve rule {} for {} - matching senders are approved automatically",
+                    pattern, channel
+                );
+            }
+        }
+
+        PairAction::AutoApprove {
+            channel, enable, ..
+        } => {
+            client.pair_auto_approve(channel.clone(), enable).await?;
+            if enable {
+                println!(
+                    "Auto-approve enabled for {} - new pairing requests are approved automatically",
+                    channel
+                );
+            } else {
+                println!(
+                    "Auto-approve disabled for {} - pairing requests need manual approval",
+                    channel
+                );
+            }
+        }
     }
 
     Ok(())
@@ -358,21 +751,29 @@ pub(crate) async fn run_channel(
     url: &str,
     token: Option<String>,
     _cfg: &gsv::config::CliConfig,
+    output: OutputFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
     match action {
         ChannelAction::Whatsapp { action } => run_whatsapp_via_gateway(url, token, action).await,
         ChannelAction::Discord { action } => run_discord_via_gateway(url, token, action).await,
-        ChannelAction::List => run_channels_list(url, token).await,
+        ChannelAction::Telegram { action } => run_telegram_via_gateway(url, token, action).await,
+        ChannelAction::Slack { action } => run_slack_via_gateway(url, token, action).await,
+        ChannelAction::List => run_channels_list(url, token, output).await,
     }
 }
 
 pub(crate) async fn run_channels_list(
     url: &str,
     token: Option<String>,
+    output: OutputFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let client = GatewayClient::connect(url, token).await?;
 
     let payload = client.channels_list().await?;
+    if output == OutputFormat::Json {
+        print_json_output(payload)?;
+        return Ok(());
+    }
 
     if let Some(channels) = payload.get("channels").and_then(|c| c.as_array()) {
         if channels.is_empty() {
@@ -413,7 +814,7 @@ pub(crate) async fn run_whatsapp_via_gateway(
     let client = GatewayClient::connect(url, token).await?;
 
     match action {
-        WhatsAppAction::Login { account_id } => {
+        WhatsAppAction::Login { account_id, save } => {
             println!("Logging in to WhatsApp account: {}", account_id);
 
             let payload = client
@@ -421,6 +822,13 @@ pub(crate) async fn run_whatsapp_via_gateway(
                 .await?;
 
             if let Some(qr_data_url) = payload.get("qrDataUrl").and_then(|q| q.as_str()) {
+                if let Some(path) = save {
+                    save_qr_png(qr_data_url, &path)?;
+                    println!("QR code saved to {}", path.display());
+                    println!("Scan it with WhatsApp within ~20 seconds. Re-run command if needed.");
+                    return Ok(());
+                }
+
                 // qrDataUrl is a data URL, extract the QR data
                 println!("\nScan this QR code with WhatsApp:\n");
 
@@ -497,6 +905,265 @@ pub(crate) async fn run_whatsapp_via_gateway(
                 .await?;
             println!("Stopped.");
         }
+
+        WhatsAppAction::Send {
+            account_id,
+            to,
+            message,
+            media_url,
+        } => {
+            validate_whatsapp_number(&to)?;
+            if message.is_empty() && media_url.is_none() {
+                return Err("Provide --message and/or --media-url".into());
+            }
+            client
+                .channel_send(
+                    "whatsapp".to_string(),
+                    account_id,
+                    json!({ "kind": "dm", "id": to }),
+                    message,
+                    media_url.as_deref().map(channel_media_from_url),
+                    None,
+                )
+                .await?;
+            println!("Message sent to {}", to);
+        }
+    }
+
+    Ok(())
+}
+
+/// Auto-approve patterns are globs ("+1555*") or regexes wrapped in slashes
+/// ("/^\+1555\d+$/"); the gateway matches them against sender IDs.
+fn validate_auto_approve_pattern(pattern: &str) -> Result<(), String> {
+    if pattern.trim().is_empty() || pattern.chars().any(char::is_whitespace) {
+        return Err(format!(
+            "Invalid pattern '{}': must be non-empty and contain no whitespace",
+            pattern
+        ));
+    }
+    if let Some(regex) = pattern
+        .strip_prefix('/')
+        .and_then(|rest| rest.strip_suffix('/'))
+        .filter(|regex| !regex.is_empty())
+    {
+        check_regex_structure(regex).map_err(|e| format!("Invalid regex '{}': {}", regex, e))?;
+    }
+    Ok(())
+}
+
+/// Catches obviously malformed regexes (unbalanced groups or classes, a
+/// trailing escape) without rejecting JavaScript-only syntax such as
+/// lookbehind; the gateway's engine has the final say.
+fn check_regex_structure(regex: &str) -> Result<(), &'static str> {
+    let mut depth = 0usize;
+    let mut in_class = false;
+    let mut chars = regex.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.next().is_none() => return Err("trailing backslash"),
+            '[' if !in_class => in_class = true,
+            ']' if in_class => in_class = false,
+            '(' if !in_class => depth += 1,
+            ')' if !in_class => {
+                depth = depth.checked_sub(1).ok_or("unmatched ')'")?;
+            }
+            _ => {}
+        }
+    }
+    if in_class {
+        return Err("unterminated character class");
+    }
+    if depth > 0 {
+        return Err("unclosed group");
+    }
+    Ok(())
+}
+
+/// WhatsApp recipients must be E.164 numbers: "+" followed by 7-15 digits.
+fn validate_whatsapp_number(to: &str) -> Result<(), String> {
+    let digits = to
+        .strip_prefix('+')
+        .ok_or_else(|| format!("'{}' must start with + and a country code", to))?;
+    if !(7..=15).contains(&digits.len()) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!(
+            "'{}' is not a valid phone number (expected e.g. +1234567890)",
+            to
+        ));
+    }
+    Ok(())
+}
+
+/// Builds a channel media attachment for `url`, inferring the type from its extension.
+fn channel_media_from_url(url: &str) -> serde_json::Value {
+    let filename = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("attachment");
+    let extension = filename
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    let (kind, mime_type) = match extension.as_str() {
+        "png" => ("image", "image/png"),
+        "jpg" | "jpeg" => ("image", "image/jpeg"),
+        "gif" => ("image", "image/gif"),
+        "webp" => ("image", "image/webp"),
+        "pdf" => ("document", "application/pdf"),
+        "txt" => ("document", "text/plain"),
+        "csv" => ("document", "text/csv"),
+        _ => ("document", "application/octet-stream"),
+    };
+    json!({
+        "type": kind,
+        "mimeType": mime_type,
+        "url": url,
+        "filename": filename,
+    })
+}
+
+pub(crate) async fn run_telegram_via_gateway(
+    url: &str,
+    token: Option<String>,
+    action: TelegramAction,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let client = GatewayClient::connect(url, token).await?;
+
+    match action {
+        TelegramAction::Start { account_id } => {
+            println!("Starting Telegram bot account: {}", account_id);
+            client
+                .channel_start("telegram".to_string(), account_id)
+                .await?;
+            println!("Telegram bot started successfully.");
+            println!(
+                "\nThe bot will connect using the TELEGRAM_BOT_TOKEN configured on the channel worker."
+            );
+        }
+
+        TelegramAction::Status { account_id } => {
+            let payload = client
+                .channel_status("telegram".to_string(), account_id)
+                .await?;
+
+            let accounts = payload
+                .get("accounts")
+                .and_then(|a| a.as_array())
+                .map(|a| a.as_slice())
+                .unwrap_or(&[]);
+            if accounts.is_empty() {
+                println!("No Telegram accounts found");
+            }
+            for acc in accounts {
+                let acc_id = acc.get("accountId").and_then(|a| a.as_str()).unwrap_or("?");
+                let flag = |key: &str| acc.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+
+                println!("Telegram account: {}", acc_id);
+                println!("  Connected: {}", flag("connected"));
+                println!("  Authenticated: {}", flag("authenticated"));
+                if let Some(error) = acc.get("error").and_then(|e| e.as_str()) {
+                    println!("  Error: {}", error);
+                }
+                if let Some(username) = acc
+                    .get("extra")
+                    .and_then(|e| e.get("botUser"))
+                    .and_then(|b| b.get("username"))
+                    .and_then(|u| u.as_str())
+                {
+                    println!("  Bot username: @{}", username);
+                }
+                if let Some(last) = acc.get("lastActivity").and_then(|t| t.as_i64()) {
+                    if let Some(dt) = chrono::DateTime::from_timestamp_millis(last) {
+                        println!("  Last activity: {}", dt.format("%Y-%m-%d %H:%M:%S"));
+                    }
+                }
+            }
+        }
+
+        TelegramAction::Stop { account_id } => {
+            println!("Stopping Telegram bot account: {}", account_id);
+            client
+                .channel_stop("telegram".to_string(), account_id)
+                .await?;
+            println!("Stopped.");
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn run_slack_via_gateway(
+    url: &str,
+    token: Option<String>,
+    action: SlackAction,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let client = GatewayClient::connect(url, token).await?;
+
+    match action {
+        SlackAction::Start { account_id } => {
+            println!("Starting Slack bot account: {}", account_id);
+            client
+                .channel_start("slack".to_string(), account_id)
+                .await?;
+            println!("Slack bot started successfully.");
+            println!(
+                "\nThe bot will connect using the SLACK_BOT_TOKEN configured on the channel worker."
+            );
+        }
+
+        SlackAction::Status { account_id } => {
+            let payload = client
+                .channel_status("slack".to_string(), account_id)
+                .await?;
+
+            let accounts = payload
+                .get("accounts")
+                .and_then(|a| a.as_array())
+                .map(|a| a.as_slice())
+                .unwrap_or(&[]);
+            if accounts.is_empty() {
+                println!("No Slack accounts found");
+            }
+            for acc in accounts {
+                let acc_id = acc.get("accountId").and_then(|a| a.as_str()).unwrap_or("?");
+                let flag = |key: &str| acc.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+
+                println!("Slack account: {}", acc_id);
+                println!("  Connected: {}", flag("connected"));
+                println!("  Authenticated: {}", flag("authenticated"));
+                if let Some(error) = acc.get("error").and_then(|e| e.as_str()) {
+                    println!("  Error: {}", error);
+                }
+                if let Some(username) = acc
+                    .get("extra")
+                    .and_then(|e| e.get("botUser"))
+                    .and_then(|b| b.get("username"))
+                    .and_then(|u| u.as_str())
+                {
+                    println!("  Bot username: {}", username);
+                }
+                if let Some(team) = acc
+                    .get("extra")
+                    .and_then(|e| e.get("team"))
+                    .and_then(|t| t.as_str())
+                {
+                    println!("  Workspace: {}", team);
+                }
+                if let Some(last) = acc.get("lastActivity").and_then(|t| t.as_i64()) {
+                    if let Some(dt) = chrono::DateTime::from_timestamp_millis(last) {
+                        println!("  Last activity: {}", dt.format("%Y-%m-%d %H:%M:%S"));
+                    }
+                }
+            }
+        }
+
+        SlackAction::Stop { account_id } => {
+            println!("Stopping Slack bot account: {}", account_id);
+            client.channel_stop("slack".to_string(), account_id).await?;
+            println!("Stopped.");
+        }
     }
 
     Ok(())
@@ -522,9 +1189,9 @@ pub(crate) async fn run_discord_via_gateway(
             );
         }
 
-        DiscordAction::Status { account_id } => {
+        DiscordAction::Status { account_id, guilds } => {
             let payload = client
-                .channel_status("discord".to_string(), account_id)
+                .channel_status("discord".to_string(), account_id.clone())
                 .await?;
 
             if let Some(accounts) = payload.get("accounts").and_then(|a| a.as_array()) {
@@ -571,6 +1238,13 @@ pub(crate) async fn run_discord_via_gateway(
                     }
                 }
             }
+
+            if guilds {
+                match client.channel_discord_guilds(account_id).await {
+                    Ok(payload) => print_discord_guilds(&payload),
+                    Err(e) => eprintln!("\nCould not list guilds: {}", e),
+                }
+            }
         }
 
         DiscordAction::Stop { account_id } => {
@@ -580,57 +1254,446 @@ pub(crate) async fn run_discord_via_gateway(
                 .await?;
             println!("Stopped.");
         }
+        DiscordAction::Send {
+            account_id,
+            channel_id,
+            message,
+            embed,
+        } => {
+            validate_discord_channel_id(&channel_id)?;
+            let embed = embed.as_deref().map(parse_discord_embed).transpose()?;
+            if message.is_empty() && embed.is_none() {
+                return Err("Provide --message and/or --embed".into());
+            }
+            client
+                .channel_send(
+                    "discord".to_string(),
+                    account_id,
+                    json!({ "kind": "channel", "id": channel_id }),
+                    message,
+                    None,
+                    embed,
+                )
+                .await?;
+            println!("Message sent to channel #{}", channel_id);
+        }
     }
 
     Ok(())
 }
 
+/// Discord channel IDs are snowflakes: numeric strings.
+fn validate_discord_channel_id(channel_id: &str) -> Result<(), String> {
+    if channel_id.is_empty() || !channel_id.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!(
+            "'{}' is not a Discord channel ID (expected digits, e.g. 123456789012345678)",
+            channel_id
+        ));
+    }
+    Ok(())
+}
+
+fn parse_discord_embed(raw: &str) -> Result<serde_json::Value, String> {
+    let embed: serde_json::Value =
+        serde_json::from_str(raw).map_err(|e| format!("Invalid --embed JSON: {}", e))?;
+    if !embed.is_object() {
+        return Err("--embed must be a JSON object".to_string());
+    }
+    Ok(embed)
+}
+
 pub(crate) async fn run_config(
     url: &str,
     token: Option<String>,
     action: ConfigAction,
+    output: OutputFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let client = GatewayClient::connect(url, token).await?;
 
     match action {
-        ConfigAction::Get { path } => {
-            let payload = client.config_get(path).await?;
-            println!("{}", serde_json::to_string_pretty(&payload)?);
-        }
-        ConfigAction::Set { path, value } => {
-            // Try to parse value as JSON, fall back to string
-            let parsed_value: serde_json::Value = serde_json::from_str(&value)
-                .unwrap_or_else(|_| serde_json::Value::String(value.clone()));
-            client.config_set(path.clone(), parsed_value).await?;
-            println!("Set {} successfully", path);
+        ConfigAction::Get {
+            keys_only: true, ..
+        } => {
+            let payload = client.config_get(None).await?;
+            let mut leaves = Vec::new();
+            collect_config_leaves(payload.get("config").unwrap_or(&payload), "", &mut leaves);
+            let keys = leaves.into_iter().map(|(path, _)| path);
+            if output == OutputFormat::Json {
+                print_json_output(json!({ "keys": keys.collect::<Vec<_>>() }))?;
+            } else {
+                for path in keys {
+                    println!("{}", path);
+                }
+            }
+        }
+        ConfigAction::Get {
+            path: None,
+            suggest: _,
+            ..
+        } => {
+            let payload = client.config_get(None).await?;
+            if output == OutputFormat::Json {
+                print_json_output(payload)?;
+                return Ok(());
+            }
+            let config = payload.get("config").unwrap_or(&payload);
+            print!("{}", render_config_tree(config));
+        }
+        ConfigAction::Get {
+            path: Some(path),
+            suggest,
+            ..
+        } => {
+            let payload = client.config_get(Some(path.clone())).await?;
+            if payload.get("value").is_some_and(|v| !v.is_null()) {
+                if output == OutputFormat::Json {
+                    print_json_output(payload)?;
+                } else {
+                    println!("{}", serde_json::to_string_pretty(&payload)?);
+                }
+            } else {
+                let mut message = format!("No config value at '{}'", path);
+                if suggest {
+                    let full = client.config_get(None).await?;
+                    let mut known = Vec::new();
+                    collect_config_paths(full.get("config").unwrap_or(&full), "", &mut known);
+                    if let Some(candidate) = closest_config_path(&path, &known) {
+                        message.push_str(&format!(" (did you mean: {}?)", candidate));
+                    }
+                }
+                return Err(message.into());
+            }
+        }
+        ConfigAction::Set {
+            from_file: Some(file),
+            force,
+            ..
+        } => {
+            let raw = if file.as_os_str() == "-" {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                input
+            } else {
+                std::fs::read_to_string(&file)
+                    .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?
+            };
+            let parsed: serde_json::Value = serde_json::from_str(&raw)
+                .map_err(|e| format!("Invalid JSON in {}: {}", file.display(), e))?;
+            if !parsed.is_object() {
+                return Err(format!("{} must contain a JSON object", file.display()).into());
+            }
+
+            let mut leaves = Vec::new();
+            collect_config_leaves(&parsed, "", &mut leaves);
+            let mut failed = Vec::new();
+            for (path, value) in leaves.iter() {
+                let result = match validate_config_value(path, value) {
+                    Err(e) if !force => Err(e),
+                    _ => client
+                        .config_set(path.clone(), value.clone())
+                        .await
+                        .map_err(|e| e.to_string()),
+                };
+                match result {
+                    Ok(_) => println!("Set {}", path),
+                    Err(e) => {
+                        eprintln!("Failed to set {}: {}", path, e);
+                        failed.push(path.as_str());
+                    }
+                }
+            }
+
+            println!(
+                "\n{} of {} key(s) set.",
+                leaves.len() - failed.len(),
+                leaves.len()
+            );
+            if !failed.is_empty() {
+                return Err(format!("Failed to set: {}", failed.join(", ")).into());
+            }
+        }
+
+        ConfigAction::Set {
+            path, value, force, ..
+        } => {
+            let (Some(path), Some(value)) = (path, value) else {
+                return Err("config set requires <PATH> <VALUE> or --from-file".into());
+            };
+            // Try to parse value as JSON, fall back to string
+            let parsed_value: serde_json::Value = serde_json::from_str(&value)
+                .unwrap_or_else(|_| serde_json::Value::String(value.clone()));
+            if !force {
+                validate_config_value(&path, &parsed_value)
+                    .map_err(|e| format!("{} (use --force to set it anyway)", e))?;
+            }
+            client.config_set(path.clone(), parsed_value).await?;
+            println!("Set {} successfully", path);
+        }
+
+        ConfigAction::Diff => {
+            let local = gsv::config::CliConfig::load();
+            let payload = client.config_get(None).await?;
+            let config = payload.get("config").unwrap_or(&payload);
+            let mut rows = Vec::new();
+            for (path, local_key) in CONFIG_DIFF_KEYS {
+                let secret = path.ends_with("token");
+                // The full config masks secrets; read those paths directly.
+                let gateway = if secret {
+                    client
+                        .config_get(Some(path.to_string()))
+                        .await?
+                        .get("value")
+                        .cloned()
+                } else {
+                    config
+                        .pointer(&format!("/{}", path.replace('.', "/")))
+                        .cloned()
+                };
+                rows.push(ConfigDiffRow {
+                    path,
+                    local: local_key.map(|key| match key {
+                        "gateway.token" => local.gateway.token.clone(),
+                        _ => None,
+                    }),
+                    gateway: gateway.and_then(|value| match value {
+                        serde_json::Value::Null => None,
+                        serde_json::Value::String(s) => Some(s),
+                        other => Some(other.to_string()),
+                    }),
+                    secret,
+                });
+            }
+            if output == OutputFormat::Json {
+                print_json_output(config_diff_json(&rows))?;
+            } else {
+                print!("{}", render_config_diff(&rows));
+            }
         }
     }
 
     Ok(())
 }
 
+/// Config keys whose values must be positive integers, wherever they appear.
+const POSITIVE_INTEGER_CONFIG_KEYS: &[&str] = &[
+    "maxTokens",
+    "reserveTokens",
+    "keepRecentTokens",
+    "maxJobs",
+    "maxRunsPerJobHistory",
+    "maxConcurrentRuns",
+    "llmMs",
+    "toolMs",
+    "skillProbeMaxAgeMs",
+];
+
+/// Client-side sanity checks for well-known config paths. Unknown paths pass.
+fn validate_config_value(path: &str, value: &serde_json::Value) -> Result<(), String> {
+    let key = path.rsplit('.').next().unwrap_or(path);
+    match path {
+        "model.provider"
+            if !value
+                .as_str()
+                .and_then(crate::normalize_llm_provider)
+                .is_some_and(|provider| crate::is_builtin_llm_provider(&provider)) =>
+        {
+            Err(format!(
+                "Invalid model.provider {}. Expected one of anthropic, openai, google, openrouter (e.g. gsv config set model.provider anthropic)",
+                value
+            ))
+        }
+        "auth.token" if value.as_str().is_none_or(|token| token.trim().is_empty()) => Err(
+            "auth.token must be a non-empty string (e.g. gsv config set auth.token \"my-secret\")"
+                .to_string(),
+        ),
+        _ if POSITIVE_INTEGER_CONFIG_KEYS.contains(&key)
+            && value.as_u64().is_none_or(|n| n == 0) =>
+        {
+            Err(format!(
+                "{} must be a positive integer, got {} (e.g. gsv config set {} 4096)",
+                path, value, path
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Renders a config object as an indented `key: value` tree.
+fn render_config_tree(config: &serde_json::Value) -> String {
+    fn render(value: &serde_json::Value, indent: usize, out: &mut String) {
+        let pad = "  ".repeat(indent);
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, child) in map {
+                    match child {
+                        serde_json::Value::Object(inner) if !inner.is_empty() => {
+                            let _ = writeln!(out, "{}{}:", pad, key);
+                            render(child, indent + 1, out);
+                        }
+                        serde_json::Value::Array(items)
+                            if items.iter().any(|i| i.is_object() || i.is_array()) =>
+                        {
+                            let _ = writeln!(out, "{}{}:", pad, key);
+                            render(child, indent + 1, out);
+                        }
+                        _ => {
+                            let _ = writeln!(out, "{}{}: {}", pad, key, scalar(child));
+                        }
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    if item.is_object() || item.is_array() {
+                        let _ = writeln!(out, "{}-", pad);
+                        render(item, indent + 1, out);
+                    } else {
+                        let _ = writeln!(out, "{}- {}", pad, scalar(item));
+                    }
+                }
+            }
+            other => {
+                let _ = writeln!(out, "{}{}", pad, scalar(other));
+            }
+        }
+    }
+
+    fn scalar(value: &serde_json::Value) -> String {
+        match value {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Array(items) => format!(
+                "[{}]",
+                items.iter().map(scalar).collect::<Vec<_>>().join(", ")
+            ),
+            serde_json::Value::Object(_) => "{}".to_string(),
+            other => other.to_string(),
+        }
+    }
+
+    let mut out = String::new();
+    render(config, 0, &mut out);
+    out
+}
+
+/// Collects every dot path (intermediate and leaf) in a config object.
+fn collect_config_paths(value: &serde_json::Value, prefix: &str, out: &mut Vec<String>) {
+    if let Some(map) = value.as_object() {
+        for (key, child) in map {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+            out.push(path.clone());
+            collect_config_paths(child, &path, out);
+        }
+    }
+}
+
+/// Flattens nested objects into `(dot.path, value)` leaves; arrays, scalars
+/// and empty objects are set as whole values.
+fn collect_config_leaves(
+    value: &serde_json::Value,
+    prefix: &str,
+    out: &mut Vec<(String, serde_json::Value)>,
+) {
+    match value.as_object() {
+        Some(map) if !map.is_empty() => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                collect_config_leaves(child, &path, out);
+            }
+        }
+        _ if !prefix.is_empty() => out.push((prefix.to_string(), value.clone())),
+        _ => {}
+    }
+}
+
+/// Parses tool call arguments read from `source` (stdin or a file); empty
+/// input means no arguments.
+fn parse_tool_call_args(input: &str, source: &str) -> Result<serde_json::Value, String> {
+    if input.trim().is_empty() {
+        return Ok(json!({}));
+    }
+    let value: serde_json::Value = serde_json::from_str(input)
+        .map_err(|e| format!("Invalid JSON args on {}: {}", source, e))?;
+    if !value.is_object() {
+        return Err(format!("Args on {} must be a JSON object", source));
+    }
+    Ok(value)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b_chars.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b_chars.len() + 1];
+        for (j, cb) in b_chars.iter().enumerate() {
+            let cost = usize::from(!ca.eq_ignore_ascii_case(cb));
+            current[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        prev = current;
+    }
+    prev[b_chars.len()]
+}
+
+/// Picks the known path closest to `path`, if it is close enough to be a likely typo.
+fn closest_config_path<'a>(path: &str, known: &'a [String]) -> Option<&'a str> {
+    let max_distance = (path.chars().count() / 3).max(2);
+    known
+        .iter()
+        .map(|candidate| (edit_distance(path, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
 pub(crate) async fn run_tools(
     url: &str,
     token: Option<String>,
     action: ToolsAction,
+    output: OutputFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let client = GatewayClient::connect(url, token).await?;
 
     match action {
-        ToolsAction::List => {
-            let payload = client.tools_list().await?;
-            if let Some(tools) = payload.get("tools").and_then(|t| t.as_array()) {
+        ToolsAction::List {
+            connected_nodes,
+            node,
+            verbose,
+        } => {
+            let mut payload = client.tools_list(node.clone()).await?;
+            // Older gateways ignore `nodeId`, so filter here as well.
+            if let (Some(node), Some(tools)) = (
+                node.as_deref(),
+                payload.get_mut("tools").and_then(|t| t.as_array_mut()),
+            ) {
+                tools.retain(|tool| tool.get("nodeId").and_then(|n| n.as_str()) == Some(node));
+            }
+            if output == OutputFormat::Json {
+                print_json_output(payload)?;
+            } else if let Some(tools) = payload.get("tools").and_then(|t| t.as_array()) {
                 if tools.is_empty() {
-                    println!("No tools available (is a node connected?)");
+                    match node.as_deref() {
+                        Some(node) => println!("No tools available from node '{}'", node),
+                        None => println!("No tools available (is a node connected?)"),
+                    }
+                } else if connected_nodes {
+                    for (node, node_tools) in group_tools_by_node(tools) {
+                        println!("Node: {} ({} tools):", node, node_tools.len());
+                        for tool in node_tools {
+                            print!("{}", render_tool_entry(tool, verbose));
+                        }
+                        println!();
+                    }
                 } else {
                     println!("Available tools ({}):", tools.len());
                     for tool in tools {
-                        let name = tool.get("name").and_then(|n| n.as_str()).unwrap_or("?");
-                        let desc = tool
-                            .get("description")
-                            .and_then(|d| d.as_str())
-                            .unwrap_or("");
-                        println!("  {} - {}", name, desc);
+                        print!("{}", render_tool_entry(tool, verbose));
                     }
                 }
             } else {
@@ -638,30 +1701,89 @@ pub(crate) async fn run_tools(
             }
         }
 
-        ToolsAction::Call { tool, args } => {
-            // Parse args as JSON
-            let args: serde_json::Value = serde_json::from_str(&args).map_err(|e| {
-                format!(
-                    "Invalid JSON args: {}. Expected format: '{{\"key\": \"value\"}}'",
-                    e
-                )
-            })?;
+        ToolsAction::Call {
+            tool,
+            args,
+            args_file,
+            timeout_seconds,
+            session,
+            watch_interval,
+            pretty_result,
+        } => {
+            // "-" (or no explicit args with piped input) reads a JSON object from stdin.
+            let args: serde_json::Value = if let Some(path) = args_file {
+                let input = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read args file {}: {}", path.display(), e))?;
+                parse_tool_call_args(&input, &format!("args file {}", path.display()))?
+            } else if args == "-" || (args == "{}" && !io::stdin().is_terminal()) {
+                eprintln!("Reading args from stdin...");
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("Failed to read args from stdin: {}", e))?;
+                parse_tool_call_args(&input, "stdin")?
+            } else {
+                // Parse args as JSON
+                serde_json::from_str(&args).map_err(|e| {
+                    format!(
+                        "Invalid JSON args: {}. Expected format: '{{\"key\": \"value\"}}'",
+                        e
+                    )
+                })?
+            };
+
+            let session_key = session.map(|session| config::normalize_session_key(&session));
 
             println!("Calling tool: {}", tool);
             println!("Args: {}", serde_json::to_string_pretty(&args)?);
+            if let Some(session_key) = &session_key {
+                println!("Session: {}", session_key);
+            }
             println!();
 
-            let payload = client.tool_invoke(tool.clone(), args).await?;
-            if let Some(result) = payload.get("result") {
-                println!("Result:");
-                // Try to print as pretty JSON, fall back to raw
-                if let Some(s) = result.as_str() {
-                    println!("{}", s);
-                } else {
-                    println!("{}", serde_json::to_string_pretty(result)?);
+            let color = io::stdout().is_terminal();
+            let mut previous: Option<String> = None;
+            loop {
+                let invoke = client.tool_invoke(tool.clone(), args.clone(), session_key.clone());
+                let payload = match timeout_seconds {
+                    Some(secs) => {
+                        match tokio::time::timeout(std::time::Duration::from_secs(secs), invoke)
+                            .await
+                        {
+                            Ok(result) => result?,
+                            Err(_) => {
+                                eprintln!("Tool call timed out after {}s", secs);
+                                std::process::exit(1);
+                            }
+                        }
+                    }
+                    None => invoke.await?,
+                };
+                let rendered = render_tool_result(&payload, pretty_result)?;
+                let record_error = session_key
+                    .as_deref()
+                    .and_then(|session_key| session_record_error(&payload, session_key));
+
+                let Some(interval) = watch_interval else {
+                    println!("{}", rendered);
+                    if let Some(error) = record_error {
+                        return Err(error.into());
+                    }
+                    break;
+                };
+
+                println!("[{}]", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"));
+                match previous.as_deref() {
+                    None => println!("{}", rendered),
+                    Some(prev) if prev == rendered => println!("(no change)"),
+                    Some(prev) => print!("{}", render_line_diff(prev, &rendered, color)),
                 }
-            } else {
-                println!("Result: {}", serde_json::to_string_pretty(&payload)?);
+                if let Some(error) = record_error {
+                    eprintln!("Warning: {}", error);
+                }
+                println!();
+                previous = Some(rendered);
+                tokio::time::sleep(std::time::Duration::from_secs(interval.max(1))).await;
             }
         }
     }
@@ -669,10 +1791,158 @@ pub(crate) async fn run_tools(
     Ok(())
 }
 
+/// The gateway reports `recorded: false` when the session refused to record a
+/// `--session` call, e.g. because a run was active; the tool itself still ran.
+fn session_record_error(payload: &serde_json::Value, session_key: &str) -> Option<String> {
+    if payload.get("recorded").and_then(|r| r.as_bool()) != Some(false) {
+        return None;
+    }
+    let reason = payload
+        .get("recordError")
+        .and_then(|e| e.as_str())
+        .unwrap_or("unknown reason");
+    Some(format!(
+        "Tool ran, but its result was not recorded in session '{}': {}",
+        session_key, reason
+    ))
+}
+
+fn render_tool_result(
+    payload: &serde_json::Value,
+    pretty: bool,
+) -> Result<String, serde_json::Error> {
+    Ok(match payload.get("result") {
+        // With --pretty-result, JSON-looking strings are parsed and re-indented
+        Some(serde_json::Value::String(s)) if pretty && s.trim_start().starts_with(['{', '[']) => {
+            match serde_json::from_str::<serde_json::Value>(s) {
+                Ok(parsed) => format!("Result:\n{}", serde_json::to_string_pretty(&parsed)?),
+                Err(_) => format!("Result:\n{}", s),
+            }
+        }
+        // Try to print as pretty JSON, fall back to raw
+        Some(serde_json::Value::String(s)) => format!("Result:\n{}", s),
+        Some(result) => format!("Result:\n{}", serde_json::to_string_pretty(result)?),
+        None => format!("Result: {}", serde_json::to_string_pretty(payload)?),
+    })
+}
+
+/// Gateway config paths `config diff` compares, with the local config key
+/// that mirrors each one (None when the CLI keeps no local copy).
+const CONFIG_DIFF_KEYS: &[(&str, Option<&str>)] = &[
+    ("auth.token", Some("gateway.token")),
+    ("model.provider", None),
+    ("model.id", None),
+];
+
+struct ConfigDiffRow {
+    path: &'static str,
+    /// None when the path has no local counterpart; Some(None) when it is unset
+    local: Option<Option<String>>,
+    gateway: Option<String>,
+    secret: bool,
+}
+
+impl ConfigDiffRow {
+    fn differs(&self) -> bool {
+        matches!(&self.local, Some(local) if *local != self.gateway)
+    }
+
+    fn display(&self, value: Option<&str>) -> String {
+        match value {
+            None => "(not set)".to_string(),
+            Some(value) if self.secret => {
+                let tail: String = value
+                    .chars()
+                    .rev()
+                    .take(4)
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .rev()
+                    .collect();
+                format!("****{}", tail)
+            }
+            Some(value) => value.to_string(),
+        }
+    }
+}
+
+fn render_config_diff(rows: &[ConfigDiffRow]) -> String {
+    let width = rows.iter().map(|row| row.path.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for row in rows {
+        let gateway = row.display(row.gateway.as_deref());
+        match &row.local {
+            None => {
+                let _ = writeln!(
+                    out,
+                    "  {:width$}  {} (gateway only)",
+                    row.path,
+                    gateway,
+                    width = width
+                );
+            }
+            Some(_) if !row.differs() => {
+                let _ = writeln!(out, "  {:width$}  {}", row.path, gateway, width = width);
+            }
+            Some(local) => {
+                let _ = writeln!(
+                    out,
+                    "~ {:width$}  [local] {}  [gateway] {}",
+                    row.path,
+                    row.display(local.as_deref()),
+                    gateway,
+                    width = width
+                );
+            }
+        }
+    }
+    let differing = rows.iter().filter(|row| row.differs()).count();
+    if differing == 0 {
+        out.push_str("\nLocal config matches the gateway.\n");
+    } else {
+        let _ = writeln!(out, "\n{} key(s) differ.", differing);
+    }
+    out
+}
+
+fn config_diff_json(rows: &[ConfigDiffRow]) -> serde_json::Value {
+    json!({
+        "keys": rows
+            .iter()
+            .map(|row| json!({
+                "path": row.path,
+                "local": row.local.as_ref().map(|local| row.display(local.as_deref())),
+                "gateway": row.display(row.gateway.as_deref()),
+                "differs": row.differs(),
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Line diff between two tool results; removed lines in red, added in green.
+fn render_line_diff(old: &str, new: &str, color: bool) -> String {
+    let mut out = String::new();
+    for change in similar::TextDiff::from_lines(old, new).iter_all_changes() {
+        let (sign, code) = match change.tag() {
+            similar::ChangeTag::Delete => ("-", "31"),
+            similar::ChangeTag::Insert => ("+", "32"),
+            similar::ChangeTag::Equal => (" ", ""),
+        };
+        let line = change.value().trim_end_matches('\n');
+        if color && !code.is_empty() {
+            let _ = writeln!(out, "\x1b[{}m{}{}\x1b[0m", code, sign, line);
+        } else {
+            let _ = writeln!(out, "{}{}", sign, line);
+        }
+    }
+    out
+}
+
 pub(crate) async fn run_skills(
     url: &str,
     token: Option<String>,
     action: SkillsAction,
+    output: OutputFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let client = GatewayClient::connect(url, token).await?;
     let (payload, is_update) = match action {
@@ -686,6 +1956,10 @@ pub(crate) async fn run_skills(
             true,
         ),
     };
+    if output == OutputFormat::Json && !is_update {
+        print_json_output(payload)?;
+        return Ok(());
+    }
     let agent_id = payload
         .get("agentId")
         .and_then(|v| v.as_str())
@@ -800,15 +2074,46 @@ pub(crate) async fn run_session(
     url: &str,
     token: Option<String>,
     action: SessionAction,
+    output: OutputFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let client = GatewayClient::connect(url, token).await?;
 
     match action {
-        SessionAction::List { limit } => {
+        SessionAction::List {
+            limit,
+            sort_by,
+            sort_desc,
+            no_fetch,
+        } => {
             let payload = client.sessions_list(limit).await?;
-            let sessions = payload.get("sessions").and_then(|s| s.as_array());
+            let mut sessions = payload.get("sessions").and_then(|s| s.as_array()).cloned();
             let count = payload.get("count").and_then(|c| c.as_i64()).unwrap_or(0);
-            if let Some(sessions) = sessions {
+            let needs_stats = matches!(sort_by.as_deref(), Some("message-count" | "token-usage"));
+            if let Some(sessions) = sessions.as_mut() {
+                if needs_stats && !no_fetch {
+                    for session in sessions.iter_mut() {
+                        let Some(key) = session.get("sessionKey").and_then(|k| k.as_str()) else {
+                            continue;
+                        };
+                        match client.session_stats(key.to_string()).await {
+                            Ok(stats) => session["stats"] = stats,
+                            Err(e) => {
+                                eprintln!("Warning: failed to fetch stats for {}: {}", key, e)
+                            }
+                        }
+                    }
+                }
+                if let Some(sort_by) = sort_by.as_deref().filter(|_| !needs_stats || !no_fetch) {
+                    sort_sessions(sessions, sort_by, sort_desc);
+                }
+            }
+            if output == OutputFormat::Json {
+                let mut payload = payload;
+                if let Some(sessions) = sessions {
+                    payload["sessions"] = json!(sessions);
+                }
+                print_json_output(payload)?;
+            } else if let Some(sessions) = sessions {
                 if sessions.is_empty() {
                     println!("No sessions found");
                 } else {
@@ -822,7 +2127,7 @@ pub(crate) async fn run_session(
                         let last_active = session.get("lastActiveAt").and_then(|t| t.as_i64());
 
                         let last_active_str = last_active
-                            .and_then(|ts| chrono::DateTime::from_timestamp_millis(ts))
+                            .and_then(chrono::DateTime::from_timestamp_millis)
                             .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
                             .unwrap_or_else(|| "?".to_string());
 
@@ -867,6 +2172,10 @@ pub(crate) async fn run_session(
         SessionAction::Get { session_key } => {
             let session_key = config::normalize_session_key(&session_key);
             let payload = client.session_get(session_key.clone()).await?;
+            if output == OutputFormat::Json {
+                print_json_output(payload)?;
+                return Ok(());
+            }
             println!("Session: {}", session_key);
             println!(
                 "  Session ID: {}",
@@ -935,6 +2244,10 @@ pub(crate) async fn run_session(
         SessionAction::Stats { session_key } => {
             let session_key = config::normalize_session_key(&session_key);
             let payload = client.session_stats(session_key.clone()).await?;
+            if output == OutputFormat::Json {
+                print_json_output(payload)?;
+                return Ok(());
+            }
             println!("Session stats: {}", session_key);
             println!(
                 "  Messages: {}",
@@ -964,11 +2277,16 @@ pub(crate) async fn run_session(
             session_key,
             path,
             value,
+            unset,
         } => {
             let session_key = config::normalize_session_key(&session_key);
-            // Build the patch params based on the path
-            let parsed_value: serde_json::Value = serde_json::from_str(&value)
-                .unwrap_or_else(|_| serde_json::Value::String(value.clone()));
+            let value = if unset { None } else { value };
+            // Build the patch params based on the path; null resets to the default
+            let parsed_value: serde_json::Value = match value.as_deref() {
+                Some(value) => serde_json::from_str(value)
+                    .unwrap_or_else(|_| serde_json::Value::String(value.to_string())),
+                None => serde_json::Value::Null,
+            };
 
             let params = match path.as_str() {
                 "label" => json!({
@@ -982,11 +2300,7 @@ pub(crate) async fn run_session(
                     || p == "maxTokens" =>
                 {
                     // Handle settings paths
-                    let settings_path = if p.starts_with("settings.") {
-                        &p[9..] // Remove "settings." prefix
-                    } else {
-                        p
-                    };
+                    let settings_path = p.strip_prefix("settings.").unwrap_or(p);
 
                     // Build nested settings object
                     let mut settings = json!({});
@@ -1003,11 +2317,7 @@ pub(crate) async fn run_session(
                     })
                 }
                 p if p.starts_with("resetPolicy.") || p == "resetPolicy" => {
-                    let policy_path = if p.starts_with("resetPolicy.") {
-                        &p[12..] // Remove "resetPolicy." prefix
-                    } else {
-                        "mode"
-                    };
+                    let policy_path = p.strip_prefix("resetPolicy.").unwrap_or("mode");
 
                     let mut policy = json!({});
                     policy[policy_path] = parsed_value;
@@ -1025,12 +2335,101 @@ pub(crate) async fn run_session(
             };
 
             client.session_patch(params).await?;
-            println!("Updated {} = {} for session '{}'", path, value, session_key);
+            match value {
+                Some(value) => {
+                    println!("Updated {} = {} for session '{}'", path, value, session_key)
+                }
+                None => println!("Unset {} for session '{}'", path, session_key),
+            }
         }
 
-        SessionAction::Compact { session_key, keep } => {
+        SessionAction::Compact {
+            session_key,
+            keep,
+            strategy,
+            max_tokens,
+            archive_trimmed,
+            summarize,
+            summary_max_tokens,
+        } => {
             let session_key = config::normalize_session_key(&session_key);
-            let payload = client.session_compact(session_key.clone(), keep).await?;
+            if summarize && strategy == "summarize" {
+                return Err(
+                    "--summarize keeps the last messages; it cannot be combined with --strategy summarize"
+                        .into(),
+                );
+            }
+            if summary_max_tokens.is_some_and(|n| n <= 0) {
+                return Err("--summary-max-tokens must be greater than 0".into());
+            }
+            let keep = match strategy.as_str() {
+                "last-n" => keep,
+                "token-budget" => {
+                    let budget = max_tokens
+                        .ok_or("--max-tokens is required with --strategy token-budget")?;
+                    let stats = client.session_stats(session_key.clone()).await?;
+                    let message_count = stats
+                        .get("messageCount")
+                        .and_then(|c| c.as_i64())
+                        .unwrap_or(0);
+                    let total_tokens = stats
+                        .get("tokens")
+                        .and_then(|t| t.get("total"))
+                        .and_then(|t| t.as_i64())
+                        .unwrap_or(0);
+                    let keep = keep_for_token_budget(message_count, total_tokens, budget);
+                    println!(
+                        "Estimated {} of {} messages fit in {} tokens",
+                        keep, message_count, budget
+                    );
+                    keep
+                }
+                "summarize" => {
+                    let payload = client
+                        .session_compact_summarize(session_key.clone())
+                        .await?;
+                    let trimmed = payload
+                        .get("trimmedMessages")
+                        .and_then(|c| c.as_i64())
+                        .unwrap_or(0);
+                    let kept = payload
+                        .get("keptMessages")
+                        .and_then(|c| c.as_i64())
+                        .unwrap_or(0);
+                    match payload.get("summary").and_then(|s| s.as_str()) {
+                        Some(summary) if trimmed > 0 => {
+                            println!("Summarized session '{}'", session_key);
+                            println!(
+                                "  Replaced {} messages with a summary, kept {}",
+                                trimmed, kept
+                            );
+                            println!();
+                            println!("{}", summary);
+                        }
+                        _ => println!(
+                            "Session '{}' has {} messages (nothing to summarize)",
+                            session_key, kept
+                        ),
+                    }
+                    return Ok(());
+                }
+                other => {
+                    return Err(format!(
+                    "Unknown compaction strategy '{}'. Expected last-n, token-budget or summarize",
+                    other
+                )
+                    .into())
+                }
+            };
+            let payload = client
+                .session_compact(
+                    session_key.clone(),
+                    keep,
+                    archive_trimmed,
+                    summarize,
+                    summary_max_tokens,
+                )
+                .await?;
             let trimmed = payload
                 .get("trimmedMessages")
                 .and_then(|c| c.as_i64())
@@ -1046,6 +2445,18 @@ pub(crate) async fn run_session(
                 if let Some(path) = payload.get("archivedTo").and_then(|p| p.as_str()) {
                     println!("  Archived to: {}", path);
                 }
+                if summarize {
+                    match payload.get("summary").and_then(|s| s.as_str()) {
+                        Some(summary) => {
+                            println!();
+                            println!("Summary of trimmed messages:");
+                            println!("{}", summary);
+                        }
+                        None => println!(
+                            "Warning: gateway did not return a summary of the trimmed messages"
+                        ),
+                    }
+                }
             } else {
                 println!(
                     "Session '{}' has {} messages (no compaction needed)",
@@ -1054,9 +2465,71 @@ pub(crate) async fn run_session(
             }
         }
 
-        SessionAction::History { session_key } => {
+        SessionAction::History {
+            session_key,
+            restore: Some(session_id),
+        } => {
             let session_key = config::normalize_session_key(&session_key);
             let payload = client.session_history(session_key.clone()).await?;
+            let previous = payload
+                .get("previousSessionIds")
+                .and_then(|p| p.as_array())
+                .map(|ids| ids.iter().filter_map(|id| id.as_str()).collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            let session_id = if previous.contains(&session_id.as_str()) {
+                session_id
+            } else {
+                let matches = previous
+                    .iter()
+                    .filter(|id| id.starts_with(session_id.as_str()))
+                    .collect::<Vec<_>>();
+                match matches.as_slice() {
+                    [full] => full.to_string(),
+                    _ => {
+                        eprintln!(
+                            "Warning: session '{}' is not in the history of '{}'",
+                            session_id, session_key
+                        );
+                        session_id
+                    }
+                }
+            };
+
+            let result = client
+                .session_history_restore(session_key.clone(), session_id.clone())
+                .await?;
+            let current = result
+                .get("sessionId")
+                .and_then(|s| s.as_str())
+                .unwrap_or("?");
+            let message_count = result
+                .get("messageCount")
+                .and_then(|c| c.as_u64())
+                .unwrap_or(0);
+
+            println!(
+                "Restored session {} into '{}'",
+                session_id.chars().take(8).collect::<String>(),
+                session_key
+            );
+            println!("  Current session: {}", current);
+            println!("  Messages: {}", message_count);
+            if let Some(archived) = result.get("archivedTo").and_then(|a| a.as_str()) {
+                println!("  Previous conversation archived to: {}", archived);
+            }
+        }
+
+        SessionAction::History {
+            session_key,
+            restore: None,
+        } => {
+            let session_key = config::normalize_session_key(&session_key);
+            let payload = client.session_history(session_key.clone()).await?;
+            if output == OutputFormat::Json {
+                print_json_output(payload)?;
+                return Ok(());
+            }
             let current = payload
                 .get("currentSessionId")
                 .and_then(|s| s.as_str())
@@ -1083,104 +2556,764 @@ pub(crate) async fn run_session(
             }
         }
 
-        SessionAction::Preview { session_key, limit } => {
+        SessionAction::Preview {
+            session_key,
+            limit,
+            format,
+            output_file,
+            export_tool_calls,
+        } => {
+            let markdown = match format.as_str() {
+                "text" => false,
+                "markdown" | "md" => true,
+                other => {
+                    return Err(format!(
+                        "Unknown preview format '{}'. Expected text or markdown",
+                        other
+                    )
+                    .into())
+                }
+            };
+
             let session_key = config::normalize_session_key(&session_key);
             let payload = client.session_preview(session_key.clone(), limit).await?;
             let msg_count = payload
                 .get("messageCount")
                 .and_then(|c| c.as_i64())
                 .unwrap_or(0);
-            let messages = payload.get("messages").and_then(|m| m.as_array());
+            let messages = payload
+                .get("messages")
+                .and_then(|m| m.as_array())
+                .map(|m| m.as_slice())
+                .unwrap_or(&[]);
+
+            let rendered = if export_tool_calls {
+                extract_tool_calls(messages)
+                    .iter()
+                    .map(|call| format!("{}\n", call))
+                    .collect()
+            } else if markdown {
+                render_session_preview_markdown(&session_key, msg_count, messages)
+            } else {
+                render_session_preview_text(&session_key, msg_count, messages)
+            };
 
-            println!("Session: {} ({} messages total)\n", session_key, msg_count);
+            match output_file {
+                Some(path) => {
+                    std::fs::write(&path, &rendered)?;
+                    println!("Wrote session preview to {}", path.display());
+                }
+                None => print!("{}", rendered),
+            }
+        }
 
-            if let Some(msgs) = messages {
-                for msg in msgs {
-                    let role = msg.get("role").and_then(|r| r.as_str()).unwrap_or("?");
+        SessionAction::Export {
+            session_key,
+            format,
+            output_file,
+        } => {
+            let render: fn(&serde_json::Value) -> String = match format.as_str() {
+                "jsonl" => render_session_export_jsonl,
+                "markdown" | "md" => render_session_export_markdown,
+                "csv" => render_session_export_csv,
+                other => {
+                    return Err(format!(
+                        "Unknown export format '{}'. Expected jsonl, markdown or csv",
+                        other
+                    )
+                    .into())
+                }
+            };
 
-                    match role {
-                        "user" => {
-                            let content = msg.get("content").and_then(|c| c.as_str()).unwrap_or("");
-                            println!("USER: {}\n", content);
-                        }
-                        "assistant" => {
-                            print!("ASSISTANT: ");
-                            if let Some(content) = msg.get("content") {
-                                if let Some(text) = content.as_str() {
-                                    println!("{}\n", text);
-                                } else if let Some(blocks) = content.as_array() {
-                                    for block in blocks {
-                                        if let Some(block_type) =
-                                            block.get("type").and_then(|t| t.as_str())
-                                        {
-                                            match block_type {
-                                                "text" => {
-                                                    if let Some(text) =
-                                                        block.get("text").and_then(|t| t.as_str())
-                                                    {
-                                                        print!("{}", text);
-                                                    }
-                                                }
-                                                "toolCall" => {
-                                                    let name = block
-                                                        .get("name")
-                                                        .and_then(|n| n.as_str())
-                                                        .unwrap_or("?");
-                                                    println!("\n[Tool call: {}]", name);
-                                                }
-                                                _ => {}
-                                            }
-                                        }
+            let session_key = config::normalize_session_key(&session_key);
+            let payload = client.session_export(session_key).await?;
+            let rendered = render(&payload);
+
+            match output_file {
+                Some(path) => {
+                    std::fs::write(&path, &rendered)?;
+                    let count = payload
+                        .get("messages")
+                        .and_then(|m| m.as_array())
+                        .map_or(0, |m| m.len());
+                    println!("Exported {} messages to {}", count, path.display());
+                }
+                None => print!("{}", rendered),
+            }
+        }
+
+        SessionAction::Import {
+            file,
+            session_key,
+            overwrite,
+        } => {
+            let content = std::fs::read_to_string(&file)
+                .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+            let (messages, tokens) =
+                parse_session_import(&content).map_err(|e| format!("{}: {}", file.display(), e))?;
+            if messages.is_empty() {
+                return Err(format!("{} contains no messages", file.display()).into());
+            }
+
+            let session_key = config::normalize_session_key(&session_key);
+            if !overwrite {
+                let stats = client.session_stats(session_key.clone()).await?;
+                let existing = stats
+                    .get("messageCount")
+                    .and_then(|c| c.as_i64())
+                    .unwrap_or(0);
+                if existing > 0 {
+                    eprintln!(
+                        "Warning: session '{}' already has {} messages.",
+                        session_key, existing
+                    );
+                    return Err("Re-run with --overwrite to archive and replace them".into());
+                }
+            }
+
+            let payload = client
+                .session_import(session_key.clone(), messages, tokens, overwrite)
+                .await?;
+            println!(
+                "Imported {} messages into '{}'",
+                payload
+                    .get("messageCount")
+                    .and_then(|c| c.as_i64())
+                    .unwrap_or(0),
+                session_key
+            );
+            if let Some(path) = payload.get("archivedTo").and_then(|p| p.as_str()) {
+                println!("  Previous messages archived to: {}", path);
+            }
+        }
+
+        SessionAction::Search {
+            query,
+            session_key,
+            limit,
+        } => {
+            let session_key = session_key.map(|key| config::normalize_session_key(&key));
+            let payload = client
+                .session_search(query.clone(), session_key, limit)
+                .await?;
+            let results = payload.get("results").cloned().unwrap_or_else(|| json!([]));
+            if output == OutputFormat::Json {
+                print_json_output(results)?;
+                return Ok(());
+            }
+            let hits = results.as_array().map(Vec::as_slice).unwrap_or_default();
+            if hits.is_empty() {
+                println!("No messages matching '{}'", query);
+                return Ok(());
+            }
+            let bold = io::stdout().is_terminal();
+            for hit in hits {
+                println!("{}", render_session_search_hit(hit, bold));
+            }
+            println!("{} match(es)", hits.len());
+        }
+    }
+
+    Ok(())
+}
+
+/// Byte offset in `s` of a UTF-16 offset (a JavaScript string index).
+fn utf16_to_byte_offset(s: &str, utf16: usize) -> usize {
+    let mut units = 0;
+    for (i, c) in s.char_indices() {
+        if units >= utf16 {
+            return i;
+        }
+        units += c.len_utf16();
+    }
+    s.len()
+}
+
+/// One `session search` hit: a header line, then the snippet with the match in bold.
+fn render_session_search_hit(hit: &serde_json::Value, bold: bool) -> String {
+    let session_key = hit
+        .get("sessionKey")
+        .and_then(|s| s.as_str())
+        .unwrap_or("unknown");
+    let role = hit
+        .get("role")
+        .and_then(|r| r.as_str())
+        .unwrap_or("unknown");
+    let index = hit.get("index").and_then(|i| i.as_u64()).unwrap_or(0);
+    let time = hit
+        .get("timestamp")
+        .and_then(|t| t.as_i64())
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+    let snippet = hit.get("snippet").and_then(|s| s.as_str()).unwrap_or("");
+
+    // Offsets are UTF-16 code units from the gateway; map them onto chars.
+    let start = hit.get("matchStart").and_then(|s| s.as_u64()).unwrap_or(0) as usize;
+    let len = hit.get("matchLength").and_then(|l| l.as_u64()).unwrap_or(0) as usize;
+    let match_start = utf16_to_byte_offset(snippet, start);
+    let match_end = utf16_to_byte_offset(snippet, start + len);
+    let highlighted = if bold && match_start < match_end {
+        format!(
+            "{}\x1b[1m{}\x1b[0m{}",
+            &snippet[..match_start],
+            &snippet[match_start..match_end],
+            &snippet[match_end..]
+        )
+    } else {
+        snippet.to_string()
+    };
+
+    format!(
+        "{} #{} {} {}\n  {}",
+        session_key, index, role, time, highlighted
+    )
+}
+
+/// Parses a `session export --format jsonl` file into its messages plus
+/// token hints summed from the assistant messages' `usage`.
+fn parse_session_import(
+    content: &str,
+) -> Result<(Vec<serde_json::Value>, serde_json::Value), String> {
+    let mut messages = Vec::new();
+    let (mut input, mut output, mut total) = (0, 0, 0);
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let message: serde_json::Value = serde_json::from_str(line)
+            .map_err(|e| format!("Line {}: invalid JSON: {}", index + 1, e))?;
+        match message.get("role").and_then(|r| r.as_str()) {
+            Some("user" | "toolResult") => {}
+            Some("assistant") => {
+                let usage = |key: &str| {
+                    message
+                        .get("usage")
+                        .and_then(|u| u.get(key))
+                        .and_then(|v| v.as_i64())
+                        .unwrap_or(0)
+                };
+                input += usage("input");
+                output += usage("output");
+                total += usage("totalTokens");
+            }
+            _ => {
+                return Err(format!(
+                    "Line {}: expected a user, assistant or toolResult message",
+                    index + 1
+                ))
+            }
+        }
+        messages.push(message);
+    }
+
+    let tokens = json!({ "input": input, "output": output, "total": total });
+    Ok((messages, tokens))
+}
+
+fn export_messages(payload: &serde_json::Value) -> &[serde_json::Value] {
+    payload
+        .get("messages")
+        .and_then(|m| m.as_array())
+        .map(|m| m.as_slice())
+        .unwrap_or(&[])
+}
+
+/// Joins the text blocks of a tool result (or returns string content as-is).
+fn tool_result_text(msg: &serde_json::Value) -> String {
+    match msg.get("content") {
+        Some(serde_json::Value::String(text)) => text.clone(),
+        Some(serde_json::Value::Array(blocks)) => blocks
+            .iter()
+            .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// One `session.export` message object per line.
+fn render_session_export_jsonl(payload: &serde_json::Value) -> String {
+    export_messages(payload)
+        .iter()
+        .map(|msg| format!("{}\n", msg))
+        .collect()
+}
+
+/// Markdown transcript with a YAML front-matter block holding the session
+/// metadata and token counts.
+fn render_session_export_markdown(payload: &serde_json::Value) -> String {
+    let str_field = |key: &str| {
+        payload
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| serde_json::Value::from(s).to_string())
+    };
+    let int_field = |value: Option<&serde_json::Value>| value.and_then(|v| v.as_i64()).unwrap_or(0);
+    let time_field = |key: &str| {
+        payload
+            .get(key)
+            .and_then(|v| v.as_i64())
+            .and_then(chrono::DateTime::from_timestamp_millis)
+            .map(|t| format!("\"{}\"", t.to_rfc3339()))
+    };
+
+    let mut out = String::from("---\n");
+    for (key, value) in [
+        ("sessionKey", str_field("sessionKey")),
+        ("sessionId", str_field("sessionId")),
+        ("label", str_field("label")),
+        ("createdAt", time_field("createdAt")),
+        ("updatedAt", time_field("updatedAt")),
+    ] {
+        if let Some(value) = value {
+            let _ = writeln!(out, "{}: {}", key, value);
+        }
+    }
+    let tokens = payload.get("tokens");
+    let _ = writeln!(
+        out,
+        "messageCount: {}\ntokens:\n  input: {}\n  output: {}\n  total: {}\n---\n",
+        int_field(payload.get("messageCount")),
+        int_field(tokens.and_then(|t| t.get("input"))),
+        int_field(tokens.and_then(|t| t.get("output"))),
+        int_field(tokens.and_then(|t| t.get("total"))),
+    );
+
+    for msg in export_messages(payload) {
+        match msg.get("role").and_then(|r| r.as_str()).unwrap_or("?") {
+            "user" => {
+                let content = msg.get("content").map(format_content).unwrap_or_default();
+                let _ = writeln!(out, "## User\n\n{}\n", content.trim_end());
+            }
+            "assistant" => {
+                let _ = writeln!(out, "## Assistant\n");
+                match msg.get("content") {
+                    Some(serde_json::Value::Array(blocks)) => {
+                        for block in blocks {
+                            match block.get("type").and_then(|t| t.as_str()) {
+                                Some("text") => {
+                                    let text =
+                                        block.get("text").and_then(|t| t.as_str()).unwrap_or("");
+                                    if !text.trim().is_empty() {
+                                        let _ = writeln!(out, "{}\n", text.trim_end());
                                     }
-                                    println!("\n");
                                 }
+                                Some("toolCall") => {
+                                    let name =
+                                        block.get("name").and_then(|n| n.as_str()).unwrap_or("?");
+                                    let args = block
+                                        .get("arguments")
+                                        .map(|a| {
+                                            serde_json::to_string_pretty(a).unwrap_or_default()
+                                        })
+                                        .unwrap_or_else(|| "{}".to_string());
+                                    let _ = writeln!(
+                                        out,
+                                        "Tool call `{}`:\n\n```json\n{}\n```\n",
+                                        name, args
+                                    );
+                                }
+                                _ => {}
                             }
                         }
-                        "toolResult" => {
-                            let tool_name =
-                                msg.get("toolName").and_then(|n| n.as_str()).unwrap_or("?");
-                            let is_error = msg
-                                .get("isError")
-                                .and_then(|e| e.as_bool())
-                                .unwrap_or(false);
-                            let prefix = if is_error { "ERROR" } else { "RESULT" };
-
-                            print!("TOOL {} ({}): ", prefix, tool_name);
-                            if let Some(content) = msg.get("content").and_then(|c| c.as_array()) {
-                                for block in content {
+                    }
+                    Some(content) => {
+                        let _ = writeln!(out, "{}\n", format_content(content).trim_end());
+                    }
+                    None => {}
+                }
+            }
+            "toolResult" => {
+                let tool_name = msg.get("toolName").and_then(|n| n.as_str()).unwrap_or("?");
+                let is_error = msg
+                    .get("isError")
+                    .and_then(|e| e.as_bool())
+                    .unwrap_or(false);
+                let _ = writeln!(
+                    out,
+                    "## Tool: {}{}\n\n```\n{}\n```\n",
+                    tool_name,
+                    if is_error { " (error)" } else { "" },
+                    tool_result_text(msg).trim_end()
+                );
+            }
+            role => {
+                let _ = writeln!(out, "## {}\n\n```json\n{}\n```\n", role, msg);
+            }
+        }
+    }
+
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// CSV with one row per message: timestamp, role, tool, content. `tool` is
+/// the tool name for results and the called tool names for assistant turns.
+fn render_session_export_csv(payload: &serde_json::Value) -> String {
+    let mut out = String::from("timestamp,role,tool,content\n");
+    for msg in export_messages(payload) {
+        let role = msg.get("role").and_then(|r| r.as_str()).unwrap_or("");
+        let timestamp = msg
+            .get("timestamp")
+            .map(|t| match t {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .unwrap_or_default();
+        let (tool, content) = if role == "toolResult" {
+            let tool = msg.get("toolName").and_then(|n| n.as_str()).unwrap_or("");
+            (tool.to_string(), tool_result_text(msg))
+        } else {
+            let tools: Vec<&str> = msg
+                .get("content")
+                .and_then(|c| c.as_array())
+                .map(|blocks| {
+                    blocks
+                        .iter()
+                        .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("toolCall"))
+                        .filter_map(|b| b.get("name").and_then(|n| n.as_str()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let content = msg.get("content").map(format_content).unwrap_or_default();
+            (tools.join(" "), content)
+        };
+        let _ = writeln!(
+            out,
+            "{},{},{},{}",
+            csv_field(&timestamp),
+            csv_field(role),
+            csv_field(&tool),
+            csv_field(&content)
+        );
+    }
+    out
+}
+
+/// Sorts `sessions.list` entries by `sort_by`; message-count and token-usage
+/// read the `stats` object attached from `session.stats`. Missing values sort first.
+fn sort_sessions(sessions: &mut [serde_json::Value], sort_by: &str, descending: bool) {
+    let sort_key = |session: &serde_json::Value| -> Option<i64> {
+        match sort_by {
+            "created" => session.get("createdAt").and_then(|t| t.as_i64()),
+            "message-count" => session
+                .get("stats")
+                .and_then(|s| s.get("messageCount"))
+                .and_then(|c| c.as_i64()),
+            "token-usage" => session
+                .get("stats")
+                .and_then(|s| s.get("tokens"))
+                .and_then(|t| t.get("total"))
+                .and_then(|t| t.as_i64()),
+            _ => session.get("lastActiveAt").and_then(|t| t.as_i64()),
+        }
+    };
+
+    sessions.sort_by_key(|session| sort_key(session));
+    if descending {
+        sessions.reverse();
+    }
+}
+
+const UNKNOWN_TOOL_NODE: &str = "[unknown node]";
+
+/// Groups `tools.list` entries by their `nodeId`, nodes sorted by name with
+/// tools lacking a node ID last.
+fn group_tools_by_node(tools: &[serde_json::Value]) -> Vec<(&str, Vec<&serde_json::Value>)> {
+    let mut by_node: std::collections::BTreeMap<&str, Vec<&serde_json::Value>> =
+        std::collections::BTreeMap::new();
+    let mut unknown = Vec::new();
+    for tool in tools {
+        match tool.get("nodeId").and_then(|n| n.as_str()) {
+            Some(node_id) => by_node.entry(node_id).or_default().push(tool),
+            None => unknown.push(tool),
+        }
+    }
+
+    let mut groups: Vec<_> = by_node.into_iter().collect();
+    if !unknown.is_empty() {
+        groups.push((UNKNOWN_TOOL_NODE, unknown));
+    }
+    groups
+}
+
+/// One `tools list` entry: `  name - description`, followed with `verbose`
+/// by the tool's input schema as indented JSON.
+fn render_tool_entry(tool: &serde_json::Value, verbose: bool) -> String {
+    let name = tool.get("name").and_then(|n| n.as_str()).unwrap_or("?");
+    let desc = tool
+        .get("description")
+        .and_then(|d| d.as_str())
+        .unwrap_or("");
+    let mut out = format!("  {} - {}\n", name, desc);
+    if verbose {
+        let schema = tool
+            .get("inputSchema")
+            .map(|schema| serde_json::to_string_pretty(schema).unwrap_or_default())
+            .unwrap_or_else(|| "(no input schema)".to_string());
+        for line in schema.lines() {
+            let _ = writeln!(out, "      {}", line);
+        }
+    }
+    out
+}
+
+fn render_session_preview_text(
+    session_key: &str,
+    msg_count: i64,
+    messages: &[serde_json::Value],
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Session: {} ({} messages total)\n",
+        session_key, msg_count
+    );
+
+    for msg in messages {
+        let role = msg.get("role").and_then(|r| r.as_str()).unwrap_or("?");
+
+        match role {
+            "user" => {
+                let content = msg.get("content").and_then(|c| c.as_str()).unwrap_or("");
+                let _ = writeln!(out, "USER: {}\n", content);
+            }
+            "assistant" => {
+                out.push_str("ASSISTANT: ");
+                if let Some(content) = msg.get("content") {
+                    if let Some(text) = content.as_str() {
+                        let _ = writeln!(out, "{}\n", text);
+                    } else if let Some(blocks) = content.as_array() {
+                        for block in blocks {
+                            match block.get("type").and_then(|t| t.as_str()) {
+                                Some("text") => {
                                     if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
-                                        // Truncate long results
-                                        if text.len() > 200 {
-                                            println!("{}", truncate_for_display(text, 200));
-                                        } else {
-                                            println!("{}", text);
-                                        }
+                                        out.push_str(text);
                                     }
                                 }
+                                Some("toolCall") => {
+                                    let name =
+                                        block.get("name").and_then(|n| n.as_str()).unwrap_or("?");
+                                    let _ = writeln!(out, "\n[Tool call: {}]", name);
+                                }
+                                _ => {}
                             }
-                            println!();
                         }
-                        _ => {
-                            println!("{}: {:?}\n", role.to_uppercase(), msg);
+                        out.push_str("\n\n");
+                    }
+                }
+            }
+            "toolResult" => {
+                let tool_name = msg.get("toolName").and_then(|n| n.as_str()).unwrap_or("?");
+                let is_error = msg
+                    .get("isError")
+                    .and_then(|e| e.as_bool())
+                    .unwrap_or(false);
+                let prefix = if is_error { "ERROR" } else { "RESULT" };
+
+                let _ = write!(out, "TOOL {} ({}): ", prefix, tool_name);
+                if let Some(content) = msg.get("content").and_then(|c| c.as_array()) {
+                    for block in content {
+                        if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
+                            // Truncate long results
+                            if text.len() > 200 {
+                                let _ = writeln!(out, "{}", truncate_for_display(text, 200));
+                            } else {
+                                let _ = writeln!(out, "{}", text);
+                            }
                         }
                     }
                 }
+                out.push('\n');
+            }
+            _ => {
+                let _ = writeln!(out, "{}: {:?}\n", role.to_uppercase(), msg);
             }
         }
     }
 
-    Ok(())
+    out
+}
+
+/// Pairs each assistant tool call with its result message (matched by
+/// `toolCallId`) and returns `{ tool, args, result_summary, timestamp }`
+/// objects in call order. Calls without a result yet get a null summary.
+fn extract_tool_calls(messages: &[serde_json::Value]) -> Vec<serde_json::Value> {
+    let mut calls: Vec<(Option<String>, serde_json::Value)> = Vec::new();
+
+    for msg in messages {
+        match msg.get("role").and_then(|r| r.as_str()) {
+            Some("assistant") => {
+                let blocks = msg
+                    .get("content")
+                    .and_then(|c| c.as_array())
+                    .map(|b| b.as_slice())
+                    .unwrap_or(&[]);
+                for block in blocks {
+                    if block.get("type").and_then(|t| t.as_str()) != Some("toolCall") {
+                        continue;
+                    }
+                    let id = block.get("id").and_then(|i| i.as_str()).map(String::from);
+                    calls.push((
+                        id,
+                        serde_json::json!({
+                            "tool": block.get("name").and_then(|n| n.as_str()).unwrap_or("?"),
+                            "args": block.get("arguments").cloned().unwrap_or(serde_json::json!({})),
+                            "result_summary": serde_json::Value::Null,
+                            "timestamp": msg.get("timestamp").cloned().unwrap_or(serde_json::Value::Null),
+                        }),
+                    ));
+                }
+            }
+            Some("toolResult") => {
+                let call_id = msg.get("toolCallId").and_then(|i| i.as_str());
+                let Some((_, call)) = calls.iter_mut().find(|(id, call)| {
+                    id.as_deref() == call_id && call["result_summary"].is_null()
+                }) else {
+                    continue;
+                };
+
+                let text: Vec<&str> = msg
+                    .get("content")
+                    .and_then(|c| c.as_array())
+                    .map(|blocks| {
+                        blocks
+                            .iter()
+                            .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let mut summary = truncate_for_display(&text.join("\n"), 200);
+                if msg
+                    .get("isError")
+                    .and_then(|e| e.as_bool())
+                    .unwrap_or(false)
+                {
+                    summary = format!("Error: {}", summary);
+                }
+                call["result_summary"] = serde_json::Value::String(summary);
+                if let Some(timestamp) = msg.get("timestamp") {
+                    call["timestamp"] = timestamp.clone();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    calls.into_iter().map(|(_, call)| call).collect()
+}
+
+/// Renders a session transcript as Markdown suitable for docs or issues.
+/// Tool calls become fenced JSON blocks tagged with the tool name.
+fn render_session_preview_markdown(
+    session_key: &str,
+    msg_count: i64,
+    messages: &[serde_json::Value],
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# Session `{}`\n\n_{} messages total_\n",
+        session_key, msg_count
+    );
+
+    for msg in messages {
+        let role = msg.get("role").and_then(|r| r.as_str()).unwrap_or("?");
+
+        match role {
+            "user" => {
+                let content = msg.get("content").and_then(|c| c.as_str()).unwrap_or("");
+                let _ = writeln!(out, "**User:** {}\n", content);
+            }
+            "assistant" => match msg.get("content") {
+                Some(content) if content.is_string() => {
+                    let _ = writeln!(out, "{}\n", content.as_str().unwrap_or(""));
+                }
+                Some(content) => {
+                    for block in content.as_array().map(|b| b.as_slice()).unwrap_or(&[]) {
+                        match block.get("type").and_then(|t| t.as_str()) {
+                            Some("text") => {
+                                let text = block.get("text").and_then(|t| t.as_str()).unwrap_or("");
+                                if !text.trim().is_empty() {
+                                    let _ = writeln!(out, "{}\n", text.trim_end());
+                                }
+                            }
+                            Some("toolCall") => {
+                                let name =
+                                    block.get("name").and_then(|n| n.as_str()).unwrap_or("?");
+                                let args = block
+                                    .get("arguments")
+                                    .map(|a| serde_json::to_string_pretty(a).unwrap_or_default())
+                                    .unwrap_or_else(|| "{}".to_string());
+                                let _ = writeln!(out, "```{}\n{}\n```\n", name, args);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                None => {}
+            },
+            "toolResult" => {
+                let tool_name = msg.get("toolName").and_then(|n| n.as_str()).unwrap_or("?");
+                let is_error = msg
+                    .get("isError")
+                    .and_then(|e| e.as_bool())
+                    .unwrap_or(false);
+                let label = if is_error {
+                    "Tool error"
+                } else {
+                    "Tool result"
+                };
+                let text: Vec<&str> = msg
+                    .get("content")
+                    .and_then(|c| c.as_array())
+                    .map(|blocks| {
+                        blocks
+                            .iter()
+                            .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let _ = writeln!(
+                    out,
+                    "_{} ({}):_\n\n```\n{}\n```\n",
+                    label,
+                    tool_name,
+                    truncate_for_display(&text.join("\n"), 2000)
+                );
+            }
+            _ => {
+                let _ = writeln!(out, "**{}:** `{}`\n", role, msg);
+            }
+        }
+    }
+
+    out
 }
 
 async fn send_chat(
     client: &GatewayClient,
     session_key: &str,
     message: &str,
-) -> Result<bool, Box<dyn std::error::Error>> {
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
     let payload = client
         .chat_send(session_key.to_string(), message.to_string())
         .await?;
 
+    Ok(handle_chat_send_payload(&payload))
+}
+
+/// Prints command/directive responses from `chat.send`; returns the run ID
+/// whose chat events to wait for, or `None` when no chat event will follow.
+fn handle_chat_send_payload(payload: &serde_json::Value) -> Option<String> {
     if let Some(status) = payload.get("status").and_then(|s| s.as_str()) {
         match status {
             "command" => {
@@ -1190,19 +3323,26 @@ async fn send_chat(
                 if let Some(error) = payload.get("error").and_then(|e| e.as_str()) {
                     eprintln!("Error: {}", error);
                 }
-                return Ok(true);
+                return None;
             }
             "directive-only" => {
                 if let Some(response) = payload.get("response").and_then(|r| r.as_str()) {
                     println!("{}", response);
                 }
-                return Ok(true);
+                return None;
             }
             _ => {}
         }
     }
 
-    Ok(false) // Wait for chat event
+    // Wait for chat event
+    Some(
+        payload
+            .get("runId")
+            .and_then(|r| r.as_str())
+            .unwrap_or_default()
+            .to_string(),
+    )
 }
 
 fn format_content(content: &serde_json::Value) -> String {
@@ -1250,9 +3390,33 @@ fn render_qr_terminal(data: &str) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+fn save_qr_png(data: &str, path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+    use qrcode::QrCode;
+
+    let code = QrCode::new(data.as_bytes())?;
+    let image = code
+        .render::<image::Luma<u8>>()
+        .min_dimensions(300, 300)
+        .build();
+    image.save_with_format(path, image::ImageFormat::Png)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::truncate_for_display;
+    use super::{
+        channel_media_from_url, chat_attachment, closest_config_path, collect_config_leaves,
+        collect_config_paths, config_diff_json, extract_tool_calls, group_tools_by_node,
+        json_output, keep_for_token_budget, parse_discord_embed, parse_session_import,
+        parse_tool_call_args, render_config_diff, render_config_tree,
+        render_heartbeat_agent_detail, render_line_diff, render_session_export_csv,
+        render_session_export_jsonl, render_session_export_markdown,
+        render_session_preview_markdown, render_session_search_hit, render_tool_entry,
+        render_tool_result, save_qr_png, session_record_error, sort_sessions, truncate_for_display,
+        validate_auto_approve_pattern, validate_config_value, validate_discord_channel_id,
+        validate_whatsapp_number, wait_for_chat_response, ChatRuns, ConfigDiffRow,
+    };
+    use serde_json::json;
 
     #[test]
     fn truncate_for_display_keeps_short_text_unchanged() {
@@ -1268,4 +3432,495 @@ mod tests {
         assert!(truncated.ends_with("..."));
         assert_eq!(truncated, format!("{}...", "a".repeat(199)));
     }
+
+    #[test]
+    fn keep_for_token_budget_scales_by_average_message_size() {
+        assert_eq!(keep_for_token_budget(100, 50_000, 10_000), 20);
+        assert_eq!(keep_for_token_budget(10, 1_000, 5_000), 10);
+        assert_eq!(keep_for_token_budget(10, 100_000, 10), 1);
+        assert_eq!(keep_for_token_budget(0, 0, 10), 0);
+    }
+
+    #[test]
+    fn render_session_preview_markdown_fences_tool_calls() {
+        let messages = vec![
+            json!({ "role": "user", "content": "list files" }),
+            json!({
+                "role": "assistant",
+                "content": [
+                    { "type": "text", "text": "Sure." },
+                    { "type": "toolCall", "name": "Bash", "arguments": { "command": "ls" } }
+                ]
+            }),
+        ];
+
+        let rendered = render_session_preview_markdown("agent:main:cli:dm:main", 2, &messages);
+        assert!(rendered.contains("**User:** list files"));
+        assert!(rendered.contains("Sure.\n"));
+        assert!(rendered.contains("```Bash\n{\n  \"command\": \"ls\"\n}\n```"));
+    }
+
+    #[test]
+    fn render_session_export_formats_messages() {
+        let payload = json!({
+            "sessionKey": "agent:main:cli:dm:main",
+            "sessionId": "abc",
+            "messageCount": 3,
+            "tokens": { "input": 10, "output": 5, "total": 15 },
+            "messages": [
+                { "role": "user", "content": "list files", "timestamp": 1 },
+                {
+                    "role": "assistant",
+                    "timestamp": 2,
+                    "content": [
+                        { "type": "text", "text": "Sure." },
+                        { "type": "toolCall", "name": "Bash", "arguments": { "command": "ls" } }
+                    ]
+                },
+                {
+                    "role": "toolResult",
+                    "toolName": "Bash",
+                    "timestamp": 3,
+                    "content": [{ "type": "text", "text": "a.txt, b.txt" }]
+                }
+            ]
+        });
+
+        let jsonl = render_session_export_jsonl(&payload);
+        assert_eq!(jsonl.lines().count(), 3);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(jsonl.lines().next().unwrap()).unwrap(),
+            payload["messages"][0]
+        );
+
+        let markdown = render_session_export_markdown(&payload);
+        assert!(markdown.starts_with("---\nsessionKey: \"agent:main:cli:dm:main\"\n"));
+        assert!(markdown.contains("tokens:\n  input: 10\n  output: 5\n  total: 15\n---\n"));
+        assert!(markdown.contains("## User\n\nlist files\n"));
+        assert!(markdown.contains("## Assistant\n\nSure.\n"));
+        assert!(markdown.contains("## Tool: Bash\n\n```\na.txt, b.txt\n```"));
+
+        let csv = render_session_export_csv(&payload);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows[0], "timestamp,role,tool,content");
+        assert_eq!(rows[2], "2,assistant,Bash,Sure.[Tool: Bash]");
+        assert_eq!(rows[3], "3,toolResult,Bash,\"a.txt, b.txt\"");
+    }
+
+    #[test]
+    fn parse_session_import_reads_export_jsonl() {
+        let content = concat!(
+            r#"{"role":"user","content":"hi","timestamp":1}"#,
+            "\n\n",
+            r#"{"role":"assistant","content":[],"usage":{"input":10,"output":4,"totalTokens":14}}"#,
+            "\n",
+            r#"{"role":"assistant","content":[],"usage":{"input":20,"output":6,"totalTokens":26}}"#,
+            "\n",
+        );
+        let (messages, tokens) = parse_session_import(content).unwrap();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(tokens, json!({ "input": 30, "output": 10, "total": 40 }));
+
+        let err = parse_session_import("{\"role\":\"user\"}\n{\"role\":\"system\"}\n").unwrap_err();
+        assert!(err.starts_with("Line 2:"));
+        assert!(parse_session_import("not json").is_err());
+    }
+
+    #[test]
+    fn extract_tool_calls_pairs_calls_with_results() {
+        let messages = vec![
+            json!({ "role": "user", "content": "write a file", "timestamp": 1 }),
+            json!({
+                "role": "assistant",
+                "timestamp": 2,
+                "content": [
+                    { "type": "toolCall", "id": "a", "name": "Write", "arguments": { "path": "x" } },
+                    { "type": "toolCall", "id": "b", "name": "Bash", "arguments": { "command": "ls" } }
+                ]
+            }),
+            json!({
+                "role": "toolResult",
+                "toolCallId": "b",
+                "toolName": "Bash",
+                "isError": true,
+                "timestamp": 3,
+                "content": [{ "type": "text", "text": "boom" }]
+            }),
+        ];
+
+        let calls = extract_tool_calls(&messages);
+        assert_eq!(calls.len(), 2);
+        assert_eq!(
+            calls[0],
+            json!({ "tool": "Write", "args": { "path": "x" }, "result_summary": null, "timestamp": 2 })
+        );
+        assert_eq!(calls[1]["tool"], "Bash");
+        assert_eq!(calls[1]["result_summary"], "Error: boom");
+        assert_eq!(calls[1]["timestamp"], 3);
+    }
+
+    #[test]
+    fn chat_attachment_sends_text_directly_and_binary_as_base64() {
+        let text = chat_attachment("notes.md", b"# hi\n".to_vec());
+        assert_eq!(text["content_type"], "text/plain; charset=utf-8");
+        assert_eq!(text["data"], "# hi\n");
+
+        let png = chat_attachment("dot.png", b"\x89PNG\r\n\x1a\n\0\0".to_vec());
+        assert_eq!(png["name"], "dot.png");
+        assert_eq!(png["content_type"], "image/png");
+        assert_eq!(png["data"], "iVBORw0KGgoAAA==");
+    }
+
+    #[test]
+    fn sort_sessions_orders_by_requested_key() {
+        let mut sessions = vec![
+            json!({ "sessionKey": "a", "createdAt": 3, "lastActiveAt": 10, "stats": { "tokens": { "total": 500 } } }),
+            json!({ "sessionKey": "b", "createdAt": 1, "lastActiveAt": 30 }),
+            json!({ "sessionKey": "c", "createdAt": 2, "lastActiveAt": 20, "stats": { "tokens": { "total": 100 } } }),
+        ];
+        let keys = |sessions: &[serde_json::Value]| -> Vec<String> {
+            sessions
+                .iter()
+                .map(|s| s["sessionKey"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        sort_sessions(&mut sessions, "last-active", false);
+        assert_eq!(keys(&sessions), ["a", "c", "b"]);
+        sort_sessions(&mut sessions, "created", true);
+        assert_eq!(keys(&sessions), ["a", "c", "b"]);
+        sort_sessions(&mut sessions, "token-usage", true);
+        assert_eq!(keys(&sessions), ["a", "c", "b"]);
+        sort_sessions(&mut sessions, "token-usage", false);
+        assert_eq!(keys(&sessions), ["b", "c", "a"]);
+    }
+
+    #[tokio::test]
+    async fn wait_for_chat_response_fails_after_timeout() {
+        use std::sync::Mutex;
+
+        let runs = Mutex::new(ChatRuns::default());
+        let timeout = tokio::time::Duration::from_millis(10);
+        runs.lock().unwrap().finished.insert("run-1".to_string());
+        assert!(wait_for_chat_response(&runs, "run-1", timeout)
+            .await
+            .is_ok());
+
+        // A late final from run-1 must not complete run-2.
+        runs.lock().unwrap().finished.insert("run-1".to_string());
+        runs.lock()
+            .unwrap()
+            .partial
+            .insert("run-2".to_string(), "Checking the logs".to_string());
+        let err = wait_for_chat_response(&runs, "run-2", timeout)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Timed out after"));
+        assert_eq!(err.partial, "Checking the logs");
+    }
+
+    #[test]
+    fn render_tool_entry_shows_schema_when_verbose() {
+        let tool = json!({
+            "name": "laptop__Read",
+            "description": "Read a file",
+            "inputSchema": { "type": "object", "required": ["path"] }
+        });
+
+        assert_eq!(
+            render_tool_entry(&tool, false),
+            "  laptop__Read - Read a file\n"
+        );
+        let verbose = render_tool_entry(&tool, true);
+        assert!(verbose.starts_with("  laptop__Read - Read a file\n      {\n"));
+        assert!(verbose.contains("        \"type\": \"object\""));
+        assert!(render_tool_entry(&json!({ "name": "Spawn" }), true)
+            .ends_with("      (no input schema)\n"));
+    }
+
+    #[test]
+    fn group_tools_by_node_puts_unknown_last() {
+        let tools = vec![
+            json!({ "name": "Spawn" }),
+            json!({ "name": "mini__Bash", "nodeId": "mini" }),
+            json!({ "name": "laptop__Bash", "nodeId": "laptop" }),
+            json!({ "name": "laptop__Read", "nodeId": "laptop" }),
+        ];
+
+        let groups: Vec<(&str, usize)> = group_tools_by_node(&tools)
+            .into_iter()
+            .map(|(node, tools)| (node, tools.len()))
+            .collect();
+        assert_eq!(groups, [("laptop", 2), ("mini", 1), ("[unknown node]", 1)]);
+    }
+
+    #[test]
+    fn collect_config_leaves_flattens_nested_objects() {
+        let config = json!({
+            "model": { "provider": "anthropic", "id": "claude" },
+            "channels": { "whatsapp": { "allowFrom": ["+1"] } },
+            "userTimezone": "UTC"
+        });
+        let mut leaves = Vec::new();
+        collect_config_leaves(&config, "", &mut leaves);
+
+        assert_eq!(
+            leaves,
+            vec![
+                ("channels.whatsapp.allowFrom".to_string(), json!(["+1"])),
+                ("model.id".to_string(), json!("claude")),
+                ("model.provider".to_string(), json!("anthropic")),
+                ("userTimezone".to_string(), json!("UTC")),
+            ]
+        );
+    }
+
+    #[test]
+    fn render_tool_result_pretty_prints_json_strings() {
+        let payload = json!({ "result": "{\"files\":[\"a.rs\"]}" });
+        assert_eq!(
+            render_tool_result(&payload, true).unwrap(),
+            "Result:\n{\n  \"files\": [\n    \"a.rs\"\n  ]\n}"
+        );
+        assert_eq!(
+            render_tool_result(&payload, false).unwrap(),
+            "Result:\n{\"files\":[\"a.rs\"]}"
+        );
+
+        let broken = json!({ "result": "[not json" });
+        assert_eq!(
+            render_tool_result(&broken, true).unwrap(),
+            "Result:\n[not json"
+        );
+    }
+
+    #[test]
+    fn json_output_adds_ok_flag() {
+        assert_eq!(
+            json_output(json!({ "tools": [] })),
+            json!({ "tools": [], "ok": true })
+        );
+        assert_eq!(
+            json_output(json!(["a"])),
+            json!({ "ok": true, "result": ["a"] })
+        );
+    }
+
+    #[test]
+    fn render_heartbeat_agent_detail_shows_reported_fields() {
+        let state = json!({
+            "agentId": "main",
+            "intervalMinutes": 30,
+            "consecutiveFailures": 2,
+            "deliveryChannel": "whatsapp",
+            "deliveryTarget": "+15551234567",
+            "lastHeartbeatPayload": format!("{{\"prompt\":\"{}\"}}", "x".repeat(300)),
+            "nextHeartbeatAt": null,
+        });
+        let out = render_heartbeat_agent_detail("main", &state);
+        assert!(out.starts_with("Agent: main\n"));
+        assert!(out.contains("  intervalMinutes: 30\n"));
+        assert!(out.contains("  consecutiveFailures: 2\n"));
+        assert!(out.contains("  deliveryChannel: whatsapp\n"));
+        assert!(out.contains("  deliveryTarget: +15551234567\n"));
+        assert!(out.contains("  lastHeartbeatPayload: {\"prompt\":\"xxx"));
+        assert!(out.contains("xxx...\n"));
+        assert!(out.contains("  nextHeartbeatAt: -\n"));
+        assert!(!out.contains("not reported by gateway"));
+
+        let sparse = render_heartbeat_agent_detail("main", &json!({ "agentId": "main" }));
+        assert!(sparse.contains("  deliveryTarget: (not reported by gateway)\n"));
+    }
+
+    #[test]
+    fn session_record_error_reports_unrecorded_calls() {
+        let session = "agent:main:cli:dm:main";
+        assert_eq!(
+            session_record_error(&json!({ "result": "ok", "recorded": true }), session),
+            None
+        );
+        assert_eq!(
+            session_record_error(&json!({ "result": "ok" }), session),
+            None
+        );
+        assert_eq!(
+            session_record_error(
+                &json!({
+                    "result": "ok",
+                    "recorded": false,
+                    "recordError": "session has an active run",
+                }),
+                session
+            )
+            .as_deref(),
+            Some(
+                "Tool ran, but its result was not recorded in session 'agent:main:cli:dm:main': session has an active run"
+            )
+        );
+    }
+
+    #[test]
+    fn discord_send_validates_channel_id_and_embed() {
+        assert!(validate_discord_channel_id("123456789012345678").is_ok());
+        assert!(validate_discord_channel_id("#general").is_err());
+        assert!(validate_discord_channel_id("").is_err());
+
+        assert_eq!(
+            parse_discord_embed(r#"{"title": "Deployed"}"#).unwrap(),
+            json!({ "title": "Deployed" })
+        );
+        assert!(parse_discord_embed(r#"["title"]"#).is_err());
+        assert!(parse_discord_embed("{title").is_err());
+    }
+
+    #[test]
+    fn render_session_search_hit_bolds_the_match() {
+        let hit = json!({
+            "sessionKey": "agent:main:cli:dm:main",
+            "index": 7,
+            "role": "user",
+            "timestamp": 1_700_000_000_000i64,
+            "snippet": "…née talked about deploys",
+            "matchStart": 18,
+            "matchLength": 7,
+        });
+        let plain = render_session_search_hit(&hit, false);
+        assert_eq!(
+            plain,
+            "agent:main:cli:dm:main #7 user 2023-11-14 22:13\n  …née talked about deploys"
+        );
+        let bold = render_session_search_hit(&hit, true);
+        assert!(bold.ends_with("about \x1b[1mdeploys\x1b[0m"));
+    }
+
+    #[test]
+    fn render_config_diff_marks_differing_keys() {
+        let rows = vec![
+            ConfigDiffRow {
+                path: "auth.token",
+                local: Some(Some("old-token-1111".to_string())),
+                gateway: Some("new-token-2222".to_string()),
+                secret: true,
+            },
+            ConfigDiffRow {
+                path: "model.id",
+                local: None,
+                gateway: Some("gpt-4.1".to_string()),
+                secret: false,
+            },
+        ];
+        let out = render_config_diff(&rows);
+        assert!(out.contains("~ auth.token  [local] ****1111  [gateway] ****2222"));
+        assert!(out.contains("  model.id    gpt-4.1 (gateway only)"));
+        assert!(out.contains("1 key(s) differ."));
+        assert!(!out.contains("old-token"));
+
+        let json = config_diff_json(&rows);
+        assert_eq!(json["keys"][0]["differs"], true);
+        assert_eq!(json["keys"][1]["local"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn validate_auto_approve_pattern_checks_regex_syntax() {
+        assert!(validate_auto_approve_pattern("+1555*").is_ok());
+        assert!(validate_auto_approve_pattern(r"/^\+1555\d+$/").is_ok());
+        // Lookbehind is valid JavaScript but not Rust regex syntax.
+        assert!(validate_auto_approve_pattern(r"/(?<=\+1)555\d+/").is_ok());
+        assert!(validate_auto_approve_pattern(r"/[()]\(/").is_ok());
+        assert!(validate_auto_approve_pattern("/([/").is_err());
+        assert!(validate_auto_approve_pattern("/(foo/").is_err());
+        assert!(validate_auto_approve_pattern("/foo)/").is_err());
+        assert!(validate_auto_approve_pattern(r"/foo\/").is_err());
+        assert!(validate_auto_approve_pattern("+1 555*").is_err());
+        assert!(validate_auto_approve_pattern("").is_err());
+    }
+
+    #[test]
+    fn validate_whatsapp_number_requires_country_code() {
+        assert!(validate_whatsapp_number("+1234567890").is_ok());
+        assert!(validate_whatsapp_number("1234567890").is_err());
+        assert!(validate_whatsapp_number("+12-345").is_err());
+        assert!(validate_whatsapp_number("+123").is_err());
+    }
+
+    #[test]
+    fn channel_media_from_url_infers_type_from_extension() {
+        let media = channel_media_from_url("https://example.com/files/cat.JPG?size=large");
+        assert_eq!(media["type"], "image");
+        assert_eq!(media["mimeType"], "image/jpeg");
+        assert_eq!(media["filename"], "cat.JPG");
+
+        let media = channel_media_from_url("https://example.com/report");
+        assert_eq!(media["type"], "document");
+        assert_eq!(media["filename"], "report");
+    }
+
+    #[test]
+    fn closest_config_path_suggests_near_miss() {
+        let config = json!({
+            "model": { "provider": "anthropic", "id": "claude" },
+            "apiKeys": { "anthropic": "****" }
+        });
+        let mut known = Vec::new();
+        collect_config_paths(&config, "", &mut known);
+
+        assert_eq!(
+            closest_config_path("model.provder", &known),
+            Some("model.provider")
+        );
+        assert_eq!(closest_config_path("timezone", &known), None);
+    }
+
+    #[test]
+    fn parse_tool_call_args_names_the_source() {
+        assert_eq!(parse_tool_call_args("  \n", "stdin").unwrap(), json!({}));
+        assert_eq!(
+            parse_tool_call_args(r#"{"command": "ls"}"#, "stdin").unwrap(),
+            json!({ "command": "ls" })
+        );
+        assert!(parse_tool_call_args("{nope", "args file a.json")
+            .unwrap_err()
+            .starts_with("Invalid JSON args on args file a.json:"));
+        assert_eq!(
+            parse_tool_call_args("[1]", "stdin").unwrap_err(),
+            "Args on stdin must be a JSON object"
+        );
+    }
+
+    #[test]
+    fn render_line_diff_marks_changed_lines() {
+        let diff = render_line_diff("a\nb\nc\n", "a\nB\nc\n", false);
+        assert_eq!(diff, " a\n-b\n+B\n c\n");
+    }
+
+    #[test]
+    fn save_qr_png_writes_png_file() {
+        let path = std::env::temp_dir().join(format!("gsv-qr-{}.png", std::process::id()));
+        save_qr_png("2@test-qr-payload", &path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(bytes.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn validate_config_value_checks_known_paths() {
+        assert!(validate_config_value("model.provider", &json!("Anthropic")).is_ok());
+        assert!(validate_config_value("model.provider", &json!("acme")).is_err());
+        assert!(validate_config_value("auth.token", &json!("  ")).is_err());
+        assert!(validate_config_value("timeouts.llmMs", &json!(30000)).is_ok());
+        assert!(validate_config_value("timeouts.llmMs", &json!(-1)).is_err());
+        assert!(validate_config_value("compaction.reserveTokens", &json!("lots")).is_err());
+        assert!(validate_config_value("systemPrompt", &json!(42)).is_ok());
+    }
+
+    #[test]
+    fn render_config_tree_indents_nested_keys() {
+        let config = json!({
+            "model": { "provider": "anthropic" },
+            "channels": ["discord", "whatsapp"]
+        });
+        let rendered = render_config_tree(&config);
+        assert!(rendered.contains("model:\n  provider: anthropic\n"));
+        assert!(rendered.contains("channels: [discord, whatsapp]\n"));
+    }
 }
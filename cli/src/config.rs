@@ -1,7 +1,35 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
 pub const DEFAULT_SESSION_KEY: &str = "agent:main:cli:dm:main";
+const REDACTED_VALUE: &str = "****";
+
+static ACTIVE_PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Select the profile whose config file `CliConfig::load`/`save` use for the
+/// rest of the process. Profile names may contain letters, digits, `-` and `_`.
+pub fn set_active_profile(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!(
+            "Invalid profile name '{}' (use letters, digits, '-' and '_')",
+            name
+        ));
+    }
+    ACTIVE_PROFILE
+        .set(name.to_string())
+        .map_err(|_| "Config profile already selected".to_string())
+}
+
+/// The profile selected with `--profile`/`GSV_PROFILE`, if any.
+pub fn active_profile() -> Option<&'static str> {
+    ACTIVE_PROFILE.get().map(String::as_str)
+}
 
 /// Normalize legacy/alias session keys to canonical format.
 pub fn normalize_session_key(raw: &str) -> String {
@@ -15,7 +43,7 @@ pub fn normalize_session_key(raw: &str) -> String {
 }
 
 /// CLI configuration loaded from ~/.config/gsv/config.toml
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CliConfig {
     /// Gateway connection settings
     #[serde(default)]
@@ -40,16 +68,35 @@ pub struct CliConfig {
     /// Channel settings
     #[serde(default)]
     pub channels: ChannelsConfig,
+
+    /// Shell completion settings
+    #[serde(default)]
+    pub completions: CompletionsConfig,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompletionsConfig {
+    /// Install the completion script for the current shell on `gsv init`
+    #[serde(default)]
+    pub auto_install: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ChannelsConfig {
     /// WhatsApp channel settings
     #[serde(default)]
     pub whatsapp: WhatsAppChannelConfig,
+
+    /// Telegram channel settings
+    #[serde(default)]
+    pub telegram: TelegramChannelConfig,
+
+    /// Slack channel settings
+    #[serde(default)]
+    pub slack: SlackChannelConfig,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct WhatsAppChannelConfig {
     /// WhatsApp channel worker URL (e.g., https://gsv-channel-whatsapp.example.workers.dev)
     pub url: Option<String>,
@@ -58,25 +105,38 @@ pub struct WhatsAppChannelConfig {
     pub token: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TelegramChannelConfig {
+    /// Telegram channel worker URL (e.g., https://gsv-channel-telegram.example.workers.dev)
+    pub url: Option<String>,
+
+    /// Auth token for Telegram channel
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SlackChannelConfig {
+    /// Slack channel worker URL (e.g., https://gsv-channel-slack.example.workers.dev)
+    pub url: Option<String>,
+
+    /// Auth token for Slack channel
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GatewayConfig {
     /// WebSocket URL for the gateway
     pub url: Option<String>,
 
+    /// Additional gateway WebSocket URLs, tried in order as fallbacks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub urls: Vec<String>,
+
     /// Auth token
     pub token: Option<String>,
 }
 
-impl Default for GatewayConfig {
-    fn default() -> Self {
-        Self {
-            url: None,
-            token: None,
-        }
-    }
-}
-
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CloudflareConfig {
     /// Cloudflare account ID
     pub account_id: Option<String>,
@@ -85,7 +145,7 @@ pub struct CloudflareConfig {
     pub api_token: Option<String>,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct R2Config {
     /// Cloudflare Account ID
     pub account_id: Option<String>,
@@ -100,16 +160,88 @@ pub struct R2Config {
     pub bucket: Option<String>,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NodeConfig {
     /// Node ID (namespace prefix for tools)
     pub id: Option<String>,
 
     /// Workspace directory for file tools
     pub workspace: Option<PathBuf>,
+
+    /// Directory for node.log and its rotations (set by `node install --log-dir`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_dir: Option<PathBuf>,
+
+    /// Extra flags appended to `gsv node --foreground` in the daemon service
+    /// (whitelisted: --debug, --quiet, --log-format <json|text>)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_args: Vec<String>,
+
+    /// Capability declarations for tools not built into the CLI
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_tools: Vec<CustomToolConfig>,
+
+    /// Per-tool execution timeouts in seconds (`[node.tool_timeouts]`, e.g. `Bash = 30`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tool_timeouts: HashMap<String, u64>,
+
+    /// Only expose these tools (by name); unset exposes every tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_allowlist: Option<Vec<String>>,
+
+    /// Never expose these tools (e.g. `["Bash"]` on shared nodes)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_denylist: Vec<String>,
+
+    /// Delay between gateway reconnect attempts (`[node.reconnect]`)
+    #[serde(default, skip_serializing_if = "ReconnectConfig::is_default")]
+    pub reconnect: ReconnectConfig,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReconnectConfig {
+    /// Delay before the first reconnect attempt
+    pub initial_delay_ms: u64,
+
+    /// Upper bound for the delay as it grows
+    pub max_delay_ms: u64,
+
+    /// Factor applied to the delay after each failed attempt
+    pub multiplier: f64,
+
+    /// Add ±10% random noise so many nodes don't reconnect in lockstep
+    pub jitter: bool,
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            initial_delay_ms: 3_000,
+            max_delay_ms: 60_000,
+            multiplier: 2.0,
+            jitter: true,
+        }
+    }
+}
+
+impl ReconnectConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CustomToolConfig {
+    /// Tool name as registered with the gateway
+    pub name: String,
+
+    /// Capabilities advertised for this tool (e.g. "shell.exec")
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionConfig {
     /// Default session key
     pub default_key: Option<String>,
@@ -124,14 +256,36 @@ impl Default for SessionConfig {
 }
 
 impl CliConfig {
-    /// Get the config file path
+    /// Get the config file path (of the active profile, if one is selected)
     pub fn config_path() -> Option<PathBuf> {
-        dirs::config_dir().map(|d| d.join("gsv").join("config.toml"))
+        match active_profile() {
+            Some(name) => Self::profile_config_path(name),
+            None => dirs::config_dir().map(|d| d.join("gsv").join("config.toml")),
+        }
+    }
+
+    /// Get the config file path for a named profile
+    pub fn profile_config_path(name: &str) -> Option<PathBuf> {
+        dirs::config_dir().map(|d| {
+            d.join("gsv")
+                .join("profiles")
+                .join(name)
+                .join("config.toml")
+        })
     }
 
     /// Load config from file, returning default if file doesn't exist
     pub fn load() -> Self {
-        let Some(path) = Self::config_path() else {
+        Self::load_from(Self::config_path())
+    }
+
+    /// Load a named profile's config, returning default if it doesn't exist
+    pub fn load_profile(name: &str) -> Self {
+        Self::load_from(Self::profile_config_path(name))
+    }
+
+    fn load_from(path: Option<PathBuf>) -> Self {
+        let Some(path) = path else {
             return Self::default();
         };
 
@@ -167,6 +321,25 @@ impl CliConfig {
         Ok(())
     }
 
+    /// Clone with credentials (tokens, secrets, access keys) replaced by `****`
+    pub fn redacted(&self) -> Self {
+        fn mask(value: &mut Option<String>) {
+            if value.is_some() {
+                *value = Some(REDACTED_VALUE.to_string());
+            }
+        }
+
+        let mut cfg = self.clone();
+        mask(&mut cfg.gateway.token);
+        mask(&mut cfg.cloudflare.api_token);
+        mask(&mut cfg.r2.access_key_id);
+        mask(&mut cfg.r2.secret_access_key);
+        mask(&mut cfg.channels.whatsapp.token);
+        mask(&mut cfg.channels.telegram.token);
+        mask(&mut cfg.channels.slack.token);
+        cfg
+    }
+
     /// Get effective gateway URL (config -> default)
     pub fn gateway_url(&self) -> String {
         self.gateway
@@ -175,6 +348,18 @@ impl CliConfig {
             .unwrap_or_else(|| "ws://localhost:8787/ws".to_string())
     }
 
+    /// Effective gateway URL followed by the `gateway.urls` fallbacks, in the
+    /// order they should be tried, without duplicates.
+    pub fn gateway_urls(&self) -> Vec<String> {
+        let mut urls = vec![self.gateway_url()];
+        for url in &self.gateway.urls {
+            if !urls.contains(url) {
+                urls.push(url.clone());
+            }
+        }
+        urls
+    }
+
     /// Get effective token (config only, no default)
     pub fn gateway_token(&self) -> Option<String> {
         self.gateway.token.clone()
@@ -263,10 +448,46 @@ default_key = "agent:main:cli:dm:main"
 # Optional defaults used by 'gsv node'
 # id = "node-macbook"
 # workspace = "/Users/you/projects"
+# Extra flags for the daemon service (--debug, --quiet, --log-format <json|text>)
+# extra_args = ["--log-format", "text"]
+
+# Restrict which tools this node exposes (a tool may not be in both lists)
+# tool_allowlist = ["Read", "Glob", "Grep"]
+# tool_denylist = ["Bash", "Process"]
+
+# Give up on a tool call after this many seconds (per tool name)
+# [node.tool_timeouts]
+# Bash = 30
+
+# Back off between gateway reconnect attempts (GSV_RECONNECT_* env vars override)
+# [node.reconnect]
+# initial_delay_ms = 3000
+# max_delay_ms = 60000
+# multiplier = 2.0
+# jitter = true
+
+# Capabilities for custom tools (repeat the table per tool)
+# [[node.custom_tools]]
+# name = "Deploy"
+# capabilities = ["shell.exec"]
 
 [channels.whatsapp]
 # WhatsApp channel worker URL
 # url = "https://gsv-channel-whatsapp.example.workers.dev"
 # token = "your-whatsapp-channel-token"
+
+[channels.telegram]
+# Telegram channel worker URL
+# url = "https://gsv-channel-telegram.example.workers.dev"
+# token = "your-telegram-channel-token"
+
+[channels.slack]
+# Slack channel worker URL
+# url = "https://gsv-channel-slack.example.workers.dev"
+# token = "your-slack-channel-token"
+
+[completions]
+# Install shell completions for $SHELL when running 'gsv init'
+# auto_install = true
 "#
 }
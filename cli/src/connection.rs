@@ -7,15 +7,30 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
+use tokio::sync::{mpsc, oneshot, Mutex, OnceCell, RwLock};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
 pub type PendingRequests = Arc<Mutex<HashMap<String, oneshot::Sender<ResponseFrame>>>>;
 pub type EventHandler = Arc<RwLock<Option<Box<dyn Fn(Frame) + Send + Sync>>>>;
 pub type BinaryHandler = Arc<RwLock<Option<Box<dyn Fn(Vec<u8>) + Send + Sync>>>>;
 pub type DisconnectFlag = Arc<AtomicBool>;
+pub type EventSubscriptions =
+    Arc<RwLock<HashMap<String, Vec<(u64, Box<dyn Fn(Frame) + Send + Sync>)>>>>;
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Identifies a handler registered with [`Connection::subscribe`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SubscriptionHandle {
+    event: String,
+    id: u64,
+}
+
+impl SubscriptionHandle {
+    pub fn event(&self) -> &str {
+        &self.event
+    }
+}
 
 const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(15);
 
@@ -46,10 +61,51 @@ pub struct Connection {
     pending: PendingRequests,
     event_handler: EventHandler,
     binary_handler: BinaryHandler,
+    subscriptions: EventSubscriptions,
+    next_subscription_id: AtomicU64,
     disconnected: DisconnectFlag,
 }
 
+/// Deliver an event frame to its subscribers, falling back to the catch-all
+/// handler when nobody subscribed to that event name.
+async fn dispatch_event(subscriptions: &EventSubscriptions, fallback: &EventHandler, frame: Frame) {
+    if let Frame::Evt(evt) = &frame {
+        let subscriptions = subscriptions.read().await;
+        if let Some(handlers) = subscriptions.get(&evt.event) {
+            if !handlers.is_empty() {
+                for (_, handler) in handlers {
+                    handler(frame.clone());
+                }
+                return;
+            }
+        }
+    }
+
+    let handler = fallback.read().await;
+    if let Some(ref h) = *handler {
+        h(frame);
+    }
+}
+
 impl Connection {
+    /// Build a connection that defers the WebSocket handshake until the first
+    /// `request()` or `set_event_handler()` call. Commands that may finish
+    /// without talking to the gateway pay no connection latency.
+    pub fn lazy(
+        url: &str,
+        mode: &str,
+        client_id: Option<String>,
+        token: Option<String>,
+    ) -> LazyConnection {
+        LazyConnection {
+            url: url.to_string(),
+            mode: mode.to_string(),
+            client_id,
+            token,
+            conn: OnceCell::new(),
+        }
+    }
+
     pub async fn connect_with_options(
         url: &str,
         mode: &str,
@@ -66,6 +122,7 @@ impl Connection {
         let pending: PendingRequests = Arc::new(Mutex::new(HashMap::new()));
         let event_handler: EventHandler = Arc::new(RwLock::new(Some(Box::new(on_event))));
         let binary_handler: BinaryHandler = Arc::new(RwLock::new(None));
+        let subscriptions: EventSubscriptions = Arc::new(RwLock::new(HashMap::new()));
         let disconnected: DisconnectFlag = Arc::new(AtomicBool::new(false));
 
         let pending_for_write = pending.clone();
@@ -73,6 +130,7 @@ impl Connection {
         let pending_clone = pending.clone();
         let event_handler_clone = event_handler.clone();
         let binary_handler_clone = binary_handler.clone();
+        let subscriptions_clone = subscriptions.clone();
         let disconnected_clone = disconnected.clone();
 
         tokio::spawn(async move {
@@ -102,10 +160,8 @@ impl Connection {
                                 }
                             }
                             _ => {
-                                let handler = event_handler_clone.read().await;
-                                if let Some(ref h) = *handler {
-                                    h(frame);
-                                }
+                                dispatch_event(&subscriptions_clone, &event_handler_clone, frame)
+                                    .await;
                             }
                         }
                     }
@@ -131,6 +187,8 @@ impl Connection {
             pending,
             event_handler,
             binary_handler,
+            subscriptions,
+            next_subscription_id: AtomicU64::new(1),
             disconnected,
         };
         conn.handshake(mode, tools, node_runtime, client_id, token)
@@ -143,6 +201,40 @@ impl Connection {
         *h = Some(Box::new(handler));
     }
 
+    /// Register a handler for a single event name. Events with at least one
+    /// subscriber bypass the catch-all handler set via `set_event_handler`.
+    pub async fn subscribe(
+        &self,
+        event: &str,
+        handler: impl Fn(Frame) + Send + Sync + 'static,
+    ) -> SubscriptionHandle {
+        let id = self.next_subscription_id.fetch_add(1, Ordering::SeqCst);
+        let mut subscriptions = self.subscriptions.write().await;
+        subscriptions
+            .entry(event.to_string())
+            .or_default()
+            .push((id, Box::new(handler)));
+        SubscriptionHandle {
+            event: event.to_string(),
+            id,
+        }
+    }
+
+    /// Remove a handler registered with `subscribe`. Returns false if it was already removed.
+    pub async fn unsubscribe(&self, handle: SubscriptionHandle) -> bool {
+        let mut subscriptions = self.subscriptions.write().await;
+        let Some(handlers) = subscriptions.get_mut(&handle.event) else {
+            return false;
+        };
+        let before = handlers.len();
+        handlers.retain(|(id, _)| *id != handle.id);
+        let removed = handlers.len() != before;
+        if handlers.is_empty() {
+            subscriptions.remove(&handle.event);
+        }
+        removed
+    }
+
     pub async fn set_binary_handler(&self, handler: impl Fn(Vec<u8>) + Send + Sync + 'static) {
         let mut h = self.binary_handler.write().await;
         *h = Some(Box::new(handler));
@@ -284,6 +376,78 @@ impl Connection {
     }
 }
 
+/// A [`Connection`] that is established on first use. Created by [`Connection::lazy`].
+pub struct LazyConnection {
+    url: String,
+    mode: String,
+    client_id: Option<String>,
+    token: Option<String>,
+    conn: OnceCell<Connection>,
+}
+
+impl LazyConnection {
+    /// Wrap a connection that is already established.
+    pub fn ready(conn: Connection) -> Self {
+        LazyConnection {
+            url: String::new(),
+            mode: String::new(),
+            client_id: None,
+            token: None,
+            conn: OnceCell::new_with(Some(conn)),
+        }
+    }
+
+    /// Whether the handshake has already happened.
+    pub fn is_connected(&self) -> bool {
+        self.conn.initialized()
+    }
+
+    /// Connect if needed and return the underlying connection.
+    pub async fn get(&self) -> Result<&Connection, Box<dyn std::error::Error>> {
+        self.conn
+            .get_or_try_init(|| {
+                Connection::connect_with_options(
+                    &self.url,
+                    &self.mode,
+                    None,
+                    None,
+                    |_| {},
+                    self.client_id.clone(),
+                    self.token.clone(),
+                )
+            })
+            .await
+    }
+
+    pub async fn set_event_handler(
+        &self,
+        handler: impl Fn(Frame) + Send + Sync + 'static,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.get().await?.set_event_handler(handler).await;
+        Ok(())
+    }
+
+    pub async fn request(
+        &self,
+        method: &str,
+        params: Option<Value>,
+    ) -> Result<ResponseFrame, Box<dyn std::error::Error>> {
+        self.get().await?.request(method, params).await
+    }
+
+    pub async fn request_with_timeout(
+        &self,
+        method: &str,
+        params: Option<Value>,
+        timeout: Duration,
+    ) -> Result<ResponseFrame, Box<dyn std::error::Error>> {
+        self.get()
+            .await?
+            .request_with_timeout(method, params, timeout)
+            .await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,4 +469,49 @@ mod tests {
         assert_eq!(error.message, "Connection closed");
         assert!(pending.lock().await.is_empty());
     }
+
+    #[tokio::test]
+    async fn lazy_connection_defers_connect_until_first_request() {
+        let lazy = Connection::lazy("ws://127.0.0.1:1/ws", "client", None, None);
+        assert!(!lazy.is_connected());
+
+        assert!(lazy.request("status", None).await.is_err());
+        assert!(!lazy.is_connected());
+    }
+
+    fn event_frame(event: &str) -> Frame {
+        Frame::Evt(crate::protocol::EventFrame {
+            event: event.to_string(),
+            payload: None,
+            seq: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn dispatch_event_prefers_subscribers_over_fallback() {
+        let subscriptions: EventSubscriptions = Arc::new(RwLock::new(HashMap::new()));
+        let fallback_hits = Arc::new(AtomicU64::new(0));
+        let chat_hits = Arc::new(AtomicU64::new(0));
+
+        let fallback_counter = fallback_hits.clone();
+        let fallback: EventHandler = Arc::new(RwLock::new(Some(Box::new(move |_| {
+            fallback_counter.fetch_add(1, Ordering::SeqCst);
+        }))));
+        let chat_counter = chat_hits.clone();
+        subscriptions.write().await.insert(
+            "chat".to_string(),
+            vec![(
+                1,
+                Box::new(move |_| {
+                    chat_counter.fetch_add(1, Ordering::SeqCst);
+                }),
+            )],
+        );
+
+        dispatch_event(&subscriptions, &fallback, event_frame("chat")).await;
+        dispatch_event(&subscriptions, &fallback, event_frame("tick")).await;
+
+        assert_eq!(chat_hits.load(Ordering::SeqCst), 1);
+        assert_eq!(fallback_hits.load(Ordering::SeqCst), 1);
+    }
 }
@@ -1,16 +1,19 @@
 use crate::config::CliConfig;
 use crate::connection::Connection;
 use base64::Engine;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use reqwest::{multipart, StatusCode};
 use serde::de::DeserializeOwned;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
+use std::cell::Cell;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::io::Cursor;
+use std::io::{Cursor, IsTerminal};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::time::sleep;
 use walkdir::WalkDir;
@@ -33,19 +36,58 @@ const SCRIPT_GATEWAY: &str = "gsv";
 const SCRIPT_CHANNEL_WHATSAPP: &str = "gsv-channel-whatsapp";
 const SCRIPT_CHANNEL_DISCORD: &str = "gsv-channel-discord";
 const SCRIPT_CHANNEL_TEST: &str = "gsv-channel-test";
+const SCRIPT_CHANNEL_TELEGRAM: &str = "gsv-channel-telegram";
+const SCRIPT_CHANNEL_SLACK: &str = "gsv-channel-slack";
+/// Worker scripts owned by gsv; `--name-prefix` only renames references to these.
+const GSV_SCRIPTS: &[&str] = &[
+    SCRIPT_GATEWAY,
+    SCRIPT_CHANNEL_WHATSAPP,
+    SCRIPT_CHANNEL_DISCORD,
+    SCRIPT_CHANNEL_TEST,
+    SCRIPT_CHANNEL_TELEGRAM,
+    SCRIPT_CHANNEL_SLACK,
+];
 const WORKERS_SUBDOMAIN_API_DATE: &str = "2025-08-01";
 const CLOUDFLARE_MAX_ATTEMPTS: usize = 5;
 const CLOUDFLARE_RETRY_BASE_MS: u64 = 400;
+/// Worker deploy error codes that clear up on their own: script locked (10088),
+/// migrations in progress (10092) and concurrent script update (10101).
+const CLOUDFLARE_RETRYABLE_ERROR_CODES: &[i64] = &[10088, 10092, 10101];
 const MAX_SOURCE_MAP_UPLOAD_BYTES: usize = 2 * 1024 * 1024;
+const DISCORD_API_BASE: &str = "https://discord.com/api/v10";
 const TEMPLATE_AGENT_ID: &str = "main";
 const TEMPLATE_SENTINEL_FILE: &str = "SOUL.md";
 static DEPLOY_NOTIFICATION_MODE: AtomicBool = AtomicBool::new(false);
+static DEPLOY_QUIET_MODE: AtomicBool = AtomicBool::new(false);
+static DEPLOY_JSON_MODE: AtomicBool = AtomicBool::new(false);
 
 pub fn set_notification_output(enabled: bool) {
     DEPLOY_NOTIFICATION_MODE.store(enabled, Ordering::Relaxed);
 }
 
+/// Suppress progress output on stdout. Warnings and errors still go to stderr.
+pub fn set_quiet_output(enabled: bool) {
+    DEPLOY_QUIET_MODE.store(enabled, Ordering::Relaxed);
+}
+
+/// Emit structured deploy events (such as `deploy.binding.dropped`) as JSON
+/// log lines on stderr instead of prose warnings.
+pub fn set_json_output(enabled: bool) {
+    DEPLOY_JSON_MODE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn quiet_output() -> bool {
+    DEPLOY_QUIET_MODE.load(Ordering::Relaxed)
+}
+
 fn emit_output_line(line: &str, is_error: bool) {
+    if quiet_output() {
+        if is_error || line.trim_start().starts_with("Warning:") {
+            ::std::eprintln!("{}", line.trim_start());
+        }
+        return;
+    }
+
     if !DEPLOY_NOTIFICATION_MODE.load(Ordering::Relaxed) {
         if is_error {
             ::std::eprintln!("{}", line);
@@ -103,6 +145,36 @@ fn emit_output_line(line: &str, is_error: bool) {
     }
 }
 
+/// Emits a single `deploy.binding.dropped` warning listing service bindings
+/// ("script:BINDING -> service") left out because their target worker is not
+/// deployed. Does nothing when `dropped` is empty.
+pub fn report_dropped_bindings(dropped: &[String]) {
+    if dropped.is_empty() {
+        return;
+    }
+    if DEPLOY_JSON_MODE.load(Ordering::Relaxed) {
+        ::std::eprintln!("{}", dropped_bindings_event(dropped));
+        return;
+    }
+    emit_output_line(
+        &format!(
+            "Warning: deploy.binding.dropped: {} service binding(s) target workers that are not deployed: {}",
+            dropped.len(),
+            dropped.join(", ")
+        ),
+        false,
+    );
+}
+
+fn dropped_bindings_event(dropped: &[String]) -> Value {
+    json!({
+        "level": "warn",
+        "event": "deploy.binding.dropped",
+        "count": dropped.len(),
+        "bindings": dropped,
+    })
+}
+
 fn deploy_println(line: String) {
     emit_output_line(&line, false);
 }
@@ -147,6 +219,23 @@ pub struct CloudflareAccountSummary {
     pub name: String,
 }
 
+/// One uploaded version of a worker script, newest first when listed.
+#[derive(Debug, Clone)]
+pub struct WorkerScriptVersion {
+    pub id: String,
+    pub number: Option<u64>,
+    pub created_on: Option<String>,
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkerScriptVersionEntry {
+    id: String,
+    number: Option<u64>,
+    #[serde(default)]
+    metadata: Value,
+}
+
 #[derive(Debug, Deserialize)]
 struct WorkerManifest {
     entrypoint: String,
@@ -182,6 +271,8 @@ struct WranglerConfig {
     ai: Option<WranglerAiBinding>,
     assets: Option<WranglerAssetsConfig>,
     observability: Option<Value>,
+    #[serde(default)]
+    vars: BTreeMap<String, Value>,
 }
 
 #[derive(Debug, Default, Deserialize, Clone)]
@@ -241,12 +332,154 @@ struct PreparedBundle {
     entrypoint_part_name: String,
     entrypoint_bytes: Vec<u8>,
     source_map: Option<(String, Vec<u8>)>,
+    /// `--name-prefix` already applied to the names above
+    name_prefix: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct DeployApplyResult {
     pub gateway_url: Option<String>,
     pub gateway_existed_before_deploy: bool,
+    /// Service bindings left out because their target worker is missing ("script:BINDING -> service")
+    pub dropped_bindings: Vec<String>,
+    /// workers.dev URL for each deployed component, when known
+    pub component_urls: Vec<(String, String)>,
+}
+
+/// What `apply_deploy` did for one component; collected for `--report`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeployedComponent {
+    pub component: String,
+    pub script_name: String,
+    pub workers_dev_url: Option<String>,
+    /// The worker script was already in the account before this deploy
+    pub existed_before: bool,
+    /// Migration tag the upload moved the worker to, if it carried migrations
+    pub migrations_applied: Option<String>,
+    /// Static assets were uploaded for this worker
+    pub assets_synced: bool,
+}
+
+/// Result of probing one component's workers.dev URL.
+#[derive(Debug, Clone)]
+pub struct ComponentHealth {
+    pub component: String,
+    pub url: String,
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+impl ComponentHealth {
+    /// Any response below 500 means the worker is up and handling requests.
+    pub fn healthy(&self) -> bool {
+        matches!(self.status, Some(status) if status < 500)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DeployApplyOptions {
+    /// Skip ensuring R2 buckets exist (assumes they were created by a previous deploy)
+    pub skip_r2: bool,
+    /// Per-component wrangler config files overlaid on the bundle's config
+    pub wrangler_overrides: HashMap<String, PathBuf>,
+    /// Prefix for worker script and R2 bucket names (`--name-prefix`)
+    pub name_prefix: Option<String>,
+    /// Never upload source maps, whatever the bundle manifest lists
+    pub skip_source_map: bool,
+    /// Skip the check for workers whose migration tag this bundle doesn't know
+    /// (`--force-redeploy`)
+    pub force_redeploy: bool,
+    /// Zone ID and route pattern to bind to the gateway worker
+    /// (`--cloudflare-zone` / `--gateway-route`)
+    pub gateway_route: Option<(String, String)>,
+    /// Print what the deploy would change (`--dry-run`) using read-only API calls
+    pub dry_run: bool,
+    /// Components whose worker script has been uploaded so far; lets callers
+    /// report progress when the deploy fails or is cancelled (e.g. `--apply-timeout`).
+    pub deployed_components: Arc<Mutex<Vec<DeployedComponent>>>,
+}
+
+/// Wrangler config keys the deploy pipeline reads; see `WranglerConfig`.
+const WRANGLER_CONFIG_KEYS: &[&str] = &[
+    "name",
+    "compatibility_date",
+    "compatibility_flags",
+    "migrations",
+    "durable_objects",
+    "r2_buckets",
+    "services",
+    "ai",
+    "assets",
+    "observability",
+    "vars",
+];
+
+/// Parses repeated `<component>:<path>` values for `--wrangler-override`.
+pub fn parse_wrangler_overrides(
+    raw: &[String],
+) -> Result<HashMap<String, PathBuf>, Box<dyn std::error::Error>> {
+    let mut overrides = HashMap::new();
+    for value in raw {
+        let (component, path) = value.split_once(':').ok_or_else(|| {
+            format!(
+                "Invalid --wrangler-override '{}'. Expected <component>:<path>",
+                value
+            )
+        })?;
+        let component = component.trim();
+        if component_to_bundle(component).is_none() {
+            return Err(format!(
+                "Unknown component '{}' in --wrangler-override. Valid components: {}",
+                component,
+                available_components().join(", ")
+            )
+            .into());
+        }
+        let path = PathBuf::from(path.trim());
+        if !path.is_file() {
+            return Err(format!("Wrangler override file not found: {}", path.display()).into());
+        }
+        if overrides.insert(component.to_string(), path).is_some() {
+            return Err(format!(
+                "--wrangler-override given more than once for component '{}'",
+                component
+            )
+            .into());
+        }
+    }
+    Ok(overrides)
+}
+
+/// Overlays top-level keys from `override_path` onto `base`, returning the keys replaced or added.
+fn apply_wrangler_override(
+    base: &mut Value,
+    override_path: &Path,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let raw = fs::read_to_string(override_path)?;
+    let overlay: Value = json5::from_str(&raw).map_err(|e| {
+        format!(
+            "Invalid wrangler override {}: {}",
+            override_path.display(),
+            e
+        )
+    })?;
+    let Value::Object(overlay) = overlay else {
+        return Err(format!(
+            "Wrangler override {} must be a JSON object",
+            override_path.display()
+        )
+        .into());
+    };
+    let base = base
+        .as_object_mut()
+        .ok_or("Bundle wrangler config is not a JSON object")?;
+
+    let mut keys = Vec::new();
+    for (key, value) in overlay {
+        keys.push(key.clone());
+        base.insert(key, value);
+    }
+    Ok(keys)
 }
 
 #[derive(Debug, Clone, Default)]
@@ -264,6 +497,13 @@ struct WorkerScriptSummary {
     migration_tag: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct WorkerRouteSummary {
+    id: String,
+    pattern: String,
+    script: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct AssetsUploadSessionResponse {
     jwt: Option<String>,
@@ -285,6 +525,18 @@ struct AssetFileUpload {
     content_type: String,
 }
 
+/// In-progress assets upload session, cached so an interrupted deploy can resume.
+#[derive(Debug, Serialize, Deserialize)]
+struct AssetsUploadSessionCache {
+    manifest_hash: String,
+    jwt: String,
+    buckets: Vec<Vec<String>>,
+    #[serde(default)]
+    completed_buckets: Vec<usize>,
+    #[serde(default)]
+    completion_jwt: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 struct UploadedAssets {
     jwt: String,
@@ -314,14 +566,70 @@ fn component_to_bundle(component: &str) -> Option<&'static str> {
     }
 }
 
-fn component_to_script_name(component: &str) -> Option<&'static str> {
-    match component {
-        COMPONENT_GATEWAY => Some(SCRIPT_GATEWAY),
-        COMPONENT_CHANNEL_WHATSAPP => Some(SCRIPT_CHANNEL_WHATSAPP),
-        COMPONENT_CHANNEL_DISCORD => Some(SCRIPT_CHANNEL_DISCORD),
-        COMPONENT_CHANNEL_TEST => Some(SCRIPT_CHANNEL_TEST),
-        _ => None,
+fn component_to_script_name(component: &str, name_prefix: Option<&str>) -> Option<String> {
+    let base = match component {
+        COMPONENT_GATEWAY => SCRIPT_GATEWAY,
+        COMPONENT_CHANNEL_WHATSAPP => SCRIPT_CHANNEL_WHATSAPP,
+        COMPONENT_CHANNEL_DISCORD => SCRIPT_CHANNEL_DISCORD,
+        COMPONENT_CHANNEL_TEST => SCRIPT_CHANNEL_TEST,
+        _ => return None,
+    };
+    Some(prefixed_name(name_prefix, base))
+}
+
+/// Applies a `--name-prefix` to a worker or bucket name (`staging` + `gsv` -> `staging-gsv`).
+fn prefixed_name(name_prefix: Option<&str>, name: &str) -> String {
+    match name_prefix {
+        Some(prefix) if prefix.ends_with('-') => format!("{}{}", prefix, name),
+        Some(prefix) => format!("{}-{}", prefix, name),
+        None => name.to_string(),
+    }
+}
+
+/// Worker and bucket names only allow lowercase letters, digits and dashes.
+pub fn validate_name_prefix(prefix: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let valid = !prefix.is_empty()
+        && !prefix.starts_with('-')
+        && prefix
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid {
+        return Err(format!(
+            "Invalid --name-prefix '{}'. Use lowercase letters, digits and dashes (e.g. staging)",
+            prefix
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Rename a bundle's worker and every gsv-owned resource it references
+/// (service bindings, cross-script Durable Objects, R2 buckets).
+fn apply_name_prefix(bundle: &mut PreparedBundle, name_prefix: &str) {
+    let prefix = Some(name_prefix);
+    bundle.script_name = prefixed_name(prefix, &bundle.script_name);
+    bundle.wrangler.name = bundle.script_name.clone();
+    // Bindings to user-defined workers keep their target.
+    for binding in &mut bundle.wrangler.services {
+        if GSV_SCRIPTS.contains(&binding.service.as_str()) {
+            binding.service = prefixed_name(prefix, &binding.service);
+        }
+    }
+    if let Some(durable_objects) = &mut bundle.wrangler.durable_objects {
+        for binding in &mut durable_objects.bindings {
+            if let Some(script_name) = &binding.script_name {
+                if GSV_SCRIPTS.contains(&script_name.as_str()) {
+                    binding.script_name = Some(prefixed_name(prefix, script_name));
+                }
+            }
+        }
     }
+    for bucket in &mut bundle.wrangler.r2_buckets {
+        if let Some(bucket_name) = &bucket.bucket_name {
+            bucket.bucket_name = Some(prefixed_name(prefix, bucket_name));
+        }
+    }
+    bundle.name_prefix = Some(name_prefix.to_string());
 }
 
 pub fn available_components() -> &'static [&'static str] {
@@ -551,11 +859,104 @@ pub fn install_bundles_from_dir(
     Ok(())
 }
 
+/// Progress bars for bundle downloads, only when stdout is an interactive
+/// terminal showing plain (non-quiet, non-wizard) output.
+fn download_progress() -> Option<MultiProgress> {
+    let plain_terminal = std::io::stdout().is_terminal()
+        && !quiet_output()
+        && !DEPLOY_NOTIFICATION_MODE.load(Ordering::Relaxed);
+    plain_terminal.then(MultiProgress::new)
+}
+
+/// A byte-count bar when the size is known, otherwise a spinner (GitHub
+/// sometimes omits `Content-Length` for large release assets).
+fn download_progress_bar(total: Option<u64>, component: &str) -> ProgressBar {
+    let bar = match total {
+        Some(total) => ProgressBar::new(total).with_style(
+            ProgressStyle::with_template(
+                "{msg:24} [{bar:30}] {bytes}/{total_bytes} {bytes_per_sec} eta {eta}",
+            )
+            .expect("valid progress template")
+            .progress_chars("=> "),
+        ),
+        None => {
+            let bar = ProgressBar::new_spinner().with_style(
+                ProgressStyle::with_template("{spinner} {msg:22} {bytes} {bytes_per_sec}")
+                    .expect("valid progress template"),
+            );
+            bar.enable_steady_tick(Duration::from_millis(100));
+            bar
+        }
+    };
+    bar.with_message(component.to_string())
+}
+
+/// Downloads one release bundle and verifies it against `expected` (sha256).
+async fn download_verified_bundle(
+    client: &reqwest::Client,
+    release_url: &str,
+    tag: &str,
+    component: &str,
+    bundle_file: &str,
+    expected: &str,
+    progress: Option<&MultiProgress>,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let bundle_url = format!("{}/{}", release_url, bundle_file);
+    if progress.is_none() {
+        println!("Downloading {} from {}", component, bundle_url);
+    }
+    let bundle_resp = client
+        .get(bundle_url)
+        .header("User-Agent", "gsv-cli")
+        .send()
+        .await?;
+    if bundle_resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(format!(
+            "Bundle '{}' not found on release {}. \
+This release likely predates Cloudflare bundle publishing.",
+            bundle_file, tag
+        )
+        .into());
+    }
+    let mut bundle_resp = bundle_resp.error_for_status()?;
+
+    let total = bundle_resp.content_length();
+    let bar = progress.map(|multi| multi.add(download_progress_bar(total, component)));
+    let mut bytes = Vec::with_capacity(total.unwrap_or(0) as usize);
+    while let Some(chunk) = bundle_resp.chunk().await? {
+        bytes.extend_from_slice(&chunk);
+        if let Some(bar) = &bar {
+            bar.inc(chunk.len() as u64);
+        }
+    }
+
+    let actual = sha256_hex(&bytes);
+    if actual != expected {
+        if let Some(bar) = &bar {
+            bar.abandon_with_message(format!("{} (checksum mismatch)", component));
+        }
+        return Err(format!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            bundle_file, expected, actual
+        )
+        .into());
+    }
+
+    match &bar {
+        Some(bar) => bar.finish_with_message(format!("{} (checksum OK)", component)),
+        None => println!("Checksum OK for {}", bundle_file),
+    }
+    Ok(bytes)
+}
+
+/// Downloads and extracts release bundles for `components`. With `parallel`
+/// the bundle downloads run concurrently once the checksum file is fetched.
 pub async fn fetch_bundles(
     cfg: &CliConfig,
     version: &str,
     components: &[String],
     force: bool,
+    parallel: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let tag = resolve_release_tag(version).await?;
     let release_url = base_release_url(&tag);
@@ -582,10 +983,10 @@ Use a newer release tag or publish a release that includes Cloudflare bundles.",
     let version_root = bundles_root(cfg).join(&tag);
     fs::create_dir_all(&version_root)?;
 
+    let mut pending = Vec::new();
     for component in components {
         let bundle_file = component_to_bundle(component)
             .ok_or_else(|| format!("Unsupported component '{}'", component))?;
-        let bundle_url = format!("{}/{}", release_url, bundle_file);
         let component_dir = version_root.join(component);
 
         if component_dir.exists() {
@@ -607,33 +1008,46 @@ Use a newer release tag or publish a release that includes Cloudflare bundles.",
             )
         })?;
 
-        println!("Downloading {} from {}", component, bundle_url);
-        let bundle_resp = client
-            .get(bundle_url)
-            .header("User-Agent", "gsv-cli")
-            .send()
-            .await?;
-        if bundle_resp.status() == reqwest::StatusCode::NOT_FOUND {
-            return Err(format!(
-                "Bundle '{}' not found on release {}. \
-This release likely predates Cloudflare bundle publishing.",
-                bundle_file, tag
-            )
-            .into());
-        }
-        let bytes = bundle_resp.error_for_status()?.bytes().await?;
+        pending.push((component, bundle_file, expected, component_dir));
+    }
 
-        let actual = sha256_hex(&bytes);
-        if actual != *expected {
-            return Err(format!(
-                "Checksum mismatch for {}: expected {}, got {}",
-                bundle_file, expected, actual
-            )
-            .into());
+    let progress = download_progress();
+    let downloads = if parallel {
+        futures_util::future::join_all(pending.iter().map(
+            |(component, bundle_file, expected, _)| {
+                download_verified_bundle(
+                    &client,
+                    &release_url,
+                    &tag,
+                    component,
+                    bundle_file,
+                    expected,
+                    progress.as_ref(),
+                )
+            },
+        ))
+        .await
+    } else {
+        let mut downloads = Vec::with_capacity(pending.len());
+        for (component, bundle_file, expected, _) in &pending {
+            downloads.push(
+                download_verified_bundle(
+                    &client,
+                    &release_url,
+                    &tag,
+                    component,
+                    bundle_file,
+                    expected,
+                    progress.as_ref(),
+                )
+                .await,
+            );
         }
+        downloads
+    };
 
-        println!("Checksum OK for {}", bundle_file);
-        extract_bundle(bytes.as_ref(), &version_root)?;
+    for ((component, _, _, component_dir), bytes) in pending.iter().zip(downloads) {
+        extract_bundle(&bytes?, &version_root)?;
         println!("Extracted {} to {}", component, component_dir.display());
     }
 
@@ -704,6 +1118,198 @@ pub async fn inspect_bundle(
     Ok(())
 }
 
+pub fn format_byte_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn directory_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Removes all but the `keep` most recently cached bundle versions (by directory
+/// mtime). The tag recorded in `latest.txt` is never removed.
+pub fn prune_cached_bundles(
+    cfg: &CliConfig,
+    keep: usize,
+    dry_run: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root = bundles_root(cfg);
+    let latest = read_local_latest_tag(cfg);
+
+    let mut versions = Vec::new();
+    if root.is_dir() {
+        for entry in fs::read_dir(&root)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let modified = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+            versions.push((entry.file_name().to_string_lossy().to_string(), modified));
+        }
+    }
+    versions.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut pruned = 0usize;
+    let mut freed = 0u64;
+    for (version, _) in versions.into_iter().skip(keep) {
+        if latest.as_deref() == Some(version.as_str()) {
+            println!("Keeping {} (latest tag)", version);
+            continue;
+        }
+        let version_dir = root.join(&version);
+        let size = directory_size(&version_dir);
+        if dry_run {
+            println!("Would remove {} ({})", version, format_byte_size(size));
+        } else {
+            fs::remove_dir_all(&version_dir)?;
+            println!("Removed {} ({})", version, format_byte_size(size));
+        }
+        pruned += 1;
+        freed += size;
+    }
+
+    let noun = if pruned == 1 { "version" } else { "versions" };
+    if dry_run {
+        println!(
+            "Would prune {} old bundle {} (would free ~{}).",
+            pruned,
+            noun,
+            format_byte_size(freed)
+        );
+    } else {
+        println!(
+            "Pruned {} old bundle {} (freed ~{}).",
+            pruned,
+            noun,
+            format_byte_size(freed)
+        );
+    }
+    Ok(())
+}
+
+/// Prints a table of bundle versions cached under `~/.gsv/deploy/bundles`.
+/// The tag recorded in `latest.txt` is marked with `*`.
+pub fn list_cached_bundles(cfg: &CliConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let root = bundles_root(cfg);
+    let latest = read_local_latest_tag(cfg);
+
+    let mut rows = Vec::new();
+    if root.is_dir() {
+        for entry in fs::read_dir(&root)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let version = entry.file_name().to_string_lossy().to_string();
+            let version_dir = entry.path();
+
+            let mut components = Vec::new();
+            for component_entry in fs::read_dir(&version_dir)? {
+                let component_entry = component_entry?;
+                if !component_entry.file_type()?.is_dir() {
+                    continue;
+                }
+                let manifest_path = component_entry.path().join("manifest.json");
+                let name = fs::read_to_string(&manifest_path)
+                    .ok()
+                    .and_then(|raw| serde_json::from_str::<BundleManifest>(&raw).ok())
+                    .map(|manifest| manifest.component)
+                    .unwrap_or_else(|| {
+                        format!("{}?", component_entry.file_name().to_string_lossy())
+                    });
+                components.push(name);
+            }
+            components.sort();
+
+            let cached_at = fs::metadata(&version_dir)
+                .and_then(|metadata| metadata.modified())
+                .map(|modified| {
+                    chrono::DateTime::<chrono::Local>::from(modified)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string()
+                })
+                .unwrap_or_else(|_| "-".to_string());
+
+            let marker = if latest.as_deref() == Some(version.as_str()) {
+                "*"
+            } else {
+                " "
+            };
+            rows.push((
+                format!("{}{}", marker, version),
+                if components.is_empty() {
+                    "-".to_string()
+                } else {
+                    components.join(", ")
+                },
+                format_byte_size(directory_size(&version_dir)),
+                cached_at,
+            ));
+        }
+    }
+
+    if rows.is_empty() {
+        println!("No cached bundles in {}", root.display());
+        return Ok(());
+    }
+    rows.sort_by(|a, b| a.0[1..].cmp(&b.0[1..]));
+
+    let version_width = rows.iter().map(|r| r.0.len()).max().unwrap_or(0).max(8);
+    let components_width = rows.iter().map(|r| r.1.len()).max().unwrap_or(0).max(10);
+    let size_width = rows.iter().map(|r| r.2.len()).max().unwrap_or(0).max(4);
+
+    println!(
+        "{:<vw$}  {:<cw$}  {:>sw$}  Cached At",
+        " Version",
+        "Components",
+        "Size",
+        vw = version_width,
+        cw = components_width,
+        sw = size_width
+    );
+    for (version, components, size, cached_at) in &rows {
+        println!(
+            "{:<vw$}  {:<cw$}  {:>sw$}  {}",
+            version,
+            components,
+            size,
+            cached_at,
+            vw = version_width,
+            cw = components_width,
+            sw = size_width
+        );
+    }
+    if latest.is_some() {
+        println!();
+        println!(
+            "* latest tag recorded in {}",
+            latest_tag_path(cfg).display()
+        );
+    }
+
+    Ok(())
+}
+
 pub async fn resolve_cloudflare_account_id(
     api_token: &str,
     configured_account_id: Option<&str>,
@@ -776,6 +1382,14 @@ fn is_retryable_cloudflare_status(status: StatusCode) -> bool {
     status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
 }
 
+fn is_retryable_cloudflare_error_code(errors: &[CloudflareApiMessage]) -> bool {
+    errors.iter().any(|error| {
+        error
+            .code
+            .is_some_and(|code| CLOUDFLARE_RETRYABLE_ERROR_CODES.contains(&code))
+    })
+}
+
 fn is_retryable_transport_error(error: &reqwest::Error) -> bool {
     error.is_timeout() || error.is_connect() || error.is_request() || error.is_body()
 }
@@ -806,6 +1420,34 @@ where
                     tokio::time::sleep(Duration::from_millis(delay)).await;
                     continue;
                 }
+                if status.is_client_error() && attempt < CLOUDFLARE_MAX_ATTEMPTS {
+                    // Some transient failures only show up as error codes in the
+                    // body, so buffer it and rebuild the response if we don't retry.
+                    let headers = response.headers().clone();
+                    let body = response.bytes().await?;
+                    let errors =
+                        serde_json::from_slice::<CloudflareApiResponse<Option<Value>>>(&body)
+                            .ok()
+                            .and_then(|parsed| parsed.errors)
+                            .unwrap_or_default();
+                    if is_retryable_cloudflare_error_code(&errors) {
+                        let delay = retry_delay_ms(attempt);
+                        println!(
+                            "Warning: {} failed with {} (attempt {}/{}). Retrying in {}ms...",
+                            action,
+                            summarize_cloudflare_messages(Some(&errors), None),
+                            attempt,
+                            CLOUDFLARE_MAX_ATTEMPTS,
+                            delay
+                        );
+                        tokio::time::sleep(Duration::from_millis(delay)).await;
+                        continue;
+                    }
+                    let mut rebuilt = http::Response::new(body);
+                    *rebuilt.status_mut() = status;
+                    *rebuilt.headers_mut() = headers;
+                    return Ok(reqwest::Response::from(rebuilt));
+                }
                 return Ok(response);
             }
             Err(error) => {
@@ -881,44 +1523,82 @@ fn decode_list_from_value<T: DeserializeOwned>(
     Err(format!("Cloudflare API list shape is unexpected: {}", value).into())
 }
 
-async fn parse_cloudflare_response<T: DeserializeOwned>(
-    response: reqwest::Response,
-    context: &str,
-) -> Result<T, Box<dyn std::error::Error>> {
-    let status = response.status();
-    let body = response.text().await?;
+/// Error from a Cloudflare API call, keeping the raw response body so callers
+/// can inspect it without parsing the error message.
+#[derive(Debug)]
+pub struct CloudflareResponseError {
+    pub context: String,
+    pub status: StatusCode,
+    pub body: String,
+    /// Deserialization error for an unexpected body shape; empty when the body parsed.
+    pub parse_error: String,
+    /// Summary of the Cloudflare error/messages entries, when present.
+    pub message: String,
+}
 
-    if !status.is_success() {
-        if let Ok(envelope) = serde_json::from_str::<CloudflareApiResponse<Value>>(&body) {
-            return Err(format!(
+impl std::fmt::Display for CloudflareResponseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.parse_error.is_empty() {
+            write!(
+                f,
+                "{} returned an unexpected response: {} (body: {})",
+                self.context, self.parse_error, self.body
+            )
+        } else if !self.status.is_success() {
+            write!(
+                f,
                 "{} failed ({}): {}",
-                context,
-                status,
-                summarize_cloudflare_messages(
-                    envelope.errors.as_deref(),
-                    envelope.messages.as_deref(),
-                )
+                self.context, self.status, self.message
             )
-            .into());
+        } else {
+            write!(f, "{} failed: {}", self.context, self.message)
         }
-
-        return Err(format!("{} failed ({}): {}", context, status, body).into());
     }
+}
 
-    let envelope: CloudflareApiResponse<T> = serde_json::from_str(&body).map_err(|e| {
-        format!(
-            "{} returned an unexpected response: {} (body: {})",
-            context, e, body
+impl std::error::Error for CloudflareResponseError {}
+
+async fn parse_cloudflare_response<T: DeserializeOwned>(
+    response: reqwest::Response,
+    context: &str,
+) -> Result<T, CloudflareResponseError> {
+    let status = response.status();
+    let error = |body: String, parse_error: String, message: String| CloudflareResponseError {
+        context: context.to_string(),
+        status,
+        body,
+        parse_error,
+        message,
+    };
+
+    let body = response.text().await.map_err(|e| {
+        error(
+            String::new(),
+            format!("failed to read body: {}", e),
+            String::new(),
         )
     })?;
 
+    if !status.is_success() {
+        let message = match serde_json::from_str::<CloudflareApiResponse<Value>>(&body) {
+            Ok(envelope) => summarize_cloudflare_messages(
+                envelope.errors.as_deref(),
+                envelope.messages.as_deref(),
+            ),
+            Err(_) => body.clone(),
+        };
+        return Err(error(body, String::new(), message));
+    }
+
+    let envelope: CloudflareApiResponse<T> = match serde_json::from_str(&body) {
+        Ok(envelope) => envelope,
+        Err(e) => return Err(error(body, e.to_string(), String::new())),
+    };
+
     if !envelope.success {
-        return Err(format!(
-            "{} failed: {}",
-            context,
-            summarize_cloudflare_messages(envelope.errors.as_deref(), envelope.messages.as_deref(),)
-        )
-        .into());
+        let message =
+            summarize_cloudflare_messages(envelope.errors.as_deref(), envelope.messages.as_deref());
+        return Err(error(body, String::new(), message));
     }
 
     Ok(envelope.result)
@@ -1013,6 +1693,7 @@ async fn ensure_r2_bucket_exists(
             )
             .await
             .err()
+            .map(Into::into)
             .unwrap_or_else(|| "Unknown R2 lookup failure".into());
             Err(error)
         }
@@ -1087,9 +1768,91 @@ async fn enable_workers_dev_for_script(
     Ok(())
 }
 
-async fn upload_worker_script(
+async fn list_worker_routes(
     client: &reqwest::Client,
-    account_id: &str,
+    api_token: &str,
+    zone_id: &str,
+) -> Result<Vec<WorkerRouteSummary>, Box<dyn std::error::Error>> {
+    let url = cloudflare_api_url(&format!("/zones/{}/workers/routes", zone_id));
+    let response = send_cloudflare_request_with_retry(
+        || {
+            client
+                .get(&url)
+                .bearer_auth(api_token)
+                .header("Content-Type", "application/json")
+                .send()
+        },
+        "List worker routes",
+    )
+    .await?;
+    let result: Value = parse_cloudflare_response(response, "List worker routes").await?;
+    decode_list_from_value(result, &["routes", "items"])
+}
+
+/// Routes `pattern` in the given zone to `script_name` via the Workers Routes API.
+/// An existing route for the same pattern is repointed rather than duplicated, so
+/// redeploys succeed. Returns false when the route was already in place.
+async fn ensure_worker_route(
+    client: &reqwest::Client,
+    account_id: &str,
+    api_token: &str,
+    zone_id: &str,
+    pattern: &str,
+    script_name: &str,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let existing = list_worker_routes(client, api_token, zone_id)
+        .await
+        .map_err(|error| format!("List worker routes (zone {}): {}", zone_id, error))?
+        .into_iter()
+        .find(|route| route.pattern == pattern);
+    if existing
+        .as_ref()
+        .is_some_and(|route| route.script.as_deref() == Some(script_name))
+    {
+        return Ok(false);
+    }
+
+    let (url, action) = match existing.as_ref() {
+        Some(route) => (
+            cloudflare_api_url(&format!("/zones/{}/workers/routes/{}", zone_id, route.id)),
+            format!("Update route {} for {}", pattern, script_name),
+        ),
+        None => (
+            cloudflare_api_url(&format!("/zones/{}/workers/routes", zone_id)),
+            format!("Add route {} for {}", pattern, script_name),
+        ),
+    };
+    let body = json!({
+        "pattern": pattern,
+        "script": script_name
+    });
+    let response = send_cloudflare_request_with_retry(
+        || {
+            let request = if existing.is_some() {
+                client.put(&url)
+            } else {
+                client.post(&url)
+            };
+            request.bearer_auth(api_token).json(&body).send()
+        },
+        &action,
+    )
+    .await?;
+    let _: Value = parse_cloudflare_response(response, &action)
+        .await
+        .map_err(|error| {
+            format!(
+                "{} (zone {} in account {}): {}",
+                action, zone_id, account_id, error
+            )
+        })?;
+    Ok(true)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn upload_worker_script(
+    client: &reqwest::Client,
+    account_id: &str,
     api_token: &str,
     script_name: &str,
     metadata: Value,
@@ -1265,6 +2028,7 @@ async fn r2_bucket_exists(
             )
             .await
             .err()
+            .map(Into::into)
             .unwrap_or_else(|| "Unknown R2 lookup failure".into());
             Err(error)
         }
@@ -1511,6 +2275,7 @@ async fn purge_r2_bucket_objects(
     api_token: &str,
     bucket_name: &str,
     jurisdiction: Option<&str>,
+    on_progress: &dyn Fn(usize),
 ) -> Result<usize, Box<dyn std::error::Error>> {
     let mut total_deleted = 0usize;
     let mut cursor: Option<String> = None;
@@ -1545,6 +2310,7 @@ async fn purge_r2_bucket_objects(
                 total_deleted += 1;
             }
         }
+        on_progress(total_deleted);
 
         if let Some(next_cursor) = page.next_cursor {
             if !seen_cursors.insert(next_cursor.clone()) {
@@ -1577,6 +2343,8 @@ fn load_prepared_bundle(
     cfg: &CliConfig,
     version: &str,
     component: &str,
+    wrangler_override: Option<&Path>,
+    skip_source_map: bool,
 ) -> Result<PreparedBundle, Box<dyn std::error::Error>> {
     let bundle_dir = bundles_root(cfg).join(version).join(component);
     if !bundle_dir.exists() {
@@ -1600,7 +2368,33 @@ fn load_prepared_bundle(
             .unwrap_or("wrangler.jsonc"),
     );
     let raw_wrangler = fs::read_to_string(&wrangler_path)?;
-    let wrangler: WranglerConfig = json5::from_str(&raw_wrangler)?;
+    let wrangler: WranglerConfig = if let Some(override_path) = wrangler_override {
+        let mut merged: Value = json5::from_str(&raw_wrangler)?;
+        let keys = apply_wrangler_override(&mut merged, override_path)?;
+        println!(
+            "Applying wrangler override for {} from {}: {}",
+            component,
+            override_path.display(),
+            if keys.is_empty() {
+                "(no fields)".to_string()
+            } else {
+                keys.join(", ")
+            }
+        );
+        for key in keys
+            .iter()
+            .filter(|key| !WRANGLER_CONFIG_KEYS.contains(&key.as_str()))
+        {
+            println!(
+                "Warning: wrangler override field '{}' is not used by gsv deploy.",
+                key
+            );
+        }
+        serde_json::from_value(merged)
+            .map_err(|e| format!("Wrangler config for {} after override: {}", component, e))?
+    } else {
+        json5::from_str(&raw_wrangler)?
+    };
     if wrangler.name.trim().is_empty() {
         return Err(format!(
             "Wrangler config in {} is missing worker name",
@@ -1622,7 +2416,9 @@ fn load_prepared_bundle(
         })?
         .to_string();
 
-    let source_map = if let Some(source_map_rel) = &manifest.worker.source_map {
+    let source_map = if skip_source_map {
+        None
+    } else if let Some(source_map_rel) = &manifest.worker.source_map {
         let source_map_path = bundle_dir.join(source_map_rel);
         if source_map_path.exists() {
             let source_map_part_name = Path::new(source_map_rel)
@@ -1652,6 +2448,7 @@ fn load_prepared_bundle(
         entrypoint_part_name,
         entrypoint_bytes,
         source_map,
+        name_prefix: None,
     })
 }
 
@@ -1659,17 +2456,18 @@ fn service_bindings_for_bundle(
     bundle: &PreparedBundle,
     selected_components: &HashSet<String>,
     available_scripts: &HashSet<String>,
-) -> Vec<WranglerServiceBinding> {
+) -> (Vec<WranglerServiceBinding>, Vec<String>) {
     let mut bindings = bundle.wrangler.services.clone();
+    let gateway_script = prefixed_name(bundle.name_prefix.as_deref(), SCRIPT_GATEWAY);
 
     if bundle.component == COMPONENT_CHANNEL_WHATSAPP
         && !bindings.iter().any(|binding| binding.binding == "GATEWAY")
         && (selected_components.contains(COMPONENT_GATEWAY)
-            || available_scripts.contains(SCRIPT_GATEWAY))
+            || available_scripts.contains(&gateway_script))
     {
         bindings.push(WranglerServiceBinding {
             binding: "GATEWAY".to_string(),
-            service: SCRIPT_GATEWAY.to_string(),
+            service: gateway_script,
             environment: None,
             entrypoint: Some("GatewayEntrypoint".to_string()),
         });
@@ -1683,13 +2481,14 @@ fn service_bindings_for_bundle(
     {
         bindings.push(WranglerServiceBinding {
             binding: "CHANNEL_TEST".to_string(),
-            service: "gsv-channel-test".to_string(),
+            service: prefixed_name(bundle.name_prefix.as_deref(), SCRIPT_CHANNEL_TEST),
             environment: None,
             entrypoint: Some("TestChannel".to_string()),
         });
     }
 
     let mut filtered = Vec::new();
+    let mut dropped = Vec::new();
     for mut binding in bindings {
         if bundle.component == COMPONENT_GATEWAY
             && binding.binding == "CHANNEL_WHATSAPP"
@@ -1702,14 +2501,14 @@ fn service_bindings_for_bundle(
         if keep {
             filtered.push(binding);
         } else {
-            println!(
-                "Warning: dropping service binding {} -> {} for {} because target worker is not yet available in account.",
-                binding.binding, binding.service, bundle.script_name
-            );
+            dropped.push(format!(
+                "{}:{} -> {}",
+                bundle.script_name, binding.binding, binding.service
+            ));
         }
     }
 
-    filtered
+    (filtered, dropped)
 }
 
 fn migration_tag(step: &Value) -> Option<&str> {
@@ -1779,6 +2578,23 @@ fn build_migrations_payload(
     }
 }
 
+/// True when an existing script carries a migration tag this bundle never
+/// defines, which usually means another tool (e.g. `wrangler deploy`) owns it.
+/// gsv's own inferred `auto-v1` tag is never foreign, so bundles that start
+/// declaring migrations can still upgrade it.
+fn has_foreign_migration_tag(config: &WranglerConfig, existing_tag: Option<&str>) -> bool {
+    let Some(existing_tag) = existing_tag else {
+        return false;
+    };
+    if existing_tag == "auto-v1" {
+        return false;
+    }
+    !config
+        .migrations
+        .iter()
+        .any(|step| migration_tag(step) == Some(existing_tag))
+}
+
 fn build_inferred_do_migration(config: &WranglerConfig) -> Option<Value> {
     let mut classes = config
         .durable_objects
@@ -1932,11 +2748,55 @@ fn build_assets_metadata_config(
     Ok(Value::Object(config))
 }
 
+fn assets_session_cache_path(cfg: &CliConfig, version: &str, component: &str) -> PathBuf {
+    cfg.gsv_home()
+        .join("deploy")
+        .join(version)
+        .join(component)
+        .join("assets-session.json")
+}
+
+/// Decode the `exp` claim of a JWT without verifying it.
+fn jwt_expiry_secs(jwt: &str) -> Option<i64> {
+    let payload = jwt.split('.').nth(1)?;
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    let claims: Value = serde_json::from_slice(&bytes).ok()?;
+    claims.get("exp").and_then(Value::as_i64)
+}
+
+fn load_assets_session_cache(path: &Path, manifest_hash: &str) -> Option<AssetsUploadSessionCache> {
+    let raw = fs::read_to_string(path).ok()?;
+    let cache: AssetsUploadSessionCache = serde_json::from_str(&raw).ok()?;
+    if cache.manifest_hash != manifest_hash {
+        return None;
+    }
+    // Leave a minute of headroom so the session does not expire mid-upload.
+    let expires_at = jwt_expiry_secs(&cache.jwt)?;
+    if expires_at <= chrono::Utc::now().timestamp() + 60 {
+        return None;
+    }
+    Some(cache)
+}
+
+fn save_assets_session_cache(
+    path: &Path,
+    cache: &AssetsUploadSessionCache,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_vec(cache)?)?;
+    Ok(())
+}
+
 async fn sync_assets_for_bundle(
     client: &reqwest::Client,
     account_id: &str,
     api_token: &str,
     bundle: &PreparedBundle,
+    session_cache_path: &Path,
 ) -> Result<Option<UploadedAssets>, Box<dyn std::error::Error>> {
     let Some(assets_dir_rel) = bundle.manifest.assets_dir.as_deref() else {
         return Ok(None);
@@ -1994,99 +2854,140 @@ async fn sync_assets_for_bundle(
         "manifest": Value::Object(manifest)
     });
 
-    let session_url = cloudflare_api_url(&format!(
-        "/accounts/{}/workers/scripts/{}/assets-upload-session",
-        account_id, bundle.script_name
-    ));
-    let session_response = send_cloudflare_request_with_retry(
-        || {
-            client
-                .post(&session_url)
-                .bearer_auth(api_token)
-                .json(&session_payload)
-                .send()
-        },
-        &format!("Start assets upload for {}", bundle.script_name),
-    )
-    .await?;
-    let session: AssetsUploadSessionResponse =
-        parse_cloudflare_response(session_response, "Start assets upload").await?;
+    let manifest_hash = sha256_hex(session_payload.to_string().as_bytes());
 
-    let mut completion_jwt = session.jwt.clone();
-    if !session.buckets.is_empty() {
-        let upload_jwt = session.jwt.as_deref().ok_or_else(|| {
-            format!(
-                "Assets upload session for {} did not return an upload jwt",
-                bundle.script_name
-            )
-        })?;
-        println!(
-            "Uploading {} static asset bucket(s) for {}.",
-            session.buckets.len(),
-            bundle.script_name
-        );
-
-        let mut files_by_hash = HashMap::new();
-        for file in &files {
-            files_by_hash
-                .entry(file.hash.clone())
-                .or_insert(file.clone());
+    let mut cache = match load_assets_session_cache(session_cache_path, &manifest_hash) {
+        Some(cache) => {
+            println!(
+                "Resuming assets upload for {} ({}/{} bucket(s) already uploaded).",
+                bundle.script_name,
+                cache.completed_buckets.len(),
+                cache.buckets.len()
+            );
+            cache
         }
+        None => {
+            let session_url = cloudflare_api_url(&format!(
+                "/accounts/{}/workers/scripts/{}/assets-upload-session",
+                account_id, bundle.script_name
+            ));
+            let session_response = send_cloudflare_request_with_retry(
+                || {
+                    client
+                        .post(&session_url)
+                        .bearer_auth(api_token)
+                        .json(&session_payload)
+                        .send()
+                },
+                &format!("Start assets upload for {}", bundle.script_name),
+            )
+            .await?;
+            let session: AssetsUploadSessionResponse =
+                parse_cloudflare_response(session_response, "Start assets upload").await?;
 
-        let upload_url =
-            cloudflare_api_url(&format!("/accounts/{}/workers/assets/upload", account_id));
-        for (bucket_index, bucket) in session.buckets.iter().enumerate() {
-            let mut bucket_parts = Vec::new();
-            for hash in bucket {
-                let file = files_by_hash.get(hash).ok_or_else(|| {
+            if session.buckets.is_empty() {
+                let _ = fs::remove_file(session_cache_path);
+                let jwt = session.jwt.ok_or_else(|| {
                     format!(
-                        "Cloudflare requested unknown asset hash {} for {}",
-                        hash, bundle.script_name
+                        "Assets upload for {} did not return a completion jwt",
+                        bundle.script_name
                     )
                 })?;
-                let bytes = fs::read(&file.absolute_path)?;
-                bucket_parts.push((
-                    hash.clone(),
-                    base64::engine::general_purpose::STANDARD.encode(bytes),
-                    file.content_type.clone(),
-                ));
+                let config = build_assets_metadata_config(bundle, &assets_dir)?;
+                return Ok(Some(UploadedAssets { jwt, config }));
             }
 
-            let action = format!(
-                "Upload assets bucket {}/{} for {}",
-                bucket_index + 1,
-                session.buckets.len(),
-                bundle.script_name
-            );
-            let response = send_cloudflare_request_with_retry(
-                || async {
-                    let mut form = multipart::Form::new();
-                    for (hash, encoded, content_type) in &bucket_parts {
-                        let part = multipart::Part::text(encoded.clone())
-                            .file_name(hash.clone())
-                            .mime_str(content_type)?;
-                        form = form.part(hash.clone(), part);
-                    }
+            let jwt = session.jwt.ok_or_else(|| {
+                format!(
+                    "Assets upload session for {} did not return an upload jwt",
+                    bundle.script_name
+                )
+            })?;
+            let cache = AssetsUploadSessionCache {
+                manifest_hash,
+                jwt,
+                buckets: session.buckets,
+                completed_buckets: Vec::new(),
+                completion_jwt: None,
+            };
+            save_assets_session_cache(session_cache_path, &cache)?;
+            cache
+        }
+    };
 
-                    client
-                        .post(&upload_url)
-                        .bearer_auth(upload_jwt)
-                        .query(&[("base64", "true")])
-                        .multipart(form)
-                        .send()
-                        .await
-                },
-                &action,
-            )
-            .await?;
-            let upload_result: AssetsUploadBucketResponse =
-                parse_cloudflare_response(response, &action).await?;
-            if let Some(jwt) = upload_result.jwt {
-                completion_jwt = Some(jwt);
-            }
+    let upload_jwt = cache.jwt.clone();
+    let total_buckets = cache.buckets.len();
+    println!(
+        "Uploading {} static asset bucket(s) for {}.",
+        total_buckets - cache.completed_buckets.len(),
+        bundle.script_name
+    );
+
+    let mut files_by_hash = HashMap::new();
+    for file in &files {
+        files_by_hash
+            .entry(file.hash.clone())
+            .or_insert(file.clone());
+    }
+
+    let upload_url = cloudflare_api_url(&format!("/accounts/{}/workers/assets/upload", account_id));
+    for bucket_index in 0..total_buckets {
+        if cache.completed_buckets.contains(&bucket_index) {
+            continue;
+        }
+        let mut bucket_parts = Vec::new();
+        for hash in &cache.buckets[bucket_index] {
+            let file = files_by_hash.get(hash).ok_or_else(|| {
+                format!(
+                    "Cloudflare requested unknown asset hash {} for {}",
+                    hash, bundle.script_name
+                )
+            })?;
+            let bytes = fs::read(&file.absolute_path)?;
+            bucket_parts.push((
+                hash.clone(),
+                base64::engine::general_purpose::STANDARD.encode(bytes),
+                file.content_type.clone(),
+            ));
         }
+
+        let action = format!(
+            "Upload assets bucket {}/{} for {}",
+            bucket_index + 1,
+            total_buckets,
+            bundle.script_name
+        );
+        let response = send_cloudflare_request_with_retry(
+            || async {
+                let mut form = multipart::Form::new();
+                for (hash, encoded, content_type) in &bucket_parts {
+                    let part = multipart::Part::text(encoded.clone())
+                        .file_name(hash.clone())
+                        .mime_str(content_type)?;
+                    form = form.part(hash.clone(), part);
+                }
+
+                client
+                    .post(&upload_url)
+                    .bearer_auth(&upload_jwt)
+                    .query(&[("base64", "true")])
+                    .multipart(form)
+                    .send()
+                    .await
+            },
+            &action,
+        )
+        .await?;
+        let upload_result: AssetsUploadBucketResponse =
+            parse_cloudflare_response(response, &action).await?;
+        if let Some(jwt) = upload_result.jwt {
+            cache.completion_jwt = Some(jwt);
+        }
+        cache.completed_buckets.push(bucket_index);
+        save_assets_session_cache(session_cache_path, &cache)?;
     }
 
+    let completion_jwt = cache.completion_jwt.clone();
     let jwt = completion_jwt.ok_or_else(|| {
         format!(
             "Assets upload for {} did not return a completion jwt",
@@ -2094,6 +2995,7 @@ async fn sync_assets_for_bundle(
         )
     })?;
     let config = build_assets_metadata_config(bundle, &assets_dir)?;
+    let _ = fs::remove_file(session_cache_path);
 
     Ok(Some(UploadedAssets { jwt, config }))
 }
@@ -2162,6 +3064,7 @@ fn collect_files_for_r2_prefix(
     Ok(out)
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn put_r2_object(
     client: &reqwest::Client,
     account_id: &str,
@@ -2300,6 +3203,7 @@ async fn sync_templates_for_bundle(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_upload_metadata(
     bundle: &PreparedBundle,
     selected_components: &HashSet<String>,
@@ -2309,7 +3213,7 @@ fn build_upload_metadata(
     script_exists: bool,
     uploaded_assets: Option<&UploadedAssets>,
     keep_assets: bool,
-) -> Result<Value, Box<dyn std::error::Error>> {
+) -> Result<(Value, Vec<String>), Box<dyn std::error::Error>> {
     let compatibility_date = bundle
         .wrangler
         .compatibility_date
@@ -2353,7 +3257,9 @@ fn build_upload_metadata(
         metadata_bindings.push(value);
     }
 
-    for service in service_bindings_for_bundle(bundle, selected_components, available_scripts) {
+    let (services, dropped_bindings) =
+        service_bindings_for_bundle(bundle, selected_components, available_scripts);
+    for service in services {
         let mut value = json!({
             "name": service.binding,
             "type": "service",
@@ -2388,6 +3294,22 @@ fn build_upload_metadata(
         }
     }
 
+    // Like wrangler: string vars are plain text, anything else is a JSON binding.
+    for (name, value) in &bundle.wrangler.vars {
+        metadata_bindings.push(match value {
+            Value::String(text) => json!({
+                "name": name,
+                "type": "plain_text",
+                "text": text
+            }),
+            other => json!({
+                "name": name,
+                "type": "json",
+                "json": other
+            }),
+        });
+    }
+
     let mut metadata = json!({
         "main_module": bundle.entrypoint_part_name,
         "bindings": metadata_bindings,
@@ -2429,7 +3351,7 @@ fn build_upload_metadata(
         metadata["keep_assets"] = json!(true);
     }
 
-    Ok(metadata)
+    Ok((metadata, dropped_bindings))
 }
 
 pub async fn apply_deploy(
@@ -2438,16 +3360,36 @@ pub async fn apply_deploy(
     api_token: &str,
     version: &str,
     components: &[String],
+    options: &DeployApplyOptions,
 ) -> Result<DeployApplyResult, Box<dyn std::error::Error>> {
     if components.is_empty() {
         return Err("No components requested for deployment".into());
     }
 
+    if options.skip_source_map {
+        println!("Skipping source map upload (--skip-source-map).");
+    }
     let mut prepared = components
         .iter()
-        .map(|component| load_prepared_bundle(cfg, version, component))
+        .map(|component| {
+            load_prepared_bundle(
+                cfg,
+                version,
+                component,
+                options
+                    .wrangler_overrides
+                    .get(component)
+                    .map(PathBuf::as_path),
+                options.skip_source_map,
+            )
+        })
         .collect::<Result<Vec<_>, _>>()?;
     prepared.sort_by_key(|bundle| deploy_order(&bundle.component));
+    if let Some(name_prefix) = options.name_prefix.as_deref() {
+        for bundle in &mut prepared {
+            apply_name_prefix(bundle, name_prefix);
+        }
+    }
 
     let selected_components: HashSet<String> = components.iter().cloned().collect();
 
@@ -2456,7 +3398,24 @@ pub async fn apply_deploy(
         list_worker_scripts(&client, account_id, api_token).await?;
     let existing_scripts: HashSet<String> =
         existing_scripts_with_migrations.keys().cloned().collect();
-    let gateway_existed_before_deploy = existing_scripts.contains(SCRIPT_GATEWAY);
+
+    if !options.force_redeploy {
+        for bundle in &prepared {
+            let existing_tag = existing_scripts_with_migrations
+                .get(&bundle.script_name)
+                .and_then(|tag| tag.as_deref());
+            if has_foreign_migration_tag(&bundle.wrangler, existing_tag) {
+                println!(
+                    "Warning: worker '{}' was previously deployed by a different tool (migration_tag mismatch). Use --force-redeploy to deploy over it without this check.",
+                    bundle.script_name
+                );
+            }
+        }
+    }
+    let gateway_existed_before_deploy = existing_scripts.contains(&prefixed_name(
+        options.name_prefix.as_deref(),
+        SCRIPT_GATEWAY,
+    ));
     let mut available_scripts = existing_scripts.clone();
 
     let mut required_buckets = HashSet::new();
@@ -2469,7 +3428,29 @@ pub async fn apply_deploy(
         }
     }
 
-    if !required_buckets.is_empty() {
+    if options.dry_run {
+        let mut sorted_buckets: Vec<(String, Option<String>)> =
+            required_buckets.into_iter().collect();
+        sorted_buckets.sort_by(|a, b| a.0.cmp(&b.0));
+        let plan = DeployPlan {
+            prepared: &prepared,
+            selected_components: &selected_components,
+            existing_scripts_with_migrations: &existing_scripts_with_migrations,
+            buckets: &sorted_buckets,
+        };
+        let dropped_bindings =
+            print_deploy_plan(&client, account_id, api_token, &plan, options).await?;
+        return Ok(DeployApplyResult {
+            gateway_url: None,
+            gateway_existed_before_deploy,
+            dropped_bindings,
+            component_urls: Vec::new(),
+        });
+    }
+
+    if options.skip_r2 {
+        println!("\nSkipping R2 bucket creation (--skip-r2).");
+    } else if !required_buckets.is_empty() {
         println!("\nEnsuring R2 buckets:");
         let mut sorted_buckets: Vec<(String, Option<String>)> =
             required_buckets.into_iter().collect();
@@ -2504,14 +3485,21 @@ pub async fn apply_deploy(
         };
 
     let mut uploaded_assets_by_script: HashMap<String, UploadedAssets> = HashMap::new();
+    let mut component_urls = Vec::new();
 
     println!("\nDeploying workers (pass 1/2):");
     for bundle in &prepared {
         println!("Deploying {} ({})", bundle.component, bundle.script_name);
 
         if bundle.manifest.assets_dir.is_some() {
-            if let Some(uploaded_assets) =
-                sync_assets_for_bundle(&client, account_id, api_token, bundle).await?
+            if let Some(uploaded_assets) = sync_assets_for_bundle(
+                &client,
+                account_id,
+                api_token,
+                bundle,
+                &assets_session_cache_path(cfg, version, &bundle.component),
+            )
+            .await?
             {
                 uploaded_assets_by_script.insert(bundle.script_name.clone(), uploaded_assets);
             }
@@ -2520,7 +3508,7 @@ pub async fn apply_deploy(
             sync_templates_for_bundle(&client, account_id, api_token, bundle).await?;
         }
 
-        let metadata = build_upload_metadata(
+        let (metadata, _) = build_upload_metadata(
             bundle,
             &selected_components,
             &available_scripts,
@@ -2532,6 +3520,10 @@ pub async fn apply_deploy(
             uploaded_assets_by_script.get(&bundle.script_name),
             false,
         )?;
+        let migrations_applied = metadata
+            .pointer("/migrations/new_tag")
+            .and_then(Value::as_str)
+            .map(str::to_string);
         let source_map_for_upload = bundle.source_map.as_ref().and_then(|(name, bytes)| {
             if bytes.len() <= MAX_SOURCE_MAP_UPLOAD_BYTES {
                 Some((name.clone(), bytes.clone()))
@@ -2559,6 +3551,16 @@ pub async fn apply_deploy(
         .await?;
         println!("Uploaded {}", bundle.script_name);
         available_scripts.insert(bundle.script_name.clone());
+        if let Ok(mut deployed) = options.deployed_components.lock() {
+            deployed.push(DeployedComponent {
+                component: bundle.component.clone(),
+                script_name: bundle.script_name.clone(),
+                workers_dev_url: None,
+                existed_before: existing_scripts.contains(&bundle.script_name),
+                migrations_applied,
+                assets_synced: uploaded_assets_by_script.contains_key(&bundle.script_name),
+            });
+        }
 
         match enable_workers_dev_for_script(&client, account_id, api_token, &bundle.script_name)
             .await
@@ -2566,10 +3568,14 @@ pub async fn apply_deploy(
             Ok(()) => {
                 if let Some(subdomain) = account_subdomain.as_deref() {
                     let workers_domain = workers_dev_domain(subdomain);
-                    println!(
-                        "workers.dev URL: https://{}.{}",
-                        bundle.script_name, workers_domain
-                    );
+                    let url = format!("https://{}.{}", bundle.script_name, workers_domain);
+                    println!("workers.dev URL: {}", url);
+                    if let Ok(mut deployed) = options.deployed_components.lock() {
+                        if let Some(entry) = deployed.last_mut() {
+                            entry.workers_dev_url = Some(url.clone());
+                        }
+                    }
+                    component_urls.push((bundle.component.clone(), url));
                 } else {
                     println!("workers.dev enabled for {}", bundle.script_name);
                 }
@@ -2581,12 +3587,32 @@ pub async fn apply_deploy(
                 );
             }
         }
+
+        if bundle.component == COMPONENT_GATEWAY {
+            if let Some((zone_id, pattern)) = options.gateway_route.as_ref() {
+                let changed = ensure_worker_route(
+                    &client,
+                    account_id,
+                    api_token,
+                    zone_id,
+                    pattern,
+                    &bundle.script_name,
+                )
+                .await?;
+                if changed {
+                    println!("Route {} -> {}", pattern, bundle.script_name);
+                } else {
+                    println!("Route {} -> {} (unchanged)", pattern, bundle.script_name);
+                }
+            }
+        }
     }
 
     println!("\nFinalizing service bindings (pass 2/2):");
+    let mut dropped_bindings = Vec::new();
+    let mut finalized = Vec::with_capacity(prepared.len());
     for bundle in &prepared {
-        println!("Finalizing {} ({})", bundle.component, bundle.script_name);
-        let metadata = build_upload_metadata(
+        let (metadata, dropped) = build_upload_metadata(
             bundle,
             &selected_components,
             &available_scripts,
@@ -2596,6 +3622,14 @@ pub async fn apply_deploy(
             None,
             bundle.manifest.assets_dir.is_some(),
         )?;
+        dropped_bindings.extend(dropped);
+        finalized.push((bundle, metadata));
+    }
+    // Report before uploading so a failed or timed-out upload still surfaces them.
+    report_dropped_bindings(&dropped_bindings);
+
+    for (bundle, metadata) in finalized {
+        println!("Finalizing {} ({})", bundle.component, bundle.script_name);
         let source_map_for_upload = bundle.source_map.as_ref().and_then(|(name, bytes)| {
             if bytes.len() <= MAX_SOURCE_MAP_UPLOAD_BYTES {
                 Some((name.clone(), bytes.clone()))
@@ -2639,28 +3673,310 @@ pub async fn apply_deploy(
     Ok(DeployApplyResult {
         gateway_url,
         gateway_existed_before_deploy,
+        dropped_bindings,
+        component_urls,
     })
 }
 
+/// One-line description of a migration step for `--migrate` output, e.g.
+/// `new_sqlite_classes: Session; renamed_classes: Old -> New`.
+fn describe_migration_step(step: &Value) -> String {
+    let Some(map) = step.as_object() else {
+        return step.to_string();
+    };
+    map.iter()
+        .map(|(kind, value)| {
+            let items: Vec<String> = value
+                .as_array()
+                .map(|items| {
+                    items
+                        .iter()
+                        .map(|item| match item {
+                            Value::String(name) => name.clone(),
+                            _ => match (
+                                item.get("from").and_then(Value::as_str),
+                                item.get("to").and_then(Value::as_str),
+                            ) {
+                                (Some(from), Some(to)) => format!("{} -> {}", from, to),
+                                _ => item.to_string(),
+                            },
+                        })
+                        .collect()
+                })
+                .unwrap_or_else(|| vec![value.to_string()]);
+            format!("{}: {}", kind, items.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Applies the gateway bundle's outstanding Durable Object migrations to an
+/// already deployed gateway worker (`deploy up --component gateway --migrate`).
+/// Only the steps after the worker's current migration tag are sent, so
+/// existing Durable Object state is kept. Nothing is uploaded when the worker
+/// is already at the bundle's latest tag. Returns the applied steps.
+pub async fn migrate_gateway_durable_objects(
+    cfg: &CliConfig,
+    version: &str,
+    account_id: &str,
+    api_token: &str,
+    options: &DeployApplyOptions,
+) -> Result<Vec<Value>, Box<dyn std::error::Error>> {
+    let mut bundle = load_prepared_bundle(
+        cfg,
+        version,
+        COMPONENT_GATEWAY,
+        options
+            .wrangler_overrides
+            .get(COMPONENT_GATEWAY)
+            .map(PathBuf::as_path),
+        options.skip_source_map,
+    )?;
+    if let Some(name_prefix) = options.name_prefix.as_deref() {
+        apply_name_prefix(&mut bundle, name_prefix);
+    }
+    let script_name = bundle.script_name.clone();
+
+    let client = reqwest::Client::new();
+    let existing_scripts = list_worker_scripts(&client, account_id, api_token).await?;
+    let Some(current_tag) = existing_scripts.get(&script_name) else {
+        return Err(format!(
+            "Gateway worker '{}' is not deployed yet; run `gsv deploy up --component gateway` first",
+            script_name
+        )
+        .into());
+    };
+    let current_tag = current_tag.as_deref();
+    if !options.force_redeploy && has_foreign_migration_tag(&bundle.wrangler, current_tag) {
+        println!(
+            "Warning: worker '{}' has migration tag '{}', which this bundle doesn't define. Use --force-redeploy to migrate without this check.",
+            script_name,
+            current_tag.unwrap_or("none")
+        );
+    }
+
+    let Some(migrations) = build_migrations_payload(&bundle.wrangler.migrations, current_tag)
+    else {
+        println!(
+            "{} is up to date (migration tag {}); nothing to migrate.",
+            script_name,
+            current_tag.unwrap_or("none")
+        );
+        return Ok(Vec::new());
+    };
+    let steps = migrations
+        .get("steps")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    println!(
+        "Migrating {} ({} -> {}):",
+        script_name,
+        current_tag.unwrap_or("none"),
+        migrations
+            .get("new_tag")
+            .and_then(Value::as_str)
+            .unwrap_or("none")
+    );
+    for (index, step) in steps.iter().enumerate() {
+        println!("  {}. {}", index + 1, describe_migration_step(step));
+    }
+
+    let selected_components = HashSet::from([COMPONENT_GATEWAY.to_string()]);
+    let available_scripts: HashSet<String> = existing_scripts.keys().cloned().collect();
+    let (metadata, dropped) = build_upload_metadata(
+        &bundle,
+        &selected_components,
+        &available_scripts,
+        current_tag,
+        true,
+        true,
+        None,
+        bundle.manifest.assets_dir.is_some(),
+    )?;
+    report_dropped_bindings(&dropped);
+    let source_map_for_upload = bundle.source_map.as_ref().and_then(|(name, bytes)| {
+        (bytes.len() <= MAX_SOURCE_MAP_UPLOAD_BYTES).then(|| (name.clone(), bytes.clone()))
+    });
+
+    upload_worker_script(
+        &client,
+        account_id,
+        api_token,
+        &script_name,
+        metadata,
+        &bundle.entrypoint_part_name,
+        bundle.entrypoint_bytes.clone(),
+        source_map_for_upload,
+    )
+    .await?;
+    println!(
+        "Applied {} migration step(s) to {}",
+        steps.len(),
+        script_name
+    );
+
+    Ok(steps)
+}
+
+/// What `apply_deploy` has resolved before it would start changing anything;
+/// input for `print_deploy_plan`.
+struct DeployPlan<'a> {
+    prepared: &'a [PreparedBundle],
+    selected_components: &'a HashSet<String>,
+    existing_scripts_with_migrations: &'a HashMap<String, Option<String>>,
+    buckets: &'a [(String, Option<String>)],
+}
+
+/// Prints the `--dry-run` plan for `apply_deploy`: `[+]` for things that would
+/// be created, `[~]` for updates and `[-]` for service bindings that would be
+/// dropped. Only read-only Cloudflare endpoints are called.
+async fn print_deploy_plan(
+    client: &reqwest::Client,
+    account_id: &str,
+    api_token: &str,
+    plan: &DeployPlan<'_>,
+    options: &DeployApplyOptions,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let DeployPlan {
+        prepared,
+        selected_components,
+        existing_scripts_with_migrations,
+        buckets,
+    } = *plan;
+    println!("\nDry run: no changes will be made.");
+
+    println!("\nWorkers:");
+    for bundle in prepared {
+        let new_tag = bundle.wrangler.migrations.last().and_then(migration_tag);
+        match existing_scripts_with_migrations.get(&bundle.script_name) {
+            None => match new_tag {
+                Some(new_tag) => println!(
+                    "[+] {} ({}, migration tag {})",
+                    bundle.script_name, bundle.component, new_tag
+                ),
+                None => println!("[+] {} ({})", bundle.script_name, bundle.component),
+            },
+            Some(current_tag) => {
+                let current_tag = current_tag.as_deref();
+                if new_tag.is_some() && new_tag != current_tag {
+                    println!(
+                        "[~] {} ({}, migration tag {} -> {})",
+                        bundle.script_name,
+                        bundle.component,
+                        current_tag.unwrap_or("none"),
+                        new_tag.unwrap_or("none")
+                    );
+                } else {
+                    println!("[~] {} ({})", bundle.script_name, bundle.component);
+                }
+            }
+        }
+    }
+
+    if !buckets.is_empty() {
+        println!("\nR2 buckets:");
+        for (bucket_name, jurisdiction) in buckets {
+            if options.skip_r2 {
+                println!("    {} (skipped, --skip-r2)", bucket_name);
+            } else if r2_bucket_exists(
+                client,
+                account_id,
+                api_token,
+                bucket_name,
+                jurisdiction.as_deref(),
+            )
+            .await?
+            {
+                println!("    {} (exists)", bucket_name);
+            } else {
+                println!("[+] {}", bucket_name);
+            }
+        }
+    }
+
+    if let Some((_, pattern)) = options.gateway_route.as_ref() {
+        if let Some(gateway) = prepared
+            .iter()
+            .find(|bundle| bundle.component == COMPONENT_GATEWAY)
+        {
+            println!("\nRoutes:");
+            println!("[+] {} -> {}", pattern, gateway.script_name);
+        }
+    }
+
+    let mut available_scripts: HashSet<String> =
+        existing_scripts_with_migrations.keys().cloned().collect();
+    available_scripts.extend(prepared.iter().map(|bundle| bundle.script_name.clone()));
+    let mut dropped_bindings = Vec::new();
+    for bundle in prepared {
+        let (_, dropped) = build_upload_metadata(
+            bundle,
+            selected_components,
+            &available_scripts,
+            None,
+            false,
+            true,
+            None,
+            false,
+        )?;
+        dropped_bindings.extend(dropped);
+    }
+    if !dropped_bindings.is_empty() {
+        println!("\nService bindings (target worker missing):");
+        for binding in &dropped_bindings {
+            println!("[-] {}", binding);
+        }
+    }
+
+    Ok(dropped_bindings)
+}
+
+/// Sends one GET to each component URL and reports the HTTP status.
+pub async fn verify_deploy(component_urls: &[(String, String)]) -> Vec<ComponentHealth> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new());
+
+    let mut results = Vec::with_capacity(component_urls.len());
+    for (component, url) in component_urls {
+        let (status, error) = match client.get(url).send().await {
+            Ok(response) => (Some(response.status().as_u16()), None),
+            Err(error) => (None, Some(error.to_string())),
+        };
+        results.push(ComponentHealth {
+            component: component.clone(),
+            url: url.clone(),
+            status,
+            error,
+        });
+    }
+    results
+}
+
 pub async fn destroy_deploy(
     account_id: &str,
     api_token: &str,
     components: &[String],
     delete_bucket_resource: bool,
     purge_bucket_resource: bool,
+    keep_bucket_resource: bool,
+    name_prefix: Option<&str>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     if components.is_empty() {
         return Err("No components requested for teardown".into());
     }
+    let storage_bucket_name = prefixed_name(name_prefix, DEFAULT_STORAGE_BUCKET_NAME);
 
     let mut component_order = components.to_vec();
     component_order.sort_by_key(|component| deploy_order(component));
 
     let mut scripts_to_delete = Vec::new();
     for component in &component_order {
-        let script_name = component_to_script_name(component)
+        let script_name = component_to_script_name(component, name_prefix)
             .ok_or_else(|| format!("Unsupported component '{}'", component))?;
-        scripts_to_delete.push((component.clone(), script_name.to_string()));
+        scripts_to_delete.push((component.clone(), script_name));
     }
 
     let selected_components: HashSet<String> = components.iter().cloned().collect();
@@ -2681,48 +3997,46 @@ pub async fn destroy_deploy(
         if purge_bucket_resource {
             println!(
                 "Purging objects from R2 bucket {} before deletion...",
-                DEFAULT_STORAGE_BUCKET_NAME
+                storage_bucket_name
             );
+            let last_reported = Cell::new(0usize);
             let deleted_objects = purge_r2_bucket_objects(
                 &client,
                 account_id,
                 api_token,
-                DEFAULT_STORAGE_BUCKET_NAME,
+                &storage_bucket_name,
                 None,
+                &|deleted| {
+                    if deleted / 100 > last_reported.get() / 100 {
+                        last_reported.set(deleted);
+                        println!("Purging bucket: deleted {} objects so far…", deleted);
+                    }
+                },
             )
             .await?;
             if deleted_objects > 0 {
                 println!(
                     "Purged {} object(s) from R2 bucket {}",
-                    deleted_objects, DEFAULT_STORAGE_BUCKET_NAME
+                    deleted_objects, storage_bucket_name
                 );
             } else {
-                println!("R2 bucket {} is already empty", DEFAULT_STORAGE_BUCKET_NAME);
+                println!("R2 bucket {} is already empty", storage_bucket_name);
             }
         }
 
-        let delete_result = delete_r2_bucket(
-            &client,
-            account_id,
-            api_token,
-            DEFAULT_STORAGE_BUCKET_NAME,
-            None,
-        )
-        .await?;
+        let delete_result =
+            delete_r2_bucket(&client, account_id, api_token, &storage_bucket_name, None).await?;
         match delete_result {
             DeleteBucketResult::Deleted => {
-                println!("Deleted R2 bucket {}", DEFAULT_STORAGE_BUCKET_NAME);
+                println!("Deleted R2 bucket {}", storage_bucket_name);
             }
             DeleteBucketResult::NotFound => {
-                println!(
-                    "R2 bucket {} was already absent",
-                    DEFAULT_STORAGE_BUCKET_NAME
-                );
+                println!("R2 bucket {} was already absent", storage_bucket_name);
             }
             DeleteBucketResult::NotEmpty => {
                 println!(
                     "R2 bucket {} was not deleted because it is not empty.",
-                    DEFAULT_STORAGE_BUCKET_NAME
+                    storage_bucket_name
                 );
                 if purge_bucket_resource {
                     println!(
@@ -2735,10 +4049,15 @@ pub async fn destroy_deploy(
                 }
             }
         }
+    } else if keep_bucket_resource {
+        println!(
+            "R2 bucket {} preserved. Data retained.",
+            storage_bucket_name
+        );
     } else if selected_components.contains(COMPONENT_GATEWAY) {
         println!(
             "R2 bucket {} retained (use --delete-bucket to remove)",
-            DEFAULT_STORAGE_BUCKET_NAME
+            storage_bucket_name
         );
     }
 
@@ -2746,11 +4065,193 @@ pub async fn destroy_deploy(
     Ok(())
 }
 
+fn component_script_name(
+    component: &str,
+    name_prefix: Option<&str>,
+    scripts: &HashMap<String, Option<String>>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    // Older, unprefixed deployments named the gateway script "gateway".
+    if component == COMPONENT_GATEWAY
+        && name_prefix.is_none()
+        && !scripts.contains_key(SCRIPT_GATEWAY)
+        && scripts.contains_key("gateway")
+    {
+        return Ok("gateway".to_string());
+    }
+    Ok(component_to_script_name(component, name_prefix)
+        .ok_or_else(|| format!("Unsupported component '{}'", component))?)
+}
+
+/// Resolves the deployed worker script name for `component`.
+pub async fn deployed_script_name(
+    account_id: &str,
+    api_token: &str,
+    component: &str,
+    name_prefix: Option<&str>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let client = reqwest::Client::new();
+    let scripts = list_worker_scripts(&client, account_id, api_token).await?;
+    let script_name = component_script_name(component, name_prefix, &scripts)?;
+    if !scripts.contains_key(&script_name) {
+        return Err(format!(
+            "Component '{}' is not deployed (no worker script '{}')",
+            component, script_name
+        )
+        .into());
+    }
+    Ok(script_name)
+}
+
+/// Lists up to `limit` uploaded versions of `script_name`, newest first.
+pub async fn list_worker_script_versions(
+    account_id: &str,
+    api_token: &str,
+    script_name: &str,
+    limit: usize,
+) -> Result<Vec<WorkerScriptVersion>, Box<dyn std::error::Error>> {
+    let client = reqwest::Client::new();
+    let url = cloudflare_api_url(&format!(
+        "/accounts/{}/workers/scripts/{}/versions",
+        account_id, script_name
+    ));
+    let action = format!("List versions of {}", script_name);
+    let response = send_cloudflare_request_with_retry(
+        || {
+            client
+                .get(&url)
+                .bearer_auth(api_token)
+                .header("Content-Type", "application/json")
+                .send()
+        },
+        &action,
+    )
+    .await?;
+    let result: Value = parse_cloudflare_response(response, &action).await?;
+    let mut entries: Vec<WorkerScriptVersionEntry> =
+        decode_list_from_value(result, &["items", "versions"])?;
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.number));
+    entries.truncate(limit);
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| {
+            let metadata_str = |key: &str| {
+                entry
+                    .metadata
+                    .get(key)
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            };
+            WorkerScriptVersion {
+                created_on: metadata_str("created_on"),
+                source: metadata_str("source"),
+                id: entry.id,
+                number: entry.number,
+            }
+        })
+        .collect())
+}
+
+/// Returns the version ID serving the largest share of traffic in the
+/// latest deployment of `script_name`, if any.
+pub async fn active_worker_script_version(
+    account_id: &str,
+    api_token: &str,
+    script_name: &str,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let client = reqwest::Client::new();
+    let url = cloudflare_api_url(&format!(
+        "/accounts/{}/workers/scripts/{}/deployments",
+        account_id, script_name
+    ));
+    let action = format!("List deployments of {}", script_name);
+    let response = send_cloudflare_request_with_retry(
+        || {
+            client
+                .get(&url)
+                .bearer_auth(api_token)
+                .header("Content-Type", "application/json")
+                .send()
+        },
+        &action,
+    )
+    .await?;
+    let result: Value = parse_cloudflare_response(response, &action).await?;
+    let deployments: Vec<Value> = decode_list_from_value(result, &["deployments", "items"])?;
+
+    Ok(deployments
+        .first()
+        .and_then(|deployment| deployment.get("versions"))
+        .and_then(|versions| versions.as_array())
+        .and_then(|versions| {
+            versions.iter().max_by(|a, b| {
+                let pct = |v: &Value| v.get("percentage").and_then(|p| p.as_f64()).unwrap_or(0.0);
+                pct(a).total_cmp(&pct(b))
+            })
+        })
+        .and_then(|version| version.get("version_id"))
+        .and_then(|id| id.as_str())
+        .map(str::to_string))
+}
+
+/// Creates a deployment sending 100% of traffic to `version_id`.
+pub async fn activate_worker_script_version(
+    account_id: &str,
+    api_token: &str,
+    script_name: &str,
+    version_id: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::Client::new();
+    let url = cloudflare_api_url(&format!(
+        "/accounts/{}/workers/scripts/{}/deployments",
+        account_id, script_name
+    ));
+    let action = format!("Activate version {} of {}", version_id, script_name);
+    let response = send_cloudflare_request_with_retry(
+        || {
+            client
+                .post(&url)
+                .bearer_auth(api_token)
+                .json(&json!({
+                    "strategy": "percentage",
+                    "versions": [{ "version_id": version_id, "percentage": 100 }],
+                    "annotations": { "workers/message": "Rollback via gsv deploy rollback" }
+                }))
+                .send()
+        },
+        &action,
+    )
+    .await?;
+    let _: Value = parse_cloudflare_response(response, &action).await?;
+    Ok(())
+}
+
+/// Returns the requested components whose worker script is not deployed.
+pub async fn missing_components(
+    account_id: &str,
+    api_token: &str,
+    components: &[String],
+    name_prefix: Option<&str>,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let client = reqwest::Client::new();
+    let scripts = list_worker_scripts(&client, account_id, api_token).await?;
+    let mut missing = Vec::new();
+    for component in components {
+        if !scripts.contains_key(&component_script_name(component, name_prefix, &scripts)?) {
+            missing.push(component.clone());
+        }
+    }
+    Ok(missing)
+}
+
+/// Prints worker (and shared infrastructure) status for `components` and
+/// returns the components whose worker is missing.
 pub async fn print_deploy_status(
     account_id: &str,
     api_token: &str,
     components: &[String],
-) -> Result<(), Box<dyn std::error::Error>> {
+    name_prefix: Option<&str>,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
     if components.is_empty() {
         return Err("No components requested for status".into());
     }
@@ -2760,24 +4261,13 @@ pub async fn print_deploy_status(
 
     let client = reqwest::Client::new();
     let scripts = list_worker_scripts(&client, account_id, api_token).await?;
-    let gateway_script_name = if scripts.contains_key(SCRIPT_GATEWAY) {
-        Some(SCRIPT_GATEWAY.to_string())
-    } else if scripts.contains_key("gateway") {
-        Some("gateway".to_string())
-    } else {
-        None
-    };
 
+    let mut missing = Vec::new();
     println!("\nWorkers:");
     for component in &component_order {
-        let script_name = if component == COMPONENT_GATEWAY {
-            gateway_script_name.as_deref().unwrap_or(SCRIPT_GATEWAY)
-        } else {
-            component_to_script_name(component)
-                .ok_or_else(|| format!("Unsupported component '{}'", component))?
-        };
+        let script_name = component_script_name(component, name_prefix, &scripts)?;
 
-        if let Some(migration_tag) = scripts.get(script_name) {
+        if let Some(migration_tag) = scripts.get(&script_name) {
             if let Some(tag) = migration_tag.as_deref() {
                 println!(
                     "  {:<18} {:<24} deployed (migration: {})",
@@ -2788,27 +4278,23 @@ pub async fn print_deploy_status(
             }
         } else {
             println!("  {:<18} {:<24} missing", component, script_name);
+            missing.push(component.clone());
         }
     }
 
     if component_order.iter().any(|c| c == COMPONENT_GATEWAY) {
         println!("\nShared infrastructure:");
-        let bucket_exists = r2_bucket_exists(
-            &client,
-            account_id,
-            api_token,
-            DEFAULT_STORAGE_BUCKET_NAME,
-            None,
-        )
-        .await?;
+        let storage_bucket_name = prefixed_name(name_prefix, DEFAULT_STORAGE_BUCKET_NAME);
+        let bucket_exists =
+            r2_bucket_exists(&client, account_id, api_token, &storage_bucket_name, None).await?;
         println!(
             "  r2 bucket {:<26} {}",
-            DEFAULT_STORAGE_BUCKET_NAME,
+            storage_bucket_name,
             if bucket_exists { "exists" } else { "missing" }
         );
     }
 
-    Ok(())
+    Ok(missing)
 }
 
 fn gateway_http_url_to_ws_url(gateway_url: &str) -> String {
@@ -2984,15 +4470,222 @@ pub async fn set_discord_bot_token_secret(
     account_id: &str,
     api_token: &str,
     bot_token: &str,
+    name_prefix: Option<&str>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let client = reqwest::Client::new();
     set_worker_secret(
         &client,
         account_id,
         api_token,
-        SCRIPT_CHANNEL_DISCORD,
+        &prefixed_name(name_prefix, SCRIPT_CHANNEL_DISCORD),
         "DISCORD_BOT_TOKEN",
         bot_token,
     )
     .await
 }
+
+/// Sets `secret_name` on a worker that has no bundled component; returns
+/// false when that worker is not deployed.
+async fn set_secret_if_deployed(
+    account_id: &str,
+    api_token: &str,
+    script_name: &str,
+    secret_name: &str,
+    value: &str,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let client = reqwest::Client::new();
+    let scripts = list_worker_scripts(&client, account_id, api_token).await?;
+    if !scripts.contains_key(script_name) {
+        return Ok(false);
+    }
+    set_worker_secret(
+        &client,
+        account_id,
+        api_token,
+        script_name,
+        secret_name,
+        value,
+    )
+    .await?;
+    Ok(true)
+}
+
+/// Sets `TELEGRAM_BOT_TOKEN` on the Telegram channel worker. Telegram has no
+/// bundled component yet, so returns false when that worker is not deployed.
+pub async fn set_telegram_bot_token_secret(
+    account_id: &str,
+    api_token: &str,
+    bot_token: &str,
+    name_prefix: Option<&str>,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    set_secret_if_deployed(
+        account_id,
+        api_token,
+        &prefixed_name(name_prefix, SCRIPT_CHANNEL_TELEGRAM),
+        "TELEGRAM_BOT_TOKEN",
+        bot_token,
+    )
+    .await
+}
+
+/// Sets `SLACK_BOT_TOKEN` on the Slack channel worker. Like Telegram, Slack
+/// has no bundled component; returns false when that worker is not deployed.
+pub async fn set_slack_bot_token_secret(
+    account_id: &str,
+    api_token: &str,
+    bot_token: &str,
+    name_prefix: Option<&str>,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    set_secret_if_deployed(
+        account_id,
+        api_token,
+        &prefixed_name(name_prefix, SCRIPT_CHANNEL_SLACK),
+        "SLACK_BOT_TOKEN",
+        bot_token,
+    )
+    .await
+}
+
+/// Slash commands understood by the gateway (see gateway/src/gateway/commands.ts).
+const DISCORD_SLASH_COMMANDS: &[(&str, &str, Option<&str>)] = &[
+    ("reset", "Reset the session", None),
+    (
+        "compact",
+        "Compact session to the last N messages",
+        Some("count"),
+    ),
+    ("stop", "Stop the current run", None),
+    ("status", "Show session status", None),
+    (
+        "model",
+        "Show or set the model (provider/model)",
+        Some("model"),
+    ),
+    ("think", "Set thinking level", Some("level")),
+    ("help", "Show available commands", None),
+];
+
+/// Registers the gateway slash commands for the Discord bot in a single guild.
+/// Guild commands are available immediately, unlike global commands.
+/// Returns the names of the registered commands.
+pub async fn register_discord_slash_commands(
+    bot_token: &str,
+    guild_id: &str,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let client = reqwest::Client::new();
+    let auth = format!("Bot {}", bot_token);
+
+    let response = client
+        .get(format!("{}/oauth2/applications/@me", DISCORD_API_BASE))
+        .header("Authorization", &auth)
+        .header("User-Agent", "gsv-cli")
+        .send()
+        .await?;
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    if !status.is_success() {
+        return Err(format!(
+            "Failed to look up Discord application ({}): {}",
+            status,
+            body.get("message").and_then(Value::as_str).unwrap_or("")
+        )
+        .into());
+    }
+    let application_id = body
+        .get("id")
+        .and_then(Value::as_str)
+        .ok_or("Discord application lookup returned no id")?
+        .to_string();
+
+    let commands: Vec<Value> = DISCORD_SLASH_COMMANDS
+        .iter()
+        .map(|(name, description, arg)| {
+            let mut command = json!({
+                "name": name,
+                "description": description,
+                "type": 1,
+            });
+            if let Some(arg) = arg {
+                command["options"] = json!([{
+                    "name": arg,
+                    "description": arg,
+                    "type": 3,
+                    "required": false,
+                }]);
+            }
+            command
+        })
+        .collect();
+
+    let response = client
+        .put(format!(
+            "{}/applications/{}/guilds/{}/commands",
+            DISCORD_API_BASE, application_id, guild_id
+        ))
+        .header("Authorization", &auth)
+        .header("User-Agent", "gsv-cli")
+        .json(&commands)
+        .send()
+        .await?;
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    if !status.is_success() {
+        return Err(format!(
+            "Failed to register Discord slash commands in guild {} ({}): {}",
+            guild_id,
+            status,
+            body.get("message").and_then(Value::as_str).unwrap_or("")
+        )
+        .into());
+    }
+
+    Ok(body
+        .as_array()
+        .map(|registered| {
+            registered
+                .iter()
+                .filter_map(|command| command.get("name").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn has_foreign_migration_tag_flags_unknown_tags() {
+        let inferred = WranglerConfig::default();
+        assert!(!has_foreign_migration_tag(&inferred, None));
+        assert!(!has_foreign_migration_tag(&inferred, Some("auto-v1")));
+        assert!(has_foreign_migration_tag(&inferred, Some("v1")));
+
+        let declared = WranglerConfig {
+            migrations: vec![
+                json!({ "tag": "v1", "new_sqlite_classes": ["Gateway"] }),
+                json!({ "tag": "v2", "new_sqlite_classes": ["Session"] }),
+            ],
+            ..Default::default()
+        };
+        assert!(!has_foreign_migration_tag(&declared, None));
+        assert!(!has_foreign_migration_tag(&declared, Some("v2")));
+        assert!(!has_foreign_migration_tag(&declared, Some("auto-v1")));
+        assert!(has_foreign_migration_tag(&declared, Some("v3")));
+    }
+
+    #[test]
+    fn dropped_bindings_event_is_structured() {
+        let dropped = vec!["gsv-channel-discord:GATEWAY -> gsv".to_string()];
+        assert_eq!(
+            dropped_bindings_event(&dropped),
+            json!({
+                "level": "warn",
+                "event": "deploy.binding.dropped",
+                "count": 1,
+                "bindings": ["gsv-channel-discord:GATEWAY -> gsv"],
+            })
+        );
+    }
+}
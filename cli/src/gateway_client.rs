@@ -1,23 +1,26 @@
-use crate::connection::Connection;
+use crate::connection::{Connection, LazyConnection};
 use serde::Serialize;
 use serde_json::{json, Map, Value};
 
 pub type GatewayResult<T> = Result<T, Box<dyn std::error::Error>>;
 
 pub struct GatewayClient {
-    conn: Connection,
+    conn: LazyConnection,
 }
 
 impl GatewayClient {
     pub fn new(conn: Connection) -> Self {
-        Self { conn }
+        Self {
+            conn: LazyConnection::ready(conn),
+        }
     }
 
+    /// The handshake happens on the first request, so commands that fail
+    /// validation or need no gateway data never connect.
     pub async fn connect(url: &str, token: Option<String>) -> GatewayResult<Self> {
-        let conn = Connection::connect_with_options(url, "client", None, None, |_| {}, None, token)
-            .await?;
-
-        Ok(Self::new(conn))
+        Ok(Self {
+            conn: Connection::lazy(url, "client", None, token),
+        })
     }
 
     async fn request<TParams: Serialize>(
@@ -41,8 +44,8 @@ impl GatewayClient {
         Ok(response.payload.unwrap_or_else(|| json!({})))
     }
 
-    pub fn connection(&self) -> &Connection {
-        &self.conn
+    pub async fn connection(&self) -> GatewayResult<&Connection> {
+        self.conn.get().await
     }
 
     pub async fn heartbeat_status(&self) -> GatewayResult<Value> {
@@ -62,6 +65,26 @@ impl GatewayClient {
         }
     }
 
+    pub async fn heartbeat_set_delivery(
+        &self,
+        agent_id: String,
+        channel: String,
+        account_id: String,
+        peer_kind: String,
+        peer_id: String,
+    ) -> GatewayResult<Value> {
+        self.request(
+            "heartbeat.set_delivery",
+            Some(json!({
+                "agentId": agent_id,
+                "channel": channel,
+                "accountId": account_id,
+                "peer": { "kind": peer_kind, "id": peer_id },
+            })),
+        )
+        .await
+    }
+
     pub async fn pair_list(&self) -> GatewayResult<Value> {
         self.request::<()>("pair.list", None).await
     }
@@ -88,6 +111,57 @@ impl GatewayClient {
         .await
     }
 
+    pub async fn pair_auto_approve(&self, channel: String, enable: bool) -> GatewayResult<Value> {
+        self.request(
+            "pair.auto_approve",
+            Some(json!({
+                "channel": channel,
+                "enable": enable,
+            })),
+        )
+        .await
+    }
+
+    pub async fn pair_auto_approve_add(
+        &self,
+        channel: String,
+        pattern: String,
+    ) -> GatewayResult<Value> {
+        self.request(
+            "pair.auto_approve.add",
+            Some(json!({
+                "channel": channel,
+                "pattern": pattern,
+            })),
+        )
+        .await
+    }
+
+    pub async fn pair_auto_approve_remove(
+        &self,
+        channel: String,
+        pattern: String,
+    ) -> GatewayResult<Value> {
+        self.request(
+            "pair.auto_approve.remove",
+            Some(json!({
+                "channel": channel,
+                "pattern": pattern,
+            })),
+        )
+        .await
+    }
+
+    pub async fn pair_auto_approve_list(&self, channel: String) -> GatewayResult<Value> {
+        self.request(
+            "pair.auto_approve.list",
+            Some(json!({
+                "channel": channel,
+            })),
+        )
+        .await
+    }
+
     pub async fn channels_list(&self) -> GatewayResult<Value> {
         self.request::<()>("channels.list", None).await
     }
@@ -118,6 +192,40 @@ impl GatewayClient {
         .await
     }
 
+    pub async fn channel_discord_guilds(&self, account_id: String) -> GatewayResult<Value> {
+        self.request(
+            "channel.discord.guilds",
+            Some(json!({
+                "accountId": account_id,
+            })),
+        )
+        .await
+    }
+
+    pub async fn channel_send(
+        &self,
+        channel: String,
+        account_id: String,
+        peer: Value,
+        text: String,
+        media: Option<Value>,
+        embed: Option<Value>,
+    ) -> GatewayResult<Value> {
+        let mut params = json!({
+            "channel": channel,
+            "accountId": account_id,
+            "peer": peer,
+            "text": text,
+        });
+        if let Some(media) = media {
+            params["media"] = json!([media]);
+        }
+        if let Some(embed) = embed {
+            params["embeds"] = json!([embed]);
+        }
+        self.request("channel.send", Some(params)).await
+    }
+
     pub async fn channel_logout(
         &self,
         channel: String,
@@ -155,13 +263,27 @@ impl GatewayClient {
         .await
     }
 
-    pub async fn tools_list(&self) -> GatewayResult<Value> {
-        self.request::<()>("tools.list", None).await
+    pub async fn tools_list(&self, node_id: Option<String>) -> GatewayResult<Value> {
+        match node_id {
+            Some(node_id) => {
+                self.request("tools.list", Some(json!({ "nodeId": node_id })))
+                    .await
+            }
+            None => self.request::<()>("tools.list", None).await,
+        }
     }
 
-    pub async fn tool_invoke(&self, tool: String, args: Value) -> GatewayResult<Value> {
-        self.request("tool.invoke", Some(json!({ "tool": tool, "args": args })))
-            .await
+    pub async fn tool_invoke(
+        &self,
+        tool: String,
+        args: Value,
+        session_key: Option<String>,
+    ) -> GatewayResult<Value> {
+        let mut params = json!({ "tool": tool, "args": args });
+        if let Some(session_key) = session_key {
+            params["sessionKey"] = json!(session_key);
+        }
+        self.request("tool.invoke", Some(params)).await
     }
 
     pub async fn config_get(&self, path: Option<String>) -> GatewayResult<Value> {
@@ -239,10 +361,31 @@ impl GatewayClient {
         self.request("session.patch", Some(patch)).await
     }
 
-    pub async fn session_compact(&self, session_key: String, keep: i64) -> GatewayResult<Value> {
+    pub async fn session_compact(
+        &self,
+        session_key: String,
+        keep: i64,
+        archive_trimmed: bool,
+        summarize: bool,
+        summary_max_tokens: Option<i64>,
+    ) -> GatewayResult<Value> {
+        let mut params = json!({ "sessionKey": session_key, "keepMessages": keep });
+        if archive_trimmed {
+            params["archiveTrimmed"] = json!(true);
+        }
+        if summarize {
+            params["summarize"] = json!(true);
+        }
+        if let Some(max_tokens) = summary_max_tokens {
+            params["summaryMaxTokens"] = json!(max_tokens);
+        }
+        self.request("session.compact", Some(params)).await
+    }
+
+    pub async fn session_compact_summarize(&self, session_key: String) -> GatewayResult<Value> {
         self.request(
             "session.compact",
-            Some(json!({ "sessionKey": session_key, "keepMessages": keep })),
+            Some(json!({ "sessionKey": session_key, "strategy": "summarize" })),
         )
         .await
     }
@@ -255,6 +398,18 @@ impl GatewayClient {
         .await
     }
 
+    pub async fn session_history_restore(
+        &self,
+        session_key: String,
+        session_id: String,
+    ) -> GatewayResult<Value> {
+        self.request(
+            "session.history.restore",
+            Some(json!({ "sessionKey": session_key, "sessionId": session_id })),
+        )
+        .await
+    }
+
     pub async fn session_preview(
         &self,
         session_key: String,
@@ -275,6 +430,43 @@ impl GatewayClient {
         }
     }
 
+    pub async fn session_export(&self, session_key: String) -> GatewayResult<Value> {
+        self.request("session.export", Some(json!({ "sessionKey": session_key })))
+            .await
+    }
+
+    pub async fn session_search(
+        &self,
+        query: String,
+        session_key: Option<String>,
+        limit: u32,
+    ) -> GatewayResult<Value> {
+        let mut params = json!({ "query": query, "limit": limit });
+        if let Some(session_key) = session_key {
+            params["sessionKey"] = json!(session_key);
+        }
+        self.request("session.search", Some(params)).await
+    }
+
+    pub async fn session_import(
+        &self,
+        session_key: String,
+        messages: Vec<Value>,
+        tokens: Value,
+        overwrite: bool,
+    ) -> GatewayResult<Value> {
+        self.request(
+            "session.import",
+            Some(json!({
+                "sessionKey": session_key,
+                "messages": messages,
+                "tokens": tokens,
+                "overwrite": overwrite,
+            })),
+        )
+        .await
+    }
+
     pub async fn chat_send(&self, session_key: String, message: String) -> GatewayResult<Value> {
         let run_id = uuid::Uuid::new_v4().to_string();
         self.request(
@@ -287,4 +479,24 @@ impl GatewayClient {
         )
         .await
     }
+
+    /// `chat.send` with a file attached as `{ name, content_type, data }`.
+    pub async fn chat_send_with_attachment(
+        &self,
+        session_key: String,
+        message: String,
+        attachment: Value,
+    ) -> GatewayResult<Value> {
+        let run_id = uuid::Uuid::new_v4().to_string();
+        self.request(
+            "chat.send",
+            Some(json!({
+                "sessionKey": session_key,
+                "message": message,
+                "runId": run_id,
+                "attachment": attachment,
+            })),
+        )
+        .await
+    }
 }
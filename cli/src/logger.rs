@@ -1,13 +1,25 @@
+use crate::config::CliConfig;
 use serde_json::json;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 const DEFAULT_NODE_LOG_MAX_BYTES: u64 = 10 * 1024 * 1024;
 const DEFAULT_NODE_LOG_MAX_FILES: usize = 5;
 
+/// Overrides the node log directory; set by the daemon service from `node install --log-dir`.
+pub const NODE_LOG_DIR_ENV: &str = "GSV_NODE_LOG_DIR";
+
+/// Resolves the node log file: `GSV_NODE_LOG_DIR`, then `node.log_dir` in
+/// config, then `~/.gsv/logs`.
 pub fn node_log_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    if let Some(dir) = std::env::var_os(NODE_LOG_DIR_ENV).filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir).join("node.log"));
+    }
+    if let Some(dir) = CliConfig::load().node.log_dir {
+        return Ok(dir.join("node.log"));
+    }
     let home = dirs::home_dir().ok_or("Could not determine home directory")?;
     Ok(home.join(".gsv").join("logs").join("node.log"))
 }
@@ -34,10 +46,28 @@ pub fn node_log_max_files() -> usize {
     parse_env_usize("GSV_NODE_LOG_MAX_FILES").unwrap_or(DEFAULT_NODE_LOG_MAX_FILES)
 }
 
-pub fn rotated_log_path(base: &PathBuf, index: usize) -> PathBuf {
+pub fn rotated_log_path(base: &Path, index: usize) -> PathBuf {
     PathBuf::from(format!("{}.{}", base.to_string_lossy(), index))
 }
 
+/// Format used when echoing log lines to stdout/stderr. The log file is always JSON.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConsoleLogFormat {
+    #[default]
+    Json,
+    Text,
+}
+
+impl ConsoleLogFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "text" => Some(Self::Text),
+            _ => None,
+        }
+    }
+}
+
 struct NodeLoggerInner {
     path: PathBuf,
     file: fs::File,
@@ -121,16 +151,22 @@ pub struct NodeLogger {
     inner: Arc<Mutex<NodeLoggerInner>>,
     node_id: String,
     workspace: String,
+    debug: bool,
+    quiet: bool,
+    console_format: ConsoleLogFormat,
 }
 
 impl NodeLogger {
-    pub fn new(node_id: &str, workspace: &PathBuf) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new(node_id: &str, workspace: &Path) -> Result<Self, Box<dyn std::error::Error>> {
         let path = node_log_path()?;
         let inner = NodeLoggerInner::open(&path, node_log_max_bytes(), node_log_max_files())?;
         Ok(Self {
             inner: Arc::new(Mutex::new(inner)),
             node_id: node_id.to_string(),
             workspace: workspace.display().to_string(),
+            debug: false,
+            quiet: false,
+            console_format: ConsoleLogFormat::default(),
         })
     }
 
@@ -146,9 +182,32 @@ impl NodeLogger {
             inner: Arc::new(Mutex::new(inner)),
             node_id: node_id.to_string(),
             workspace: workspace.to_string(),
+            debug: false,
+            quiet: false,
+            console_format: ConsoleLogFormat::default(),
         })
     }
 
+    /// Configure console echo: `debug` enables DEBUG events, `quiet` suppresses
+    /// non-error console output (the log file still receives every line).
+    pub fn with_console_options(
+        mut self,
+        debug: bool,
+        quiet: bool,
+        console_format: ConsoleLogFormat,
+    ) -> Self {
+        self.debug = debug;
+        self.quiet = quiet;
+        self.console_format = console_format;
+        self
+    }
+
+    pub fn debug(&self, event: &str, fields: serde_json::Value) {
+        if self.debug {
+            self.log("DEBUG", event, fields);
+        }
+    }
+
     pub fn info(&self, event: &str, fields: serde_json::Value) {
         self.log("INFO", event, fields);
     }
@@ -185,12 +244,17 @@ impl NodeLogger {
             }
         }
 
+        let console_line = match self.console_format {
+            ConsoleLogFormat::Json => None,
+            ConsoleLogFormat::Text => Some(format_text_line(&obj)),
+        };
         let line = serde_json::Value::Object(obj).to_string();
+        let console_line = console_line.as_deref().unwrap_or(&line);
 
         if level == "ERROR" {
-            eprintln!("{}", line);
-        } else {
-            println!("{}", line);
+            eprintln!("{}", console_line);
+        } else if !self.quiet {
+            println!("{}", console_line);
         }
 
         let mut guard = match self.inner.lock() {
@@ -206,3 +270,21 @@ impl NodeLogger {
         }
     }
 }
+
+fn format_text_line(obj: &serde_json::Map<String, serde_json::Value>) -> String {
+    let field = |key: &str| obj.get(key).and_then(|v| v.as_str()).unwrap_or("");
+    let mut line = format!("{} {:<5} {}", field("ts"), field("level"), field("event"));
+    for (key, value) in obj {
+        if matches!(
+            key.as_str(),
+            "ts" | "level" | "component" | "event" | "nodeId" | "workspace"
+        ) {
+            continue;
+        }
+        match value {
+            serde_json::Value::String(s) => line.push_str(&format!(" {}={}", key, s)),
+            other => line.push_str(&format!(" {}={}", key, other)),
+        }
+    }
+    line
+}
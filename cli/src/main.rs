@@ -1,5 +1,6 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand};
 use cliclack::{confirm, input, intro, log, multiselect, note, outro_cancel, password, select};
+use gsv::backoff::ReconnectBackoff;
 use gsv::config::{self, CliConfig};
 use gsv::connection::Connection;
 use gsv::deploy;
@@ -8,17 +9,21 @@ use gsv::protocol::{
     NodeProbeResultParams, NodeRuntimeInfo, ToolDefinition, ToolInvokePayload, ToolResultParams,
     TransferEndPayload, TransferReceivePayload, TransferSendPayload, TransferStartPayload,
 };
-use gsv::tools::{all_tools_with_workspace, subscribe_exec_events, Tool};
+use gsv::tools::{all_tools_with_workspace, subscribe_exec_events, tool_filter, Tool};
 use gsv::transfer::TransferCoordinator;
 use serde_json::json;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::ffi::{OsStr, OsString};
 use std::fs;
 use std::future::Future;
-use std::io::{self, BufRead, IsTerminal};
+use std::io::{self, BufRead, IsTerminal, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 mod commands;
 
 #[derive(Parser)]
@@ -36,17 +41,44 @@ struct Cli {
     #[arg(short, long, env = "GSV_TOKEN")]
     token: Option<String>,
 
+    /// Output format for read-only gateway queries (list/get/status commands)
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+
+    /// Config profile (uses ~/.config/gsv/profiles/<NAME>/config.toml)
+    #[arg(long, global = true, env = "GSV_PROFILE")]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum OutputFormat {
+    /// Human-readable tables and prose
+    Text,
+    /// The gateway payload as JSON, with `"ok": true` added
+    Json,
+}
+
 #[derive(Subcommand)]
 enum Commands {
-    /// Initialize CLI config file (~/.config/gsv/config.toml)
+    /// Initialize CLI config file (~/.config/gsv/config.toml, or the --profile file)
     Init {
         /// Overwrite existing config
         #[arg(long)]
         force: bool,
+
+        /// Also prompt for node ID and workspace and save them as node defaults
+        #[arg(long)]
+        with_sample_node: bool,
+    },
+
+    /// Print a shell completion script to stdout
+    #[command(hide = true)]
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
     },
 
     /// Send a message to the agent (interactive or one-shot)
@@ -57,6 +89,14 @@ enum Commands {
         /// Session key (default from config or "agent:main:cli:dm:main")
         #[arg(short, long)]
         session: Option<String>,
+
+        /// Attach a file (up to 1 MB) to the one-shot message
+        #[arg(long, requires = "message")]
+        file: Option<PathBuf>,
+
+        /// Seconds to wait for each response before giving up with an error
+        #[arg(long, env = "GSV_CLIENT_TIMEOUT", default_value = "120", value_parser = clap::value_parser!(u64).range(1..))]
+        timeout: u64,
     },
 
     /// Run as a tool-providing node
@@ -73,6 +113,30 @@ enum Commands {
         #[arg(long)]
         workspace: Option<PathBuf>,
 
+        /// Emit DEBUG-level log events
+        #[arg(long)]
+        debug: bool,
+
+        /// Suppress non-error log output on the console (log file is unaffected)
+        #[arg(long)]
+        quiet: bool,
+
+        /// Console log format: json or text (log file is always JSON)
+        #[arg(long, default_value = "json")]
+        log_format: String,
+
+        /// Serve `GET /health` on this port (503 while disconnected from the gateway)
+        #[arg(long)]
+        health_port: Option<u16>,
+
+        /// Address to serve --health-port on (use 0.0.0.0 for container probes)
+        #[arg(long, default_value = "127.0.0.1", requires = "health_port")]
+        health_host: std::net::IpAddr,
+
+        /// Run under the Windows service control manager (set by `node install --windows-service`)
+        #[arg(long, hide = true, requires = "foreground")]
+        as_windows_service: bool,
+
         /// Optional daemon management action (install/start/stop/status/logs)
         #[command(subcommand)]
         action: Option<NodeAction>,
@@ -142,7 +206,11 @@ enum Commands {
 #[derive(Subcommand)]
 enum HeartbeatAction {
     /// Show heartbeat status for all agents
-    Status,
+    Status {
+        /// Show full heartbeat state for a single agent
+        #[arg(long)]
+        agent_id: Option<String>,
+    },
 
     /// Start the heartbeat scheduler
     Start,
@@ -153,6 +221,27 @@ enum HeartbeatAction {
         #[arg(default_value = "main")]
         agent_id: String,
     },
+
+    /// Pin heartbeat delivery to a specific channel peer instead of the last active one
+    SetDelivery {
+        /// Agent ID
+        agent_id: String,
+
+        /// Channel type
+        #[arg(value_parser = ["whatsapp", "discord", "telegram", "slack"])]
+        channel: String,
+
+        /// Peer to deliver to (e.g., "+1234567890" or a Discord channel ID)
+        peer_id: String,
+
+        /// Channel account ID
+        #[arg(long, default_value = "default")]
+        account_id: String,
+
+        /// Peer kind
+        #[arg(long, default_value = "dm", value_parser = ["dm", "group", "channel", "thread"])]
+        peer_kind: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -177,6 +266,34 @@ enum PairAction {
         /// Sender ID (e.g., "+1234567890")
         sender_id: String,
     },
+
+    /// Automatically approve pairing requests on a channel
+    ///
+    /// Without a pattern, approves every new request. With a pattern, adds or
+    /// removes a rule matched against sender IDs.
+    AutoApprove {
+        /// Channel name (e.g., "discord")
+        channel: String,
+
+        /// Sender ID pattern: a glob ("+1555*") or a regex wrapped in slashes ("/^\+1555\d+$/")
+        pattern: Option<String>,
+
+        /// Add the pattern as a rule (the default when a pattern is given)
+        #[arg(long, requires = "pattern", conflicts_with_all = ["remove", "list"])]
+        add: bool,
+
+        /// Remove the pattern from the channel's rules
+        #[arg(long, requires = "pattern", conflicts_with = "list")]
+        remove: bool,
+
+        /// List the channel's auto-approve rules
+        #[arg(long, conflicts_with = "pattern")]
+        list: bool,
+
+        /// Enable or disable auto-approval of every request (without a pattern)
+        #[arg(long, default_value_t = true, action = clap::ArgAction::Set, conflicts_with_all = ["pattern", "list"])]
+        enable: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -193,6 +310,18 @@ enum ChannelAction {
         action: DiscordAction,
     },
 
+    /// Telegram channel management
+    Telegram {
+        #[command(subcommand)]
+        action: TelegramAction,
+    },
+
+    /// Slack channel management
+    Slack {
+        #[command(subcommand)]
+        action: SlackAction,
+    },
+
     /// List all channel accounts
     List,
 }
@@ -204,6 +333,10 @@ enum WhatsAppAction {
         /// Account ID (arbitrary name for this WhatsApp account)
         #[arg(default_value = "default")]
         account_id: String,
+
+        /// Save the QR code as a PNG image instead of printing it (for headless setups)
+        #[arg(long, value_name = "PATH")]
+        save: Option<PathBuf>,
     },
 
     /// Check WhatsApp account status
@@ -226,6 +359,73 @@ enum WhatsAppAction {
         #[arg(default_value = "default")]
         account_id: String,
     },
+
+    /// Send a message from an authenticated account
+    Send {
+        /// Account ID
+        #[arg(long, default_value = "default")]
+        account_id: String,
+
+        /// Recipient phone number with country code (e.g., "+1234567890")
+        #[arg(long)]
+        to: String,
+
+        /// Message text (used as the caption when sending media)
+        #[arg(long, default_value = "")]
+        message: String,
+
+        /// URL of an image or document to attach
+        #[arg(long)]
+        media_url: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TelegramAction {
+    /// Start Telegram bot connection
+    Start {
+        /// Account ID (arbitrary name for this Telegram bot)
+        #[arg(default_value = "default")]
+        account_id: String,
+    },
+
+    /// Check Telegram bot status
+    Status {
+        /// Account ID
+        #[arg(default_value = "default")]
+        account_id: String,
+    },
+
+    /// Stop Telegram bot connection
+    Stop {
+        /// Account ID
+        #[arg(default_value = "default")]
+        account_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum SlackAction {
+    /// Start Slack bot connection
+    Start {
+        /// Account ID (arbitrary name for this Slack bot)
+        #[arg(default_value = "default")]
+        account_id: String,
+    },
+
+    /// Check Slack bot status
+    Status {
+        /// Account ID
+        #[arg(default_value = "default")]
+        account_id: String,
+    },
+
+    /// Stop Slack bot connection
+    Stop {
+        /// Account ID
+        #[arg(default_value = "default")]
+        account_id: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -242,6 +442,10 @@ enum DiscordAction {
         /// Account ID
         #[arg(default_value = "default")]
         account_id: String,
+
+        /// Also list the guilds the bot is a member of
+        #[arg(long)]
+        guilds: bool,
     },
 
     /// Stop Discord bot connection
@@ -250,6 +454,25 @@ enum DiscordAction {
         #[arg(default_value = "default")]
         account_id: String,
     },
+
+    /// Send a message to a Discord channel as the bot
+    Send {
+        /// Account ID
+        #[arg(long, default_value = "default")]
+        account_id: String,
+
+        /// Numeric Discord channel ID
+        #[arg(long)]
+        channel_id: String,
+
+        /// Message text
+        #[arg(long, default_value = "")]
+        message: String,
+
+        /// Discord embed object as JSON (e.g., '{"title": "Deployed", "color": 5763719}')
+        #[arg(long)]
+        embed: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -271,6 +494,14 @@ enum MountAction {
         /// R2 bucket name (default: gsv-storage)
         #[arg(long, default_value = "gsv-storage")]
         bucket: String,
+
+        /// Verify credentials with `rclone ls` without saving any config files
+        #[arg(long)]
+        test: bool,
+
+        /// After saving the config, check bucket access with the saved config
+        #[arg(long, conflicts_with = "test")]
+        verify: bool,
     },
 
     /// Start the mount (requires setup first)
@@ -298,6 +529,30 @@ enum NodeAction {
         /// Workspace directory (saved to local config during install)
         #[arg(long)]
         workspace: Option<PathBuf>,
+
+        /// Install and enable the service without starting it
+        #[arg(long)]
+        no_start: bool,
+
+        /// Write node logs to this directory instead of ~/.gsv/logs (saved to local config)
+        #[arg(long)]
+        log_dir: Option<PathBuf>,
+
+        /// Have the daemon serve `GET /health` on this port
+        #[arg(long)]
+        health_port: Option<u16>,
+
+        /// Address the daemon serves --health-port on (default 127.0.0.1)
+        #[arg(long, requires = "health_port")]
+        health_host: Option<std::net::IpAddr>,
+
+        /// Set an environment variable for the daemon (repeatable, e.g. --env GSV_URL=wss://...)
+        #[arg(long = "env", value_name = "KEY=VALUE")]
+        env: Vec<String>,
+
+        /// Windows: install a service that starts at boot instead of a logon task (needs an elevated terminal)
+        #[arg(long)]
+        windows_service: bool,
     },
 
     /// Uninstall and stop node daemon service
@@ -310,7 +565,19 @@ enum NodeAction {
     Stop,
 
     /// Show node daemon service status
-    Status,
+    Status {
+        /// Print machine-readable JSON; exit code 0 = running, 1 = stopped, 2 = not installed
+        #[arg(long, conflicts_with = "watch")]
+        json: bool,
+
+        /// Keep refreshing the status until interrupted with Ctrl+C
+        #[arg(long)]
+        watch: bool,
+
+        /// Seconds between refreshes in watch mode
+        #[arg(long, default_value_t = 5, requires = "watch")]
+        interval: u64,
+    },
 
     /// Show node daemon service logs
     Logs {
@@ -321,22 +588,90 @@ enum NodeAction {
         /// Follow logs
         #[arg(long)]
         follow: bool,
+
+        /// Read the rotated log file with this index (node.log.<n>); 0 is the active file
+        #[arg(long, default_value = "0", conflicts_with = "follow")]
+        from_rotated: usize,
+
+        /// Read the log file directly instead of shelling out to `tail`
+        #[arg(long, conflicts_with = "from_rotated")]
+        no_system_tail: bool,
+
+        /// Show only JSON log lines whose `tool` field equals this name
+        #[arg(long, conflicts_with_all = ["follow", "from_rotated"])]
+        tool: Option<String>,
+
+        /// Parse JSON log lines and show ts/level/event/error as a table (raw JSON with --output json)
+        #[arg(long, conflicts_with_all = ["from_rotated", "tool", "no_system_tail"])]
+        json: bool,
+    },
+
+    /// Inspect or change the node workspace directory
+    Workspace {
+        #[command(subcommand)]
+        action: WorkspaceAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum WorkspaceAction {
+    /// Print a tree of the workspace with file/directory counts and total size
+    Show {
+        /// Directory levels to print (counts and size always cover the whole tree)
+        #[arg(long, default_value_t = 3)]
+        depth: usize,
+    },
+
+    /// Set node.workspace in local config
+    Set {
+        /// Existing directory to use as the workspace
+        path: PathBuf,
     },
 }
 
 #[derive(Subcommand)]
 enum ToolsAction {
     /// List available tools from connected nodes
-    List,
+    List {
+        /// Group tools under the node that provides them
+        #[arg(long)]
+        connected_nodes: bool,
+        /// Only list tools provided by this node
+        #[arg(long, value_name = "ID")]
+        node: Option<String>,
+        /// Also print each tool's input JSON schema
+        #[arg(short, long)]
+        verbose: bool,
+    },
 
     /// Call a tool directly
     Call {
         /// Tool name (e.g., "macbook:Bash")
         tool: String,
 
-        /// Arguments as JSON object (e.g., '{"command": "ls -la"}')
+        /// Arguments as JSON object (e.g., '{"command": "ls -la"}'); "-" reads stdin (also used when omitted and piped)
         #[arg(default_value = "{}")]
         args: String,
+
+        /// Read the JSON arguments object from this file
+        #[arg(long, value_name = "PATH", conflicts_with = "args")]
+        args_file: Option<PathBuf>,
+
+        /// Give up waiting for the tool result after this many seconds
+        #[arg(long)]
+        timeout_seconds: Option<u64>,
+
+        /// Record the call and its result in this session's history
+        #[arg(long)]
+        session: Option<String>,
+
+        /// Call the tool again every N seconds and show what changed between results
+        #[arg(long, value_name = "SECONDS", conflicts_with = "session")]
+        watch_interval: Option<u64>,
+
+        /// Pretty-print string results that contain JSON
+        #[arg(long, visible_alias = "pretty")]
+        pretty_result: bool,
     },
 }
 
@@ -371,20 +706,40 @@ enum ConfigAction {
     Get {
         /// Config path (e.g., "apiKeys.anthropic", "model.provider")
         path: Option<String>,
+        /// If the path does not exist, suggest the closest known path
+        #[arg(long)]
+        suggest: bool,
+        /// List the dot paths of every set config value, without values
+        #[arg(long, conflicts_with_all = ["path", "suggest"])]
+        keys_only: bool,
     },
     /// Set configuration value
     Set {
         /// Config path (e.g., "apiKeys.anthropic", "model.provider")
-        path: String,
+        #[arg(required_unless_present = "from_file", conflicts_with = "from_file")]
+        path: Option<String>,
         /// Value to set
-        value: String,
+        #[arg(required_unless_present = "from_file")]
+        value: Option<String>,
+        /// Set every leaf of a JSON object file as a dot path ("-" reads stdin)
+        #[arg(long, value_name = "PATH")]
+        from_file: Option<PathBuf>,
+        /// Skip client-side validation of known config paths
+        #[arg(long)]
+        force: bool,
     },
+    /// Compare local config values with the live gateway config
+    Diff,
 }
 
 #[derive(Subcommand)]
 enum LocalConfigAction {
     /// Show current local config
-    Show,
+    Show {
+        /// Replace tokens, secrets, and access keys with "****"
+        #[arg(long)]
+        redact: bool,
+    },
     /// Get a config value
     Get {
         /// Config key (e.g., "gateway.url", "gateway.token", "workspace.path")
@@ -392,10 +747,16 @@ enum LocalConfigAction {
     },
     /// Set a config value
     Set {
-        /// Config key (e.g., "gateway.url", "gateway.token", "workspace.path")
-        key: String,
+        /// Config key (e.g., "gateway.url", "gateway.token", "workspace.path").
+        /// "gateway.urls" takes a comma-separated list of ws:// or wss:// URLs
+        #[arg(required_unless_present = "from_env", conflicts_with = "from_env")]
+        key: Option<String>,
         /// Value to set
-        value: String,
+        #[arg(required_unless_present = "from_env")]
+        value: Option<String>,
+        /// Import GSV_URL, GSV_TOKEN, CF_API_TOKEN and CF_ACCOUNT_ID from the environment
+        #[arg(long)]
+        from_env: bool,
     },
     /// Show config file path
     Path,
@@ -404,59 +765,7 @@ enum LocalConfigAction {
 #[derive(Subcommand)]
 enum DeployAction {
     /// Deploy prebuilt Cloudflare bundles (fetch/install + apply)
-    Up {
-        /// Release tag (e.g., v0.2.0) or "latest"
-        #[arg(long, default_value = "latest")]
-        version: String,
-
-        /// Component to include (repeat for multiple)
-        #[arg(short = 'c', long = "component")]
-        component: Vec<String>,
-
-        /// Include all components
-        #[arg(long)]
-        all: bool,
-
-        /// Overwrite existing extracted bundle directories
-        #[arg(long)]
-        force_fetch: bool,
-
-        /// Use local Cloudflare bundle directory instead of downloading from release assets
-        #[arg(long)]
-        bundle_dir: Option<PathBuf>,
-
-        /// Run interactive setup prompts (first-time guided flow)
-        #[arg(long)]
-        wizard: bool,
-
-        /// Cloudflare API token (falls back to config `cloudflare.api_token`)
-        #[arg(long, env = "CF_API_TOKEN")]
-        api_token: Option<String>,
-
-        /// Cloudflare account ID override (falls back to config `cloudflare.account_id`)
-        #[arg(long, env = "CF_ACCOUNT_ID")]
-        account_id: Option<String>,
-
-        /// Gateway auth token to set in gateway config (`auth.token`)
-        #[arg(long, env = "GSV_GATEWAY_AUTH_TOKEN")]
-        gateway_auth_token: Option<String>,
-
-        /// LLM provider to configure on gateway (`anthropic`, `openai`, `google`, `openrouter`, or custom)
-        #[arg(long)]
-        llm_provider: Option<String>,
-
-        /// LLM model ID to configure on gateway
-        #[arg(long)]
-        llm_model: Option<String>,
-
-        /// LLM API key to configure on gateway (`apiKeys.<provider>`)
-        #[arg(long)]
-        llm_api_key: Option<String>,
-
-        /// Discord bot token to upload as worker secret (`DISCORD_BOT_TOKEN`)
-        #[arg(long, env = "DISCORD_BOT_TOKEN")]
-        discord_bot_token: Option<String>,
-    },
+    Up(Box<DeployUpArgs>),
 
     /// Tear down deployed Cloudflare workers for selected components
     Down {
@@ -476,6 +785,14 @@ enum DeployAction {
         #[arg(long)]
         purge_bucket: bool,
 
+        /// Explicitly keep the shared R2 storage bucket and its data (default)
+        #[arg(long, conflicts_with_all = ["delete_bucket", "purge_bucket"])]
+        keep_r2: bool,
+
+        /// Name prefix used when the components were deployed with `deploy up --name-prefix`
+        #[arg(long)]
+        name_prefix: Option<String>,
+
         /// Run interactive teardown wizard
         #[arg(long)]
         wizard: bool,
@@ -506,6 +823,53 @@ enum DeployAction {
         /// Cloudflare account ID override (falls back to config `cloudflare.account_id`)
         #[arg(long, env = "CF_ACCOUNT_ID")]
         account_id: Option<String>,
+
+        /// Exit with code 1 if this component is not deployed (repeat for multiple)
+        #[arg(long)]
+        require: Vec<String>,
+
+        /// Poll every 5 seconds until this component is deployed (repeat for multiple)
+        #[arg(long)]
+        wait_for: Vec<String>,
+
+        /// Seconds to wait for --wait-for components before exiting with code 1
+        #[arg(long, default_value = "300", requires = "wait_for")]
+        timeout: u64,
+
+        /// Redraw the status every N seconds until q or Ctrl+C is pressed
+        #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..), conflicts_with_all = ["require", "wait_for"])]
+        watch: Option<u64>,
+
+        /// Name prefix used when the components were deployed with `deploy up --name-prefix`
+        #[arg(long)]
+        name_prefix: Option<String>,
+    },
+
+    /// Roll a component's worker back to a previously uploaded version
+    Rollback {
+        /// Component to roll back
+        #[arg(short = 'c', long = "component", default_value = "gateway")]
+        component: String,
+
+        /// Version ID (or unique prefix) to activate; defaults to the version before the active one
+        #[arg(long)]
+        version_id: Option<String>,
+
+        /// Number of recent versions to list
+        #[arg(long, default_value = "10")]
+        limit: usize,
+
+        /// Name prefix used when the component was deployed with `deploy up --name-prefix`
+        #[arg(long)]
+        name_prefix: Option<String>,
+
+        /// Cloudflare API token (falls back to config `cloudflare.api_token`)
+        #[arg(long, env = "CF_API_TOKEN")]
+        api_token: Option<String>,
+
+        /// Cloudflare account ID override (falls back to config `cloudflare.account_id`)
+        #[arg(long, env = "CF_ACCOUNT_ID")]
+        account_id: Option<String>,
     },
 
     /// Manage prebuilt Cloudflare bundles from GitHub releases
@@ -523,44 +887,193 @@ enum DeployAction {
     },
 }
 
-#[derive(Subcommand)]
-enum DeployBundleAction {
-    /// Download and verify prebuilt Cloudflare bundles
-    Fetch {
-        /// Release tag (e.g., v0.2.0) or "latest"
-        #[arg(long, default_value = "latest")]
-        version: String,
+#[derive(Args)]
+struct DeployUpArgs {
+    /// Release tag (e.g., v0.2.0) or "latest"
+    #[arg(long, default_value = "latest")]
+    version: String,
 
-        /// Component to fetch (repeat for multiple)
-        #[arg(short = 'c', long = "component")]
-        component: Vec<String>,
+    /// Component to include (repeat for multiple)
+    #[arg(short = 'c', long = "component")]
+    component: Vec<String>,
 
-        /// Fetch all components
-        #[arg(long)]
-        all: bool,
+    /// Include all components
+    #[arg(long)]
+    all: bool,
 
-        /// Overwrite existing extracted bundle directories
-        #[arg(long)]
-        force: bool,
+    /// Overwrite existing extracted bundle directories
+    #[arg(long)]
+    force_fetch: bool,
 
-        /// Use local Cloudflare bundle directory instead of downloading from release assets
-        #[arg(long)]
-        from_dir: Option<PathBuf>,
-    },
+    /// Use local Cloudflare bundle directory instead of downloading from release assets
+    #[arg(long)]
+    bundle_dir: Option<PathBuf>,
 
-    /// Show bundle manifest details from local extracted bundles
-    Inspect {
-        /// Release tag (e.g., v0.2.0) or "latest"
-        #[arg(long, default_value = "latest")]
-        version: String,
+    /// Run interactive setup prompts (first-time guided flow)
+    #[arg(long)]
+    wizard: bool,
 
-        /// Component to inspect
-        #[arg(short = 'c', long = "component")]
-        component: String,
-    },
+    /// Cloudflare API token (falls back to config `cloudflare.api_token`)
+    #[arg(long, env = "CF_API_TOKEN")]
+    api_token: Option<String>,
 
-    /// List valid component names
-    ListComponents,
+    /// Cloudflare account ID override (falls back to config `cloudflare.account_id`)
+    #[arg(long, env = "CF_ACCOUNT_ID")]
+    account_id: Option<String>,
+
+    /// Gateway auth token to set in gateway config (`auth.token`)
+    #[arg(long, env = "GSV_GATEWAY_AUTH_TOKEN")]
+    gateway_auth_token: Option<String>,
+
+    /// LLM provider to configure on gateway (`anthropic`, `openai`, `google`, `openrouter`, or custom)
+    #[arg(long)]
+    llm_provider: Option<String>,
+
+    /// LLM model ID to configure on gateway
+    #[arg(long)]
+    llm_model: Option<String>,
+
+    /// LLM API key to configure on gateway (`apiKeys.<provider>`)
+    #[arg(long)]
+    llm_api_key: Option<String>,
+
+    /// Discord bot token to upload as worker secret (`DISCORD_BOT_TOKEN`)
+    #[arg(long, env = "DISCORD_BOT_TOKEN")]
+    discord_bot_token: Option<String>,
+
+    /// Register Discord slash commands in this guild after configuring the bot token
+    #[arg(long)]
+    discord_channel_guild_id: Option<String>,
+
+    /// Telegram bot token to upload as worker secret (`TELEGRAM_BOT_TOKEN`)
+    #[arg(long, env = "TELEGRAM_BOT_TOKEN")]
+    telegram_bot_token: Option<String>,
+
+    /// Slack bot token to upload as worker secret (`SLACK_BOT_TOKEN`)
+    #[arg(long, env = "SLACK_BOT_TOKEN")]
+    slack_bot_token: Option<String>,
+
+    /// Skip R2 bucket creation (buckets must already exist)
+    #[arg(long)]
+    skip_r2: bool,
+
+    /// Overlay a wrangler config file onto a component's bundle config (<component>:<path>, repeatable)
+    #[arg(long = "wrangler-override", value_name = "COMPONENT:PATH")]
+    wrangler_override: Vec<String>,
+
+    /// Prefix worker script and R2 bucket names (e.g. "staging" deploys staging-gsv)
+    #[arg(long)]
+    name_prefix: Option<String>,
+
+    /// Don't upload worker source maps
+    #[arg(long)]
+    skip_source_map: bool,
+
+    /// Skip the check for workers last deployed by another tool (unknown migration tag)
+    #[arg(long)]
+    force_redeploy: bool,
+
+    /// Cloudflare zone ID to add the --gateway-route route in
+    #[arg(long, requires = "gateway_route")]
+    cloudflare_zone: Option<String>,
+
+    /// Route pattern to send to the gateway worker (e.g. "gsv.example.com/*")
+    #[arg(long, requires = "cloudflare_zone")]
+    gateway_route: Option<String>,
+
+    /// Abort if applying the deploy takes longer than this many seconds
+    #[arg(long, default_value = "300", value_parser = clap::value_parser!(u64).range(1..))]
+    apply_timeout: u64,
+
+    /// After deploying, poll workers until they respond without a 5xx error
+    #[arg(long)]
+    wait: bool,
+
+    /// Seconds to wait for workers to become healthy with --wait
+    #[arg(long, default_value = "120", requires = "wait")]
+    wait_timeout: u64,
+
+    /// Suppress progress output (warnings and errors still go to stderr)
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Write the deployed version tag to this file after a successful deploy ("-" for stdout)
+    #[arg(long)]
+    tag_output: Option<String>,
+
+    /// Print `<COMPONENT>_URL=<url>` lines for the deployed workers (shell-sourceable)
+    #[arg(long)]
+    output_urls: bool,
+
+    /// Write a JSON summary of the deploy to this file, also when it fails
+    #[arg(long, value_name = "PATH")]
+    report: Option<PathBuf>,
+
+    /// Show which workers, R2 buckets and routes would change, without changing anything
+    #[arg(long, conflicts_with_all = ["wizard", "wait", "report", "tag_output", "output_urls"])]
+    dry_run: bool,
+
+    /// Only apply outstanding Durable Object migrations to the deployed gateway (with --component gateway)
+    #[arg(long, conflicts_with_all = ["all", "dry_run", "wizard", "wait", "report", "tag_output", "output_urls", "llm_provider", "cloudflare_zone"])]
+    migrate: bool,
+}
+
+#[derive(Subcommand)]
+enum DeployBundleAction {
+    /// Download and verify prebuilt Cloudflare bundles
+    Fetch {
+        /// Release tag (e.g., v0.2.0) or "latest"
+        #[arg(long, default_value = "latest")]
+        version: String,
+
+        /// Component to fetch (repeat for multiple)
+        #[arg(short = 'c', long = "component")]
+        component: Vec<String>,
+
+        /// Fetch all components
+        #[arg(long)]
+        all: bool,
+
+        /// Overwrite existing extracted bundle directories
+        #[arg(long)]
+        force: bool,
+
+        /// Use local Cloudflare bundle directory instead of downloading from release assets
+        #[arg(long)]
+        from_dir: Option<PathBuf>,
+
+        /// Download component bundles concurrently
+        #[arg(long, conflicts_with = "from_dir")]
+        parallel: bool,
+    },
+
+    /// Show bundle manifest details from local extracted bundles
+    Inspect {
+        /// Release tag (e.g., v0.2.0) or "latest"
+        #[arg(long, default_value = "latest")]
+        version: String,
+
+        /// Component to inspect
+        #[arg(short = 'c', long = "component")]
+        component: String,
+    },
+
+    /// List bundle versions cached locally
+    List,
+
+    /// Remove old cached bundle versions, keeping the most recent ones
+    Prune {
+        /// Number of most recently cached versions to keep
+        #[arg(long, default_value_t = 3)]
+        keep: usize,
+
+        /// Show what would be removed without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// List valid component names
+    ListComponents,
 }
 
 #[derive(Subcommand)]
@@ -584,6 +1097,16 @@ enum SessionAction {
         /// Maximum number of sessions to show
         #[arg(short, long, default_value = "50")]
         limit: i64,
+        /// Sort order (ascending unless --sort-desc); without it, the gateway's
+        /// most-recently-active-first order is kept
+        #[arg(long, value_parser = ["last-active", "created", "message-count", "token-usage"])]
+        sort_by: Option<String>,
+        /// Sort in descending order
+        #[arg(long, requires = "sort_by")]
+        sort_desc: bool,
+        /// Don't fetch per-session stats for message-count/token-usage sorting (keeps gateway order)
+        #[arg(long)]
+        no_fetch: bool,
     },
     /// Reset a session (clear message history, archive to R2)
     Reset {
@@ -610,7 +1133,11 @@ enum SessionAction {
         /// Path to set (e.g., "model.provider", "thinkingLevel", "resetPolicy.mode")
         path: String,
         /// Value to set
-        value: String,
+        #[arg(required_unless_present = "unset")]
+        value: Option<String>,
+        /// Reset the path to its default (sends null instead of a value)
+        #[arg(long, conflicts_with = "value")]
+        unset: bool,
     },
     /// Compact session (trim to last N messages)
     Compact {
@@ -620,12 +1147,30 @@ enum SessionAction {
         /// Number of messages to keep (default: 20)
         #[arg(short, long, default_value = "20")]
         keep: i64,
+        /// Compaction strategy: last-n, token-budget, or summarize
+        #[arg(long, default_value = "last-n")]
+        strategy: String,
+        /// Token budget to trim under (token-budget strategy)
+        #[arg(long)]
+        max_tokens: Option<i64>,
+        /// Save trimmed messages as JSON in R2 and abort the compaction if that fails
+        #[arg(long)]
+        archive_trimmed: bool,
+        /// Keep the last N messages and summarize the trimmed ones into a system message
+        #[arg(long)]
+        summarize: bool,
+        /// Cap the length of the --summarize summary
+        #[arg(long, value_name = "N", requires = "summarize")]
+        summary_max_tokens: Option<i64>,
     },
     /// Show session history (previous session IDs)
     History {
         /// Session key (default: "agent:main:cli:dm:main")
         #[arg(default_value = "agent:main:cli:dm:main")]
         session_key: String,
+        /// Restore a previous session (full ID or unique prefix) as the current one
+        #[arg(long, value_name = "SESSION_ID")]
+        restore: Option<String>,
     },
     /// Preview session messages
     Preview {
@@ -635,6 +1180,49 @@ enum SessionAction {
         /// Number of messages to show (default: all)
         #[arg(short, long)]
         limit: Option<i64>,
+        /// Output format: text or markdown
+        #[arg(long, default_value = "text")]
+        format: String,
+        /// Write the preview to this file instead of stdout
+        #[arg(long)]
+        output_file: Option<PathBuf>,
+        /// Print only tool calls, one JSON object per line ({tool, args, result_summary, timestamp})
+        #[arg(long, conflicts_with = "format")]
+        export_tool_calls: bool,
+    },
+    /// Export all session messages as JSONL, Markdown or CSV
+    Export {
+        /// Session key (default: "agent:main:cli:dm:main")
+        #[arg(default_value = "agent:main:cli:dm:main")]
+        session_key: String,
+        /// Output format: jsonl, markdown or csv
+        #[arg(long, default_value = "jsonl")]
+        format: String,
+        /// Write the export to this file instead of stdout
+        #[arg(long)]
+        output_file: Option<PathBuf>,
+    },
+    /// Search message text across sessions (case-insensitive)
+    Search {
+        /// Text to search for
+        query: String,
+        /// Only search this session
+        #[arg(long)]
+        session_key: Option<String>,
+        /// Maximum number of matches to return
+        #[arg(short, long, default_value_t = 20)]
+        limit: u32,
+    },
+    /// Import messages from a JSONL file written by `session export`
+    Import {
+        /// JSONL file with one message object per line
+        file: PathBuf,
+        /// Session key to import into (default: "agent:main:cli:dm:main")
+        #[arg(default_value = "agent:main:cli:dm:main")]
+        session_key: String,
+        /// Replace the session's existing messages (they are archived first)
+        #[arg(long)]
+        overwrite: bool,
     },
 }
 
@@ -658,6 +1246,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 async fn async_main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
+    // Completions need neither config nor network.
+    if let Commands::Completions { shell } = cli.command {
+        clap_complete::generate(shell, &mut Cli::command(), "gsv", &mut io::stdout());
+        return Ok(());
+    }
+
+    if let Some(profile) = cli.profile.as_deref() {
+        config::set_active_profile(profile)?;
+    }
+
     // Load config from file
     let cfg = CliConfig::load();
 
@@ -665,23 +1263,46 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error>> {
     let cli_url_override = cli.url.clone();
     let cli_token_override = cli.token.clone();
 
-    // Merge CLI args with config (CLI takes precedence)
-    let url = cli_url_override
-        .clone()
-        .unwrap_or_else(|| cfg.gateway_url());
+    // Merge CLI args with config (CLI takes precedence). An explicit --url
+    // disables the gateway.urls fallbacks of `gsv node`.
+    let gateway_urls = match cli_url_override.clone() {
+        Some(url) => vec![url],
+        None => cfg.gateway_urls(),
+    };
+    let url = gateway_urls[0].clone();
     let token = cli_token_override.clone().or_else(|| cfg.gateway_token());
+    let output = cli.output;
 
     match cli.command {
-        Commands::Init { force } => run_init(force),
-        Commands::Client { message, session } => {
+        Commands::Init {
+            force,
+            with_sample_node,
+        } => run_init(force, with_sample_node),
+        Commands::Completions { .. } => unreachable!("handled before config load"),
+        Commands::Client {
+            message,
+            session,
+            file,
+            timeout,
+        } => {
             let session = session.unwrap_or_else(|| cfg.default_session());
             let session = config::normalize_session_key(&session);
-            commands::run_client(&url, token, message, &session).await
+            let attachment = file
+                .as_deref()
+                .map(commands::load_chat_attachment)
+                .transpose()?;
+            commands::run_client(&url, token, message, attachment, &session, timeout).await
         }
         Commands::Node {
             foreground,
             id,
             workspace,
+            debug,
+            quiet,
+            log_format,
+            health_port,
+            health_host,
+            as_windows_service,
             action,
         } => {
             if let Some(action) = action {
@@ -695,41 +1316,96 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error>> {
                     &cfg,
                     cli_url_override.as_deref(),
                     cli_token_override.as_deref(),
+                    output,
                 )
             } else if foreground {
                 let node_id = resolve_node_id(id, &cfg);
                 let workspace = resolve_node_workspace(workspace, &cfg);
-                run_node(&url, token, node_id, workspace).await
+                let console_format =
+                    logger::ConsoleLogFormat::parse(&log_format).ok_or_else(|| {
+                        format!(
+                            "Invalid --log-format '{}' (expected json or text)",
+                            log_format
+                        )
+                    })?;
+                let logger = NodeLogger::new(&node_id, &workspace)?.with_console_options(
+                    debug,
+                    quiet,
+                    console_format,
+                );
+                let health_addr =
+                    health_port.map(|port| std::net::SocketAddr::new(health_host, port));
+                if as_windows_service {
+                    #[cfg(target_os = "windows")]
+                    return run_as_windows_service(NodeServiceLaunch {
+                        urls: gateway_urls,
+                        token,
+                        node_id,
+                        workspace,
+                        logger,
+                        cfg,
+                        health_addr,
+                        runtime: tokio::runtime::Handle::current(),
+                    });
+                    #[cfg(not(target_os = "windows"))]
+                    return Err("--as-windows-service is only supported on Windows".into());
+                }
+                run_node(
+                    &gateway_urls,
+                    token,
+                    node_id,
+                    workspace,
+                    logger,
+                    &cfg,
+                    health_addr,
+                )
+                .await
             } else {
                 run_node_default_managed(
                     &cfg,
                     id,
                     workspace,
+                    health_port.map(|port| std::net::SocketAddr::new(health_host, port)),
                     cli_url_override.as_deref(),
                     cli_token_override.as_deref(),
                 )
             }
         }
-        Commands::Config { action } => commands::run_config(&url, token, action).await,
+        Commands::Config { action } => commands::run_config(&url, token, action, output).await,
         Commands::LocalConfig { action } => run_local_config(action),
-        Commands::Deploy { action } => run_deploy(action, &cfg).await,
-        Commands::Session { action } => commands::run_session(&url, token, action).await,
-        Commands::Tools { action } => commands::run_tools(&url, token, action).await,
-        Commands::Skills { action } => commands::run_skills(&url, token, action).await,
+        Commands::Deploy { action } => {
+            deploy::set_json_output(output == OutputFormat::Json);
+            run_deploy(action, &cfg).await
+        }
+        Commands::Session { action } => commands::run_session(&url, token, action, output).await,
+        Commands::Tools { action } => commands::run_tools(&url, token, action, output).await,
+        Commands::Skills { action } => commands::run_skills(&url, token, action, output).await,
         Commands::Mount { action } => run_mount(action, &cfg).await,
-        Commands::Heartbeat { action } => commands::run_heartbeat(&url, token, action).await,
-        Commands::Pair { action } => commands::run_pair(&url, token, action).await,
-        Commands::Channel { action } => commands::run_channel(action, &url, token, &cfg).await,
+        Commands::Heartbeat { action } => {
+            commands::run_heartbeat(&url, token, action, output).await
+        }
+        Commands::Pair { action } => commands::run_pair(&url, token, action, output).await,
+        Commands::Channel { action } => {
+            commands::run_channel(action, &url, token, &cfg, output).await
+        }
     }
 }
 
-fn run_init(force: bool) -> Result<(), Box<dyn std::error::Error>> {
+fn run_init(force: bool, with_sample_node: bool) -> Result<(), Box<dyn std::error::Error>> {
     let Some(path) = CliConfig::config_path() else {
         return Err("Could not determine config directory".into());
     };
 
+    // Read before the sample config overwrites it.
+    let auto_install_completions = CliConfig::load().completions.auto_install;
+
     if path.exists() && !force {
         println!("Config file already exists at: {}", path.display());
+        if auto_install_completions {
+            if let Err(e) = install_shell_completions() {
+                eprintln!("Warning: could not install shell completions: {}", e);
+            }
+        }
         println!("\nUse --force to overwrite, or edit directly:");
         println!("  $EDITOR {}", path.display());
         return Ok(());
@@ -740,10 +1416,52 @@ fn run_init(force: bool) -> Result<(), Box<dyn std::error::Error>> {
         std::fs::create_dir_all(parent)?;
     }
 
-    // Write sample config
-    std::fs::write(&path, config::sample_config())?;
+    // Write sample config, keeping completions.auto_install if it was set
+    let sample = if auto_install_completions {
+        config::sample_config().replace("# auto_install = true", "auto_install = true")
+    } else {
+        config::sample_config().to_string()
+    };
+    std::fs::write(&path, sample)?;
 
     println!("Created config file: {}", path.display());
+
+    if auto_install_completions {
+        if let Err(e) = install_shell_completions() {
+            eprintln!("Warning: could not install shell completions: {}", e);
+        }
+    }
+
+    if with_sample_node {
+        let cfg = CliConfig::load();
+        let default_id = resolve_node_id(None, &cfg);
+        let default_workspace = resolve_node_workspace(None, &cfg);
+
+        let (node_id, workspace) = if can_prompt_interactively() {
+            let node_id = prompt_line("Node ID", Some(&default_id))?;
+            let workspace = prompt_line(
+                "Node workspace",
+                Some(&default_workspace.display().to_string()),
+            )?
+            .map(PathBuf::from);
+            (node_id, workspace)
+        } else {
+            println!("Not an interactive terminal; using default node ID and workspace.");
+            (Some(default_id), Some(default_workspace))
+        };
+
+        let (node_id, workspace, _) = persist_node_defaults(&cfg, node_id, workspace)?;
+        println!(
+            "Saved node defaults: id={}, workspace={}",
+            node_id,
+            workspace.display()
+        );
+        println!("\nNext steps:");
+        println!("  gsv deploy up --all --wizard   # deploy the gateway to Cloudflare");
+        println!("  gsv node install               # run this machine as a node");
+        return Ok(());
+    }
+
     println!("\nEdit it to set your gateway URL and token:");
     println!("  $EDITOR {}", path.display());
     println!("\nOr use 'gsv local-config set' to update values:");
@@ -755,6 +1473,34 @@ fn run_init(force: bool) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Install the completion script for the shell named by `$SHELL` into the
+/// directory that shell loads user completions from.
+fn install_shell_completions() -> Result<(), Box<dyn std::error::Error>> {
+    let shell_path = std::env::var("SHELL").map_err(|_| "SHELL is not set")?;
+    let shell = clap_complete::Shell::from_shell_path(&shell_path)
+        .ok_or_else(|| format!("Unsupported shell: {}", shell_path))?;
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    let target = match shell {
+        clap_complete::Shell::Bash => home.join(".local/share/bash-completion/completions/gsv"),
+        clap_complete::Shell::Zsh => home.join(".zfunc/_gsv"),
+        clap_complete::Shell::Fish => home.join(".config/fish/completions/gsv.fish"),
+        other => return Err(format!("No default completion directory for {}", other).into()),
+    };
+
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut Cli::command(), "gsv", &mut script);
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&target, script)?;
+
+    println!("Installed {} completions: {}", shell, target.display());
+    if shell == clap_complete::Shell::Zsh {
+        println!("  Add to ~/.zshrc (before compinit): fpath=(~/.zfunc $fpath)");
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -780,6 +1526,139 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_completions_script_names_gsv_subcommands() {
+        let mut script = Vec::new();
+        clap_complete::generate(
+            clap_complete::Shell::Bash,
+            &mut Cli::command(),
+            "gsv",
+            &mut script,
+        );
+        let script = String::from_utf8(script).unwrap();
+        assert!(script.contains("_gsv"));
+        assert!(script.contains("local-config"));
+    }
+
+    #[test]
+    fn test_set_local_config_value_parses_completions_auto_install() {
+        let mut cfg = CliConfig::default();
+        assert!(set_local_config_value(&mut cfg, "completions.auto_install", "true").unwrap());
+        assert!(cfg.completions.auto_install);
+        assert!(set_local_config_value(&mut cfg, "completions.auto_install", "yes").is_err());
+    }
+
+    #[test]
+    fn test_resolve_rollback_version_picks_version_before_active() {
+        let version = |id: &str| deploy::WorkerScriptVersion {
+            id: id.to_string(),
+            number: None,
+            created_on: None,
+            source: None,
+        };
+        let versions = vec![version("ccc1"), version("bbb2"), version("aaa3")];
+
+        assert_eq!(
+            resolve_rollback_version(&versions, None, None).unwrap(),
+            "bbb2"
+        );
+        assert_eq!(
+            resolve_rollback_version(&versions, Some("bbb2"), None).unwrap(),
+            "aaa3"
+        );
+        assert!(resolve_rollback_version(&versions, Some("aaa3"), None).is_err());
+        assert_eq!(
+            resolve_rollback_version(&versions, None, Some("aa")).unwrap(),
+            "aaa3"
+        );
+        assert!(resolve_rollback_version(&versions, None, Some("zz")).is_err());
+    }
+
+    #[test]
+    fn test_write_node_log_json_line_formats_table_and_passthrough() {
+        let line = r#"{"ts":"2024-05-01T12:00:00.000Z","level":"ERROR","event":"tool.execute.failed","error":"boom","tool":"Bash"}"#;
+
+        let mut table = termcolor::Buffer::no_color();
+        write_node_log_json_line(&mut table, line, false).unwrap();
+        write_node_log_json_line(&mut table, "plain text", false).unwrap();
+        let table = String::from_utf8(table.into_inner()).unwrap();
+        assert_eq!(
+            table,
+            format!(
+                "{:<24}  ERROR  {:<28}  boom\nplain text\n",
+                "2024-05-01T12:00:00.000Z", "tool.execute.failed"
+            )
+        );
+
+        let mut raw = termcolor::Buffer::no_color();
+        write_node_log_json_line(&mut raw, line, true).unwrap();
+        write_node_log_json_line(&mut raw, "plain text", true).unwrap();
+        let raw = String::from_utf8(raw.into_inner()).unwrap();
+        assert_eq!(raw.lines().count(), 1);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(raw.trim()).unwrap()["tool"],
+            "Bash"
+        );
+    }
+
+    #[test]
+    fn test_log_line_matches_tool_requires_exact_tool_field() {
+        let line = r#"{"event":"tool.invoke","tool":"Bash","args":"Bash2"}"#;
+        assert!(log_line_matches_tool(line, "Bash"));
+        assert!(!log_line_matches_tool(line, "Bas"));
+        assert!(!log_line_matches_tool(
+            r#"{"event":"connect","note":"Bash"}"#,
+            "Bash"
+        ));
+        assert!(!log_line_matches_tool("plain text Bash", "Bash"));
+    }
+
+    #[test]
+    fn test_read_node_log_lines_filtered_counts_only_matches() {
+        let dir = std::env::temp_dir().join(format!("gsv-node-logs-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("node.log"),
+            "tool.invoke Bash 1\nconnect\ntool.invoke Bash 2\nheartbeat\ntool.invoke Bash 3\n",
+        )
+        .unwrap();
+        std::env::set_var(logger::NODE_LOG_DIR_ENV, &dir);
+
+        let (lines, truncated) =
+            read_node_log_lines_filtered(2, |line| line.contains("Bash")).unwrap();
+        assert_eq!(lines, vec!["tool.invoke Bash 2", "tool.invoke Bash 3"]);
+        assert!(truncated);
+
+        let (lines, truncated) =
+            read_node_log_lines_filtered(5, |line| line.contains("connect")).unwrap();
+        assert_eq!(lines, vec!["connect"]);
+        assert!(!truncated);
+
+        std::env::remove_var(logger::NODE_LOG_DIR_ENV);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_deploy_url_assignments_use_shell_variable_names() {
+        let urls = vec![
+            (
+                "gateway".to_string(),
+                "https://gsv.example.workers.dev".to_string(),
+            ),
+            (
+                "channel-discord".to_string(),
+                "https://gsv-channel-discord.example.workers.dev".to_string(),
+            ),
+        ];
+        assert_eq!(
+            deploy_url_assignments(&urls),
+            vec![
+                "GATEWAY_URL=https://gsv.example.workers.dev",
+                "CHANNEL_DISCORD_URL=https://gsv-channel-discord.example.workers.dev",
+            ]
+        );
+    }
+
     #[test]
     fn test_normalize_host_env_keys_trims_and_dedups() {
         let keys = vec![
@@ -806,6 +1685,315 @@ mod tests {
         assert_eq!(normalized, vec!["BAR".to_string(), "FOO".to_string()]);
     }
 
+    #[test]
+    fn test_capabilities_for_tool_prefers_custom_tools_config() {
+        let mut cfg = CliConfig::default();
+        cfg.node.custom_tools.push(config::CustomToolConfig {
+            name: "Deploy".to_string(),
+            capabilities: vec!["shell.exec".to_string()],
+        });
+
+        assert_eq!(
+            capabilities_for_tool("Deploy", Some(&cfg)),
+            Some(vec!["shell.exec".to_string()])
+        );
+        assert_eq!(
+            capabilities_for_tool("Glob", None),
+            Some(vec!["filesystem.list".to_string()])
+        );
+        assert_eq!(capabilities_for_tool("Unknown", None), None);
+    }
+
+    #[test]
+    fn test_build_execution_node_runtime_drops_duplicate_tool_names() {
+        let tool = |name: &str, description: &str| ToolDefinition {
+            name: name.to_string(),
+            description: description.to_string(),
+            input_schema: json!({ "type": "object" }),
+            timeout_seconds: None,
+        };
+        let defs = vec![
+            tool("Bash", "built-in"),
+            tool("Read", "built-in"),
+            tool("Bash", "custom"),
+        ];
+        let logger = test_logger();
+
+        let mut lenient = defs.clone();
+        let runtime = build_execution_node_runtime(&mut lenient, None, false, &logger)
+            .expect("duplicates are dropped");
+        assert_eq!(lenient.len(), 2);
+        assert_eq!(lenient[0].description, "built-in");
+        assert!(runtime.tool_capabilities.contains_key("Bash"));
+
+        let mut strict = defs;
+        assert!(build_execution_node_runtime(&mut strict, None, true, &logger).is_err());
+    }
+
+    #[test]
+    fn test_profile_config_path_and_name_validation() {
+        let path = CliConfig::profile_config_path("staging").unwrap();
+        assert!(path.ends_with("gsv/profiles/staging/config.toml"));
+        assert!(config::set_active_profile("").is_err());
+        assert!(config::set_active_profile("../prod").is_err());
+        assert!(config::set_active_profile("a b").is_err());
+    }
+
+    #[test]
+    fn test_cli_config_redacted_masks_credentials() {
+        let mut cfg = CliConfig::default();
+        cfg.gateway.url = Some("wss://gateway.example.com/ws".to_string());
+        cfg.gateway.token = Some("gateway-secret".to_string());
+        cfg.cloudflare.api_token = Some("cf-secret".to_string());
+        cfg.r2.secret_access_key = Some("r2-secret".to_string());
+
+        let redacted = cfg.redacted();
+        assert_eq!(redacted.gateway.token.as_deref(), Some("****"));
+        assert_eq!(redacted.cloudflare.api_token.as_deref(), Some("****"));
+        assert_eq!(redacted.r2.secret_access_key.as_deref(), Some("****"));
+        assert_eq!(redacted.r2.access_key_id, None);
+        assert_eq!(
+            redacted.gateway.url.as_deref(),
+            Some("wss://gateway.example.com/ws")
+        );
+        assert_eq!(cfg.gateway.token.as_deref(), Some("gateway-secret"));
+    }
+
+    #[test]
+    fn test_parse_gateway_urls_trims_and_validates() {
+        assert_eq!(
+            parse_gateway_urls(" wss://a.example/ws, ws://localhost:8787/ws ,").unwrap(),
+            vec![
+                "wss://a.example/ws".to_string(),
+                "ws://localhost:8787/ws".to_string()
+            ]
+        );
+        assert!(parse_gateway_urls("https://a.example/ws").is_err());
+        assert!(parse_gateway_urls("wss://").is_err());
+        assert!(parse_gateway_urls(" , ").is_err());
+    }
+
+    #[test]
+    fn test_parse_ps_elapsed() {
+        assert_eq!(parse_ps_elapsed("00:42"), Some(42));
+        assert_eq!(parse_ps_elapsed("01:02:03"), Some(3723));
+        assert_eq!(parse_ps_elapsed("2-00:00:01"), Some(172_801));
+        assert_eq!(parse_ps_elapsed("bogus"), None);
+    }
+
+    #[test]
+    fn test_windows_task_xml() {
+        let xml = windows_task_xml(
+            r"C:\Program Files\gsv\gsv.exe",
+            &["--profile".to_string(), "my work".to_string()],
+            r"CORP\alice",
+        );
+
+        assert!(xml.contains("<UserId>CORP\\alice</UserId>\n    </LogonTrigger>"));
+        assert!(xml.contains("<LogonType>InteractiveToken</LogonType>"));
+        assert!(xml.contains(
+            "<RestartOnFailure>\n      <Interval>PT1M</Interval>\n      <Count>999</Count>"
+        ));
+        assert!(xml.contains("<ExecutionTimeLimit>PT0S</ExecutionTimeLimit>"));
+        assert!(xml.contains(r"<Command>C:\Program Files\gsv\gsv.exe</Command>"));
+        assert!(
+            xml.contains("<Arguments>node --foreground --profile &quot;my work&quot;</Arguments>")
+        );
+    }
+
+    #[test]
+    fn test_parse_service_env() {
+        let env = parse_service_env(&[
+            "GSV_URL=wss://gw.example.com/ws".to_string(),
+            "WORKSPACE_ROOT=/srv/my files".to_string(),
+            "EMPTY=".to_string(),
+            "QUERY=a=b".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(env[0], ("GSV_URL".into(), "wss://gw.example.com/ws".into()));
+        assert_eq!(env[2], ("EMPTY".into(), String::new()));
+        assert_eq!(env[3], ("QUERY".into(), "a=b".into()));
+
+        assert!(parse_service_env(&["NOVALUE".to_string()]).is_err());
+        assert!(parse_service_env(&["=x".to_string()]).is_err());
+        assert!(parse_service_env(&["1ABC=x".to_string()]).is_err());
+        assert!(parse_service_env(&["A-B=x".to_string()]).is_err());
+        assert!(parse_service_env(&["A=line\nbreak".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_service_environment_rendering() {
+        let env = vec![
+            ("GSV_TOKEN".to_string(), "s3cr\"t 100%".to_string()),
+            ("GSV_URL".to_string(), "wss://a/b?x=1&y=<2>".to_string()),
+        ];
+
+        assert_eq!(
+            systemd_environment_lines(&env),
+            "Environment=\"GSV_TOKEN=s3cr\\\"t 100%%\"\nEnvironment=\"GSV_URL=wss://a/b?x=1&y=<2>\"\n"
+        );
+        assert_eq!(
+            launchd_environment_dict(&env),
+            "  <key>EnvironmentVariables</key>\n  <dict>\n    <key>GSV_TOKEN</key>\n    <string>s3cr&quot;t 100%</string>\n    <key>GSV_URL</key>\n    <string>wss://a/b?x=1&amp;y=&lt;2&gt;</string>\n  </dict>\n"
+        );
+        assert_eq!(launchd_environment_dict(&[]), "");
+        assert_eq!(systemd_environment_lines(&[]), "");
+    }
+
+    #[test]
+    fn test_parse_systemd_show() {
+        let mut status = NodeServiceStatus::default();
+        parse_systemd_show(
+            "ActiveState=active\nSubState=running\nMainPID=4242\nExecMainStatus=0\nNRestarts=3\n",
+            &mut status,
+        );
+        assert!(status.running);
+        assert_eq!(status.pid, Some(4242));
+        assert_eq!(status.restarts, Some(3));
+        assert_eq!(status.state.as_deref(), Some("active (running)"));
+
+        let mut stopped = NodeServiceStatus::default();
+        parse_systemd_show(
+            "ActiveState=inactive\nSubState=dead\nMainPID=0\n",
+            &mut stopped,
+        );
+        assert!(!stopped.running);
+        assert_eq!(stopped.pid, None);
+    }
+
+    #[test]
+    fn test_render_node_status_block() {
+        let status = NodeServiceStatus {
+            installed: true,
+            running: true,
+            pid: Some(4242),
+            uptime_seconds: Some(3723),
+            last_exit_code: None,
+            ..Default::default()
+        };
+        let rendered = render_node_status_block(&status, &["started".to_string()]);
+        assert!(rendered.starts_with("Node daemon: running\n  PID: 4242\n  Uptime: 1h 2m 3s\n"));
+        assert!(rendered.ends_with("Recent logs:\n  started\n"));
+
+        let missing = render_node_status_block(&NodeServiceStatus::default(), &[]);
+        assert_eq!(missing, "Node daemon: not installed\n");
+    }
+
+    #[test]
+    fn test_node_health_response_reflects_connection_state() {
+        let up = node_health_response("GET /health HTTP/1.1", "laptop", true, 42);
+        assert!(up.starts_with("HTTP/1.1 200 OK\r\n"));
+        let body: serde_json::Value =
+            serde_json::from_str(up.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+        assert_eq!(
+            body,
+            json!({"ok": true, "connected": true, "nodeId": "laptop", "uptime_seconds": 42})
+        );
+
+        let down = node_health_response("GET /health?probe=1 HTTP/1.1", "laptop", false, 5);
+        assert!(down.starts_with("HTTP/1.1 503 Service Unavailable\r\n"));
+        assert!(down.contains("\"connected\":false"));
+
+        let other = node_health_response("POST /health HTTP/1.1", "laptop", true, 5);
+        assert!(other.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+
+    #[test]
+    fn test_rclone_error_is_auth_failure() {
+        assert!(rclone_error_is_auth_failure(
+            "ERROR : : error listing: AccessDenied: Access Denied\n\tstatus code: 403"
+        ));
+        assert!(!rclone_error_is_auth_failure(
+            "ERROR : : error listing: NoSuchBucket: The specified bucket does not exist"
+        ));
+    }
+
+    #[test]
+    fn test_validate_node_extra_args_splits_and_rejects_unknown() {
+        let args = vec![
+            "--debug".to_string(),
+            "--log-format text".to_string(),
+            "--log-format=json".to_string(),
+        ];
+        assert_eq!(
+            validate_node_extra_args(&args).expect("valid args"),
+            vec!["--debug", "--log-format", "text", "--log-format", "json"]
+        );
+
+        assert!(validate_node_extra_args(&["--log-format".to_string()]).is_err());
+        assert!(validate_node_extra_args(&["--log-format xml".to_string()]).is_err());
+        assert!(validate_node_extra_args(&["--id evil".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_render_workspace_tree_limits_depth_but_counts_everything() {
+        let root =
+            std::env::temp_dir().join(format!("gsv-workspace-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(root.join("src").join("deep")).unwrap();
+        fs::write(root.join("README.md"), "hello").unwrap();
+        fs::write(root.join("src").join("main.rs"), "fn main() {}").unwrap();
+        fs::write(root.join("src").join("deep").join("x.txt"), "abc").unwrap();
+
+        let mut tree = String::new();
+        let mut summary = WorkspaceSummary::default();
+        render_workspace_tree(&root, "", 0, 2, &mut tree, &mut summary).unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(
+            tree,
+            "├── README.md\n└── src/\n    ├── deep/\n    └── main.rs\n"
+        );
+        assert_eq!(
+            summary,
+            WorkspaceSummary {
+                dirs: 2,
+                files: 3,
+                bytes: 20,
+            }
+        );
+    }
+
+    #[test]
+    fn test_deploy_report_marks_components_that_were_not_uploaded() {
+        let deployed = vec![deploy::DeployedComponent {
+            component: "gateway".to_string(),
+            script_name: "gsv".to_string(),
+            workers_dev_url: Some("https://gsv.acme.workers.dev".to_string()),
+            existed_before: true,
+            migrations_applied: Some("v3".to_string()),
+            assets_synced: true,
+        }];
+        let report = deploy_report(
+            "2026-01-01T00:00:00+00:00",
+            "v0.4.0",
+            &["gateway".to_string(), "channel-discord".to_string()],
+            &deployed,
+            Some("upload failed"),
+        );
+
+        assert_eq!(
+            report,
+            json!({
+                "deployed_at": "2026-01-01T00:00:00+00:00",
+                "bundle_version": "v0.4.0",
+                "success": false,
+                "error": "upload failed",
+                "components": [
+                    {
+                        "component": "gateway",
+                        "deployed": true,
+                        "script_name": "gsv",
+                        "workers_dev_url": "https://gsv.acme.workers.dev",
+                        "existed_before": true,
+                        "migrations_applied": "v3",
+                        "assets_synced": true
+                    },
+                    { "component": "channel-discord", "deployed": false }
+                ]
+            })
+        );
+    }
+
     #[test]
     fn test_queue_exec_event_for_retry_drops_oldest_when_full() {
         let logger = test_logger();
@@ -851,10 +2039,90 @@ mod tests {
     }
 }
 
+/// Parse a comma-separated list of gateway WebSocket URLs.
+fn parse_gateway_urls(value: &str) -> Result<Vec<String>, String> {
+    let urls: Vec<String> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    if urls.is_empty() {
+        return Err("gateway.urls requires at least one URL".to_string());
+    }
+    for url in &urls {
+        let host = url
+            .strip_prefix("wss://")
+            .or_else(|| url.strip_prefix("ws://"))
+            .ok_or_else(|| format!("Invalid gateway URL '{}': expected ws:// or wss://", url))?;
+        if host.is_empty() || host.starts_with('/') {
+            return Err(format!("Invalid gateway URL '{}': missing host", url));
+        }
+    }
+
+    Ok(urls)
+}
+
+/// Environment variables imported by `local-config set --from-env`.
+const LOCAL_CONFIG_ENV_VARS: &[(&str, &str)] = &[
+    ("GSV_URL", "gateway.url"),
+    ("GSV_TOKEN", "gateway.token"),
+    ("CF_API_TOKEN", "cloudflare.api_token"),
+    ("CF_ACCOUNT_ID", "cloudflare.account_id"),
+];
+
+/// Applies `value` to `key`; returns false for unknown keys.
+fn set_local_config_value(
+    cfg: &mut CliConfig,
+    key: &str,
+    value: &str,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let value = value.to_string();
+    match key {
+        "gateway.url" => cfg.gateway.url = Some(value),
+        "gateway.urls" => cfg.gateway.urls = parse_gateway_urls(&value)?,
+        "gateway.token" => cfg.gateway.token = Some(value),
+        "cloudflare.account_id" => cfg.cloudflare.account_id = Some(value),
+        "cloudflare.api_token" => cfg.cloudflare.api_token = Some(value),
+        "r2.account_id" => cfg.r2.account_id = Some(value),
+        "r2.access_key_id" => cfg.r2.access_key_id = Some(value),
+        "r2.secret_access_key" => cfg.r2.secret_access_key = Some(value),
+        "r2.bucket" => cfg.r2.bucket = Some(value),
+        "session.default_key" => {
+            cfg.session.default_key = Some(config::normalize_session_key(&value))
+        }
+        "node.id" => cfg.node.id = Some(value),
+        "node.workspace" => cfg.node.workspace = Some(PathBuf::from(value)),
+        "channels.whatsapp.url" => cfg.channels.whatsapp.url = Some(value),
+        "channels.whatsapp.token" => cfg.channels.whatsapp.token = Some(value),
+        "channels.telegram.url" => cfg.channels.telegram.url = Some(value),
+        "channels.telegram.token" => cfg.channels.telegram.token = Some(value),
+        "channels.slack.url" => cfg.channels.slack.url = Some(value),
+        "channels.slack.token" => cfg.channels.slack.token = Some(value),
+        "completions.auto_install" => {
+            cfg.completions.auto_install = value
+                .parse()
+                .map_err(|_| format!("Invalid value for {}: expected true or false", key))?
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn masked_local_config_value<'a>(key: &str, value: &'a str) -> &'a str {
+    if key.contains("token") || key.contains("secret") {
+        "****"
+    } else {
+        value
+    }
+}
+
 fn run_local_config(action: LocalConfigAction) -> Result<(), Box<dyn std::error::Error>> {
     match action {
-        LocalConfigAction::Show => {
+        LocalConfigAction::Show { redact } => {
             let cfg = CliConfig::load();
+            let cfg = if redact { cfg.redacted() } else { cfg };
             let toml_str = toml::to_string_pretty(&cfg)?;
             println!("{}", toml_str);
         }
@@ -863,6 +2131,7 @@ fn run_local_config(action: LocalConfigAction) -> Result<(), Box<dyn std::error:
             let cfg = CliConfig::load();
             let value = match key.as_str() {
                 "gateway.url" => cfg.gateway.url.map(|s| s.to_string()),
+                "gateway.urls" => Some(cfg.gateway.urls.join("\n")).filter(|s| !s.is_empty()),
                 "gateway.token" => cfg.gateway.token.map(|s| {
                     // Mask token for security
                     if s.len() > 8 {
@@ -891,14 +2160,24 @@ fn run_local_config(action: LocalConfigAction) -> Result<(), Box<dyn std::error:
                 "session.default_key" => cfg.session.default_key,
                 "node.id" => cfg.node.id,
                 "node.workspace" => cfg.node.workspace.map(|path| path.display().to_string()),
+                "channels.telegram.url" => cfg.channels.telegram.url,
+                "channels.telegram.token" => {
+                    cfg.channels.telegram.token.map(|_| "****".to_string())
+                }
+                "channels.slack.url" => cfg.channels.slack.url,
+                "channels.slack.token" => cfg.channels.slack.token.map(|_| "****".to_string()),
+                "completions.auto_install" => Some(cfg.completions.auto_install.to_string()),
                 _ => {
                     eprintln!("Unknown config key: {}", key);
                     eprintln!("\nValid keys:");
-                    eprintln!("  gateway.url, gateway.token");
+                    eprintln!("  gateway.url, gateway.urls, gateway.token");
                     eprintln!("  cloudflare.account_id, cloudflare.api_token");
                     eprintln!("  r2.account_id, r2.access_key_id, r2.bucket");
                     eprintln!("  session.default_key");
                     eprintln!("  node.id, node.workspace");
+                    eprintln!("  channels.telegram.url, channels.telegram.token");
+                    eprintln!("  channels.slack.url, channels.slack.token");
+                    eprintln!("  completions.auto_install");
                     return Ok(());
                 }
             };
@@ -909,29 +2188,38 @@ fn run_local_config(action: LocalConfigAction) -> Result<(), Box<dyn std::error:
             }
         }
 
-        LocalConfigAction::Set { key, value } => {
+        LocalConfigAction::Set { from_env: true, .. } => {
+            let mut cfg = CliConfig::load();
+            let mut applied = Vec::new();
+            for (env_var, key) in LOCAL_CONFIG_ENV_VARS {
+                let Some(value) = std::env::var(env_var).ok().filter(|v| !v.is_empty()) else {
+                    continue;
+                };
+                set_local_config_value(&mut cfg, key, &value)?;
+                applied.push((*key, value));
+            }
+
+            if applied.is_empty() {
+                let names: Vec<&str> = LOCAL_CONFIG_ENV_VARS.iter().map(|(env, _)| *env).collect();
+                println!("None of {} are set; nothing to import.", names.join(", "));
+                return Ok(());
+            }
+
+            cfg.save()?;
+            for (key, value) in applied {
+                println!("Set {} = {}", key, masked_local_config_value(key, &value));
+            }
+        }
+
+        LocalConfigAction::Set { key, value, .. } => {
+            let (Some(key), Some(value)) = (key, value) else {
+                return Err("local-config set requires <KEY> <VALUE> or --from-env".into());
+            };
             let mut cfg = CliConfig::load();
 
-            match key.as_str() {
-                "gateway.url" => cfg.gateway.url = Some(value.clone()),
-                "gateway.token" => cfg.gateway.token = Some(value.clone()),
-                "cloudflare.account_id" => cfg.cloudflare.account_id = Some(value.clone()),
-                "cloudflare.api_token" => cfg.cloudflare.api_token = Some(value.clone()),
-                "r2.account_id" => cfg.r2.account_id = Some(value.clone()),
-                "r2.access_key_id" => cfg.r2.access_key_id = Some(value.clone()),
-                "r2.secret_access_key" => cfg.r2.secret_access_key = Some(value.clone()),
-                "r2.bucket" => cfg.r2.bucket = Some(value.clone()),
-                "session.default_key" => {
-                    cfg.session.default_key = Some(config::normalize_session_key(&value))
-                }
-                "node.id" => cfg.node.id = Some(value.clone()),
-                "node.workspace" => cfg.node.workspace = Some(PathBuf::from(value.clone())),
-                "channels.whatsapp.url" => cfg.channels.whatsapp.url = Some(value.clone()),
-                "channels.whatsapp.token" => cfg.channels.whatsapp.token = Some(value.clone()),
-                _ => {
-                    eprintln!("Unknown config key: {}", key);
-                    return Ok(());
-                }
+            if !set_local_config_value(&mut cfg, &key, &value)? {
+                eprintln!("Unknown config key: {}", key);
+                return Ok(());
             }
 
             cfg.save()?;
@@ -943,11 +2231,7 @@ fn run_local_config(action: LocalConfigAction) -> Result<(), Box<dyn std::error:
             println!(
                 "Set {} = {}",
                 key,
-                if key.contains("token") || key.contains("secret") {
-                    "****"
-                } else {
-                    display_value
-                }
+                masked_local_config_value(&key, display_value)
             );
         }
 
@@ -1011,6 +2295,54 @@ fn generate_gateway_auth_token() -> String {
     )
 }
 
+fn format_worker_version(version: &deploy::WorkerScriptVersion, active: bool) -> String {
+    let mut label = match version.number {
+        Some(number) => format!("#{:<4} {}", number, version.id),
+        None => version.id.clone(),
+    };
+    if let Some(created_on) = &version.created_on {
+        label.push_str(&format!("  {}", created_on));
+    }
+    if let Some(source) = &version.source {
+        label.push_str(&format!("  ({})", source));
+    }
+    if active {
+        label.push_str("  [active]");
+    }
+    label
+}
+
+/// Picks the version to roll back to: `requested` by full ID or unique
+/// prefix, otherwise the newest version older than `active` (or the second
+/// newest when the active version is unknown).
+fn resolve_rollback_version(
+    versions: &[deploy::WorkerScriptVersion],
+    active: Option<&str>,
+    requested: Option<&str>,
+) -> Result<String, String> {
+    if let Some(requested) = requested {
+        let matches: Vec<&deploy::WorkerScriptVersion> = versions
+            .iter()
+            .filter(|v| v.id.starts_with(requested))
+            .collect();
+        return match matches.as_slice() {
+            [version] => Ok(version.id.clone()),
+            [] if requested.len() >= 32 => Ok(requested.to_string()),
+            [] => Err(format!(
+                "No listed version matches '{}' (use the full ID for older versions)",
+                requested
+            )),
+            _ => Err(format!("Version prefix '{}' is ambiguous", requested)),
+        };
+    }
+
+    let active_index = active.and_then(|id| versions.iter().position(|v| v.id == id));
+    versions
+        .get(active_index.unwrap_or(0) + 1)
+        .map(|v| v.id.clone())
+        .ok_or_else(|| "No previous version to roll back to".to_string())
+}
+
 fn can_prompt_interactively() -> bool {
     io::stdin().is_terminal() && io::stdout().is_terminal()
 }
@@ -1236,33 +2568,196 @@ fn prompt_llm_provider(
     Ok(selection)
 }
 
-fn gateway_http_url_to_ws_url(gateway_url: &str) -> String {
-    let mut ws_url = if let Some(rest) = gateway_url.strip_prefix("https://") {
-        format!("wss://{}", rest)
-    } else if let Some(rest) = gateway_url.strip_prefix("http://") {
-        format!("ws://{}", rest)
-    } else {
-        gateway_url.to_string()
-    };
+fn gateway_http_url_to_ws_url(gateway_url: &str) -> String {
+    let mut ws_url = if let Some(rest) = gateway_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = gateway_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        gateway_url.to_string()
+    };
+
+    if !ws_url.ends_with("/ws") {
+        ws_url = ws_url.trim_end_matches('/').to_string();
+        ws_url.push_str("/ws");
+    }
+
+    ws_url
+}
+
+fn save_gateway_local_config(
+    gateway_url: &str,
+    auth_token: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut local_cfg = CliConfig::load();
+    local_cfg.gateway.url = Some(gateway_http_url_to_ws_url(gateway_url));
+    if let Some(token) = auth_token {
+        local_cfg.gateway.token = Some(token.to_string());
+    }
+    local_cfg.save()?;
+    Ok(())
+}
+
+/// `println!` for `gsv deploy up` progress; silenced by `--quiet` (warnings move to stderr).
+macro_rules! deploy_status {
+    () => {
+        if !deploy::quiet_output() {
+            println!();
+        }
+    };
+    ($($arg:tt)*) => {{
+        let line = format!($($arg)*);
+        if !deploy::quiet_output() {
+            println!("{}", line);
+        } else if line.starts_with("Warning:") {
+            eprintln!("{}", line);
+        }
+    }};
+}
+
+const DEPLOY_HEALTH_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Polls `deploy::verify_deploy` until every component answers without a 5xx
+/// or `timeout_secs` elapses.
+async fn wait_for_deploy_health(
+    component_urls: &[(String, String)],
+    timeout_secs: u64,
+    quiet: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if component_urls.is_empty() {
+        deploy_status!(
+            "Warning: no workers.dev URLs known for deployed components; skipping --wait."
+        );
+        return Ok(());
+    }
+
+    let started = std::time::Instant::now();
+    let timeout = std::time::Duration::from_secs(timeout_secs);
+    let spinner = (!quiet && io::stderr().is_terminal()).then(cliclack::spinner);
+    if let Some(spinner) = &spinner {
+        spinner.start("Waiting for workers to become healthy...");
+    }
+
+    loop {
+        let results = deploy::verify_deploy(component_urls).await;
+        let elapsed = started.elapsed().as_secs();
+        let unhealthy: Vec<&deploy::ComponentHealth> =
+            results.iter().filter(|r| !r.healthy()).collect();
+
+        if unhealthy.is_empty() {
+            if let Some(spinner) = &spinner {
+                spinner.stop(format!("All components healthy ({}s).", elapsed));
+            } else {
+                deploy_status!("All components healthy ({}s).", elapsed);
+            }
+            return Ok(());
+        }
+
+        if started.elapsed() >= timeout {
+            if let Some(spinner) = &spinner {
+                spinner.error(format!("Timed out after {}s", elapsed));
+            }
+            eprintln!("Components not healthy after {}s:", elapsed);
+            for result in &unhealthy {
+                let last = match (result.status, result.error.as_deref()) {
+                    (Some(status), _) => format!("HTTP {}", status),
+                    (None, Some(error)) => error.to_string(),
+                    (None, None) => "no response".to_string(),
+                };
+                eprintln!("  {:<18} {} ({})", result.component, result.url, last);
+            }
+            return Err("Deployment did not become healthy before --wait-timeout".into());
+        }
+
+        let pending = unhealthy
+            .iter()
+            .map(|r| r.component.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        match &spinner {
+            Some(spinner) => spinner.set_message(format!("Waiting for {} ({}s)", pending, elapsed)),
+            None => deploy_status!("Waiting for {} ({}s)...", pending, elapsed),
+        }
+        tokio::time::sleep(DEPLOY_HEALTH_POLL_INTERVAL).await;
+    }
+}
+
+/// `gateway` -> `GATEWAY_URL=...`, `channel-discord` -> `CHANNEL_DISCORD_URL=...`.
+fn deploy_url_assignments(component_urls: &[(String, String)]) -> Vec<String> {
+    component_urls
+        .iter()
+        .map(|(component, url)| {
+            format!(
+                "{}_URL={}",
+                component.to_ascii_uppercase().replace('-', "_"),
+                url
+            )
+        })
+        .collect()
+}
+
+fn write_deploy_tag_output(target: &str, version: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if target == "-" {
+        println!("{}", version);
+        return Ok(());
+    }
+
+    let path = Path::new(target);
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, format!("{}\n", version))
+        .map_err(|e| format!("Failed to write --tag-output {}: {}", path.display(), e))?;
+    deploy_status!("Wrote deployed version {} to {}", version, path.display());
+    Ok(())
+}
+
+/// JSON summary for `deploy up --report`. Requested components that were not
+/// uploaded are listed with `"deployed": false`.
+fn deploy_report(
+    deployed_at: &str,
+    bundle_version: &str,
+    components: &[String],
+    deployed: &[deploy::DeployedComponent],
+    error: Option<&str>,
+) -> serde_json::Value {
+    let entries: Vec<serde_json::Value> = components
+        .iter()
+        .map(
+            |component| match deployed.iter().find(|entry| &entry.component == component) {
+                Some(entry) => {
+                    let mut value = json!(entry);
+                    value["deployed"] = json!(true);
+                    value
+                }
+                None => json!({ "component": component, "deployed": false }),
+            },
+        )
+        .collect();
 
-    if !ws_url.ends_with("/ws") {
-        ws_url = ws_url.trim_end_matches('/').to_string();
-        ws_url.push_str("/ws");
+    let mut report = json!({
+        "deployed_at": deployed_at,
+        "bundle_version": bundle_version,
+        "success": error.is_none(),
+        "components": entries,
+    });
+    if let Some(error) = error {
+        report["error"] = json!(error);
     }
-
-    ws_url
+    report
 }
 
-fn save_gateway_local_config(
-    gateway_url: &str,
-    auth_token: Option<&str>,
+fn write_deploy_report(
+    path: &Path,
+    report: &serde_json::Value,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut local_cfg = CliConfig::load();
-    local_cfg.gateway.url = Some(gateway_http_url_to_ws_url(gateway_url));
-    if let Some(token) = auth_token {
-        local_cfg.gateway.token = Some(token.to_string());
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to write --report {}: {}", path.display(), e))?;
     }
-    local_cfg.save()?;
+    fs::write(path, format!("{}\n", serde_json::to_string_pretty(report)?))
+        .map_err(|e| format!("Failed to write --report {}: {}", path.display(), e))?;
+    deploy_status!("Wrote deploy report to {}", path.display());
     Ok(())
 }
 
@@ -1272,24 +2767,52 @@ async fn run_deploy(
 ) -> Result<(), Box<dyn std::error::Error>> {
     deploy::set_notification_output(false);
     match action {
-        DeployAction::Up {
-            version,
-            component,
-            all,
-            force_fetch,
-            bundle_dir,
-            wizard,
-            api_token,
-            account_id,
-            gateway_auth_token,
-            llm_provider,
-            llm_model,
-            llm_api_key,
-            discord_bot_token,
-        } => {
+        DeployAction::Up(args) => {
+            let DeployUpArgs {
+                version,
+                component,
+                all,
+                force_fetch,
+                bundle_dir,
+                wizard,
+                api_token,
+                account_id,
+                gateway_auth_token,
+                llm_provider,
+                llm_model,
+                llm_api_key,
+                discord_bot_token,
+                discord_channel_guild_id,
+                telegram_bot_token,
+                slack_bot_token,
+                skip_r2,
+                wrangler_override,
+                name_prefix,
+                skip_source_map,
+                force_redeploy,
+                cloudflare_zone,
+                gateway_route,
+                apply_timeout,
+                wait,
+                wait_timeout,
+                quiet,
+                tag_output,
+                output_urls,
+                report,
+                dry_run,
+                migrate,
+            } = *args;
             if all && !component.is_empty() {
                 return Err("Use either --all or one/more --component values, not both".into());
             }
+            if quiet && wizard {
+                return Err("--quiet cannot be combined with --wizard".into());
+            }
+            deploy::set_quiet_output(quiet);
+            let wrangler_overrides = deploy::parse_wrangler_overrides(&wrangler_override)?;
+            if let Some(prefix) = name_prefix.as_deref() {
+                deploy::validate_name_prefix(prefix)?;
+            }
 
             let interactive = can_prompt_interactively();
             let wizard_mode = wizard;
@@ -1320,7 +2843,7 @@ async fn run_deploy(
                 interactive,
             )
             .await?;
-            println!("Cloudflare account ID: {}", resolved_account_id);
+            deploy_status!("Cloudflare account ID: {}", resolved_account_id);
 
             let mut components = if all {
                 deploy::available_components()
@@ -1338,6 +2861,12 @@ async fn run_deploy(
                 }
             }
 
+            if migrate && components != ["gateway"] {
+                return Err(
+                    "--migrate only applies to the gateway; use --component gateway".into(),
+                );
+            }
+
             let deploying_gateway = components.iter().any(|c| c == "gateway");
             let deploying_whatsapp = components.iter().any(|c| c == "channel-whatsapp");
             let deploying_discord = components.iter().any(|c| c == "channel-discord");
@@ -1370,10 +2899,10 @@ async fn run_deploy(
                     )?
                 {
                     if persist_cloudflare_account_id(&resolved_account_id)? {
-                        println!("Saved cloudflare.account_id to local config.");
+                        deploy_status!("Saved cloudflare.account_id to local config.");
                     }
                 } else {
-                    println!(
+                    deploy_status!(
                         "Tip: persist it with `gsv local-config set cloudflare.account_id {}`",
                         resolved_account_id
                     );
@@ -1389,6 +2918,7 @@ async fn run_deploy(
             let mut resolved_llm_model = llm_model;
             let mut resolved_llm_api_key = llm_api_key;
             let mut resolved_discord_bot_token = discord_bot_token;
+            let mut resolved_slack_bot_token = slack_bot_token;
             let explicit_gateway_auth_token = gateway_auth_token.clone();
             let mut desired_gateway_auth_token = explicit_gateway_auth_token.clone();
             let connect_gateway_auth_token = explicit_gateway_auth_token
@@ -1482,6 +3012,19 @@ async fn run_deploy(
                 }
             }
 
+            // Slack has no bundled component; the token goes to an existing
+            // gsv-channel-slack worker, so only offer it (default no).
+            if resolved_slack_bot_token.is_none()
+                && wizard_mode
+                && interactive
+                && prompt_yes_no(
+                    "Configure Slack bot token on an existing Slack channel worker?",
+                    false,
+                )?
+            {
+                resolved_slack_bot_token = prompt_secret("Slack bot token (xoxb-...)")?;
+            }
+
             if wizard_mode && interactive {
                 let mut summary = format!(
                     "Account: {}\nComponents: {}",
@@ -1507,6 +3050,9 @@ async fn run_deploy(
                         }
                     ));
                 }
+                if resolved_slack_bot_token.is_some() {
+                    summary.push_str("\nSlack bot token: provided");
+                }
                 note("Deployment summary", summary)?;
                 if !prompt_yes_no("Ready to deploy?", true)? {
                     let _ = outro_cancel("Deployment cancelled.");
@@ -1520,27 +3066,114 @@ async fn run_deploy(
             } else {
                 deploy::resolve_release_tag(&version).await?
             };
-            println!("Preparing components: {}", components.join(", "));
+            deploy_status!("Preparing components: {}", components.join(", "));
             if let Some(dir) = bundle_dir {
-                println!("Using local bundles from {}", dir.display());
+                deploy_status!("Using local bundles from {}", dir.display());
                 deploy::install_bundles_from_dir(cfg, &dir, &version, &components, force_fetch)?;
             } else {
-                deploy::fetch_bundles(cfg, &version, &components, force_fetch).await?;
+                deploy::fetch_bundles(cfg, &version, &components, force_fetch, false).await?;
             }
 
-            println!();
-            println!(
+            deploy_status!();
+            deploy_status!(
                 "Preparation complete. Applying deploy from version {}.",
                 bundle_version
             );
-            let apply_result = deploy::apply_deploy(
-                cfg,
-                &resolved_account_id,
-                &token,
-                &bundle_version,
-                &components,
+            let apply_options = deploy::DeployApplyOptions {
+                skip_r2,
+                wrangler_overrides,
+                name_prefix: name_prefix.clone(),
+                skip_source_map,
+                force_redeploy,
+                gateway_route: cloudflare_zone.zip(gateway_route),
+                dry_run,
+                ..Default::default()
+            };
+            if migrate {
+                deploy::migrate_gateway_durable_objects(
+                    cfg,
+                    &bundle_version,
+                    &resolved_account_id,
+                    &token,
+                    &apply_options,
+                )
+                .await?;
+                return Ok(());
+            }
+            if dry_run {
+                deploy::apply_deploy(
+                    cfg,
+                    &resolved_account_id,
+                    &token,
+                    &bundle_version,
+                    &components,
+                    &apply_options,
+                )
+                .await?;
+                return Ok(());
+            }
+            let apply_result = match tokio::time::timeout(
+                std::time::Duration::from_secs(apply_timeout),
+                deploy::apply_deploy(
+                    cfg,
+                    &resolved_account_id,
+                    &token,
+                    &bundle_version,
+                    &components,
+                    &apply_options,
+                ),
             )
-            .await?;
+            .await
+            {
+                Ok(result) => result,
+                Err(_) => {
+                    let deployed = apply_options
+                        .deployed_components
+                        .lock()
+                        .map(|deployed| {
+                            deployed
+                                .iter()
+                                .map(|entry| entry.component.clone())
+                                .collect::<Vec<_>>()
+                        })
+                        .unwrap_or_default();
+                    eprintln!();
+                    if deployed.is_empty() {
+                        eprintln!("No components were uploaded before the timeout.");
+                    } else {
+                        eprintln!(
+                            "Uploaded before the timeout (service bindings may not be finalized): {}",
+                            deployed.join(", ")
+                        );
+                    }
+                    Err(format!(
+                        "Deploy apply timed out after {}s (--apply-timeout)",
+                        apply_timeout
+                    )
+                    .into())
+                }
+            };
+            if let Some(path) = report.as_deref() {
+                let deployed = apply_options
+                    .deployed_components
+                    .lock()
+                    .map(|deployed| deployed.clone())
+                    .unwrap_or_default();
+                let error = apply_result.as_ref().err().map(|e| e.to_string());
+                let summary = deploy_report(
+                    &chrono::Utc::now().to_rfc3339(),
+                    &bundle_version,
+                    &components,
+                    &deployed,
+                    error.as_deref(),
+                );
+                // The deploy outcome matters more than the report; don't let a
+                // write failure hide it.
+                if let Err(e) = write_deploy_report(path, &summary) {
+                    eprintln!("Warning: {}", e);
+                }
+            }
+            let apply_result = apply_result?;
 
             if deploying_gateway
                 && desired_gateway_auth_token.is_none()
@@ -1573,8 +3206,8 @@ async fn run_deploy(
 
                     let mut bootstrap_applied = false;
                     if should_bootstrap {
-                        println!();
-                        println!("Applying gateway runtime configuration...");
+                        deploy_status!();
+                        deploy_status!("Applying gateway runtime configuration...");
                         match deploy::bootstrap_gateway_config(
                             gateway_url,
                             connect_gateway_auth_token
@@ -1588,7 +3221,7 @@ async fn run_deploy(
                                 bootstrap_applied = true;
                                 if let Some(token_value) = gateway_bootstrap.auth_token.as_deref() {
                                     if cfg.gateway.token.as_deref() != Some(token_value) {
-                                        println!(
+                                        deploy_status!(
                                             "Gateway auth token: {}...{}",
                                             &token_value[..4.min(token_value.len())],
                                             &token_value[token_value.len().saturating_sub(4)..]
@@ -1597,15 +3230,15 @@ async fn run_deploy(
                                 }
                             }
                             Err(error) => {
-                                println!(
+                                deploy_status!(
                                     "Warning: gateway runtime configuration failed: {}",
                                     error
                                 );
-                                println!("You can apply settings manually with:");
-                                println!("  gsv config set auth.token <token>");
-                                println!("  gsv config set model.provider <provider>");
-                                println!("  gsv config set model.id <model>");
-                                println!("  gsv config set apiKeys.<provider> <api-key>");
+                                deploy_status!("You can apply settings manually with:");
+                                deploy_status!("  gsv config set auth.token <token>");
+                                deploy_status!("  gsv config set model.provider <provider>");
+                                deploy_status!("  gsv config set model.id <model>");
+                                deploy_status!("  gsv config set apiKeys.<provider> <api-key>");
                             }
                         }
                     }
@@ -1617,17 +3250,17 @@ async fn run_deploy(
                     };
                     save_gateway_local_config(gateway_url, token_to_save)?;
                     if bootstrap_applied {
-                        println!("Saved gateway URL/token to local config.");
+                        deploy_status!("Saved gateway URL/token to local config.");
                     } else {
-                        println!("Saved gateway URL to local config.");
+                        deploy_status!("Saved gateway URL to local config.");
                         if should_bootstrap {
-                            println!(
+                            deploy_status!(
                                 "Gateway token was not saved because runtime configuration did not complete."
                             );
                         }
                     }
                 } else {
-                    println!(
+                    deploy_status!(
                         "Warning: gateway URL was unavailable, skipping runtime configuration step."
                     );
                 }
@@ -1635,18 +3268,94 @@ async fn run_deploy(
 
             if deploying_discord {
                 if let Some(bot_token) = resolved_discord_bot_token.as_deref() {
-                    println!("Setting DISCORD_BOT_TOKEN secret on Discord channel worker...");
-                    deploy::set_discord_bot_token_secret(&resolved_account_id, &token, bot_token)
-                        .await?;
-                    println!("Configured DISCORD_BOT_TOKEN.");
+                    deploy_status!("Setting DISCORD_BOT_TOKEN secret on Discord channel worker...");
+                    deploy::set_discord_bot_token_secret(
+                        &resolved_account_id,
+                        &token,
+                        bot_token,
+                        name_prefix.as_deref(),
+                    )
+                    .await?;
+                    deploy_status!("Configured DISCORD_BOT_TOKEN.");
+
+                    if let Some(guild_id) = discord_channel_guild_id.as_deref() {
+                        deploy_status!(
+                            "Registering Discord slash commands in guild {}...",
+                            guild_id
+                        );
+                        let registered =
+                            deploy::register_discord_slash_commands(bot_token, guild_id).await?;
+                        for name in &registered {
+                            deploy_status!("  /{}", name);
+                        }
+                        deploy_status!("Registered {} slash command(s).", registered.len());
+                    }
                 } else {
-                    println!("Note: Discord bot token not configured.");
-                    println!(
+                    deploy_status!("Note: Discord bot token not configured.");
+                    deploy_status!(
                         "Tip: rerun deploy with --discord-bot-token (or DISCORD_BOT_TOKEN env) before `gsv channel discord start`."
                     );
+                    if discord_channel_guild_id.is_some() {
+                        deploy_status!(
+                            "Warning: --discord-channel-guild-id ignored; slash commands need a bot token."
+                        );
+                    }
+                }
+            }
+
+            if let Some(bot_token) = telegram_bot_token.as_deref() {
+                deploy_status!("Setting TELEGRAM_BOT_TOKEN secret on Telegram channel worker...");
+                if deploy::set_telegram_bot_token_secret(
+                    &resolved_account_id,
+                    &token,
+                    bot_token,
+                    name_prefix.as_deref(),
+                )
+                .await?
+                {
+                    deploy_status!("Configured TELEGRAM_BOT_TOKEN.");
+                } else {
+                    deploy_status!(
+                        "Warning: Telegram channel worker is not deployed; TELEGRAM_BOT_TOKEN not set."
+                    );
+                }
+            }
+
+            if let Some(bot_token) = resolved_slack_bot_token.as_deref() {
+                deploy_status!("Setting SLACK_BOT_TOKEN secret on Slack channel worker...");
+                if deploy::set_slack_bot_token_secret(
+                    &resolved_account_id,
+                    &token,
+                    bot_token,
+                    name_prefix.as_deref(),
+                )
+                .await?
+                {
+                    deploy_status!("Configured SLACK_BOT_TOKEN.");
+                } else {
+                    deploy_status!(
+                        "Warning: Slack channel worker is not deployed; SLACK_BOT_TOKEN not set."
+                    );
                 }
             }
 
+            if let Some(target) = tag_output.as_deref() {
+                write_deploy_tag_output(target, &bundle_version)?;
+            }
+
+            if output_urls {
+                deploy_status!();
+                for line in deploy_url_assignments(&apply_result.component_urls) {
+                    println!("{}", line);
+                }
+            }
+
+            // Wait last so a health timeout can't skip the gateway bootstrap
+            // or lose a freshly generated auth token.
+            if wait {
+                wait_for_deploy_health(&apply_result.component_urls, wait_timeout, quiet).await?;
+            }
+
             Ok(())
         }
         DeployAction::Down {
@@ -1654,6 +3363,8 @@ async fn run_deploy(
             all,
             delete_bucket,
             purge_bucket,
+            keep_r2,
+            name_prefix,
             wizard,
             api_token,
             account_id,
@@ -1661,6 +3372,9 @@ async fn run_deploy(
             if all && !component.is_empty() {
                 return Err("Use either --all or one/more --component values, not both".into());
             }
+            if let Some(prefix) = name_prefix.as_deref() {
+                deploy::validate_name_prefix(prefix)?;
+            }
             let interactive = can_prompt_interactively();
             let wizard_mode = wizard;
 
@@ -1723,8 +3437,12 @@ async fn run_deploy(
             let mut purge_bucket_resource = purge_bucket;
 
             if wizard_mode && interactive {
-                delete_bucket_resource =
-                    prompt_yes_no("Also delete R2 bucket gsv-storage?", delete_bucket_resource)?;
+                if !keep_r2 {
+                    delete_bucket_resource = prompt_yes_no(
+                        "Also delete R2 bucket gsv-storage?",
+                        delete_bucket_resource,
+                    )?;
+                }
                 if delete_bucket_resource {
                     purge_bucket_resource = prompt_yes_no(
                         "Purge bucket objects before deletion?",
@@ -1758,6 +3476,8 @@ async fn run_deploy(
                 &components,
                 delete_bucket_resource,
                 purge_bucket_resource,
+                keep_r2,
+                name_prefix.as_deref(),
             )
             .await
         }
@@ -1766,10 +3486,18 @@ async fn run_deploy(
             all,
             api_token,
             account_id,
+            require,
+            wait_for,
+            timeout,
+            watch,
+            name_prefix,
         } => {
             if all && !component.is_empty() {
                 return Err("Use either --all or one/more --component values, not both".into());
             }
+            if let Some(prefix) = name_prefix.as_deref() {
+                deploy::validate_name_prefix(prefix)?;
+            }
 
             let token = api_token
                 .or_else(|| cfg.cloudflare.api_token.clone())
@@ -1792,8 +3520,170 @@ async fn run_deploy(
                 deploy::normalize_components(&component)?
             };
 
+            // normalize_components treats an empty list as "all components".
+            let required = if require.is_empty() {
+                Vec::new()
+            } else {
+                deploy::normalize_components(&require)?
+            };
+            let wait_for = if wait_for.is_empty() {
+                Vec::new()
+            } else {
+                deploy::normalize_components(&wait_for)?
+            };
+
+            if !wait_for.is_empty() {
+                let deadline = std::time::Instant::now() + std::time::Duration::from_secs(timeout);
+                loop {
+                    let missing = deploy::missing_components(
+                        &resolved_account_id,
+                        &token,
+                        &wait_for,
+                        name_prefix.as_deref(),
+                    )
+                    .await?;
+                    if missing.is_empty() {
+                        println!("Deployed: {}", wait_for.join(", "));
+                        break;
+                    }
+                    if std::time::Instant::now() >= deadline {
+                        eprintln!(
+                            "Timed out after {}s waiting for: {}",
+                            timeout,
+                            missing.join(", ")
+                        );
+                        std::process::exit(1);
+                    }
+                    println!("Waiting for: {}", missing.join(", "));
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                }
+            }
+
+            if let Some(interval) = watch {
+                return watch_deploy_status(
+                    &resolved_account_id,
+                    &token,
+                    &components,
+                    name_prefix.as_deref(),
+                    interval,
+                )
+                .await;
+            }
+
+            // Required components are always checked, even if not selected.
+            let mut components = components;
+            for component in &required {
+                if !components.contains(component) {
+                    components.push(component.clone());
+                }
+            }
+
             println!("Checking components: {}", components.join(", "));
-            deploy::print_deploy_status(&resolved_account_id, &token, &components).await
+            let missing = deploy::print_deploy_status(
+                &resolved_account_id,
+                &token,
+                &components,
+                name_prefix.as_deref(),
+            )
+            .await?;
+
+            let missing_required: Vec<&String> =
+                required.iter().filter(|c| missing.contains(c)).collect();
+            if !missing_required.is_empty() {
+                eprintln!(
+                    "Required component(s) not deployed: {}",
+                    missing_required
+                        .iter()
+                        .map(|c| c.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        DeployAction::Rollback {
+            component,
+            version_id,
+            limit,
+            name_prefix,
+            api_token,
+            account_id,
+        } => {
+            if let Some(prefix) = name_prefix.as_deref() {
+                deploy::validate_name_prefix(prefix)?;
+            }
+            let token = api_token
+                .or_else(|| cfg.cloudflare.api_token.clone())
+                .ok_or("Cloudflare API token missing. Set --api-token or `gsv local-config set cloudflare.api_token ...`")?;
+            let configured_account_id = account_id
+                .or_else(|| cfg.cloudflare.account_id.clone())
+                .filter(|v| !v.trim().is_empty());
+            let resolved_account_id =
+                deploy::resolve_cloudflare_account_id(&token, configured_account_id.as_deref())
+                    .await?;
+
+            let component =
+                deploy::normalize_components(std::slice::from_ref(&component))?.remove(0);
+            let script_name = deploy::deployed_script_name(
+                &resolved_account_id,
+                &token,
+                &component,
+                name_prefix.as_deref(),
+            )
+            .await?;
+            let versions = deploy::list_worker_script_versions(
+                &resolved_account_id,
+                &token,
+                &script_name,
+                limit.max(1),
+            )
+            .await?;
+            let active =
+                deploy::active_worker_script_version(&resolved_account_id, &token, &script_name)
+                    .await?;
+
+            println!("Recent versions of {}:", script_name);
+            for version in &versions {
+                println!(
+                    "  {}",
+                    format_worker_version(version, active.as_deref() == Some(&version.id))
+                );
+            }
+
+            let target = match version_id {
+                Some(requested) => resolve_rollback_version(&versions, None, Some(&requested))?,
+                None => {
+                    let previous = resolve_rollback_version(&versions, active.as_deref(), None)?;
+                    if can_prompt_interactively() {
+                        let mut prompt = select("Version to activate").initial_value(previous);
+                        for version in &versions {
+                            let label = format_worker_version(
+                                version,
+                                active.as_deref() == Some(&version.id),
+                            );
+                            prompt = prompt.item(version.id.clone(), label, "");
+                        }
+                        prompt.interact()?
+                    } else {
+                        previous
+                    }
+                }
+            };
+
+            if active.as_deref() == Some(target.as_str()) {
+                println!("Version {} is already active; nothing to do.", target);
+                return Ok(());
+            }
+            deploy::activate_worker_script_version(
+                &resolved_account_id,
+                &token,
+                &script_name,
+                &target,
+            )
+            .await?;
+            println!("Activated version {} for {}", target, script_name);
+            Ok(())
         }
         DeployAction::Bundle { action } => match action {
             DeployBundleAction::Fetch {
@@ -1802,6 +3692,7 @@ async fn run_deploy(
                 all,
                 force,
                 from_dir,
+                parallel,
             } => {
                 if all && !component.is_empty() {
                     return Err("Use either --all or one/more --component values, not both".into());
@@ -1821,12 +3712,16 @@ async fn run_deploy(
                     println!("Installing bundles from local directory: {}", dir.display());
                     deploy::install_bundles_from_dir(cfg, &dir, &version, &components, force)
                 } else {
-                    deploy::fetch_bundles(cfg, &version, &components, force).await
+                    deploy::fetch_bundles(cfg, &version, &components, force, parallel).await
                 }
             }
             DeployBundleAction::Inspect { version, component } => {
                 deploy::inspect_bundle(cfg, &version, &component).await
             }
+            DeployBundleAction::List => deploy::list_cached_bundles(cfg),
+            DeployBundleAction::Prune { keep, dry_run } => {
+                deploy::prune_cached_bundles(cfg, keep, dry_run)
+            }
             DeployBundleAction::ListComponents => {
                 println!("Available components:");
                 for component in deploy::available_components() {
@@ -1865,27 +3760,276 @@ async fn run_deploy(
     }
 }
 
-#[cfg(target_os = "linux")]
-const NODE_SYSTEMD_UNIT_NAME: &str = "gsv-node.service";
-#[cfg(target_os = "macos")]
-const NODE_LAUNCHD_LABEL: &str = "dev.gsv.node";
+#[cfg(target_os = "linux")]
+const NODE_SYSTEMD_UNIT_NAME: &str = "gsv-node.service";
+#[cfg(target_os = "macos")]
+const NODE_LAUNCHD_LABEL: &str = "dev.gsv.node";
+#[cfg(target_os = "windows")]
+const NODE_WINDOWS_TASK_NAME: &str = "\\GSV\\Node";
+#[cfg(target_os = "windows")]
+const NODE_WINDOWS_TASK_TRIGGER: &str = "at logon";
+/// Task Scheduler's minimum restart interval, and its maximum restart count.
+#[cfg(any(target_os = "windows", test))]
+const NODE_WINDOWS_TASK_RESTART_INTERVAL: &str = "PT1M";
+#[cfg(any(target_os = "windows", test))]
+const NODE_WINDOWS_TASK_RESTART_COUNT: u32 = 999;
+#[cfg(target_os = "windows")]
+const NODE_WINDOWS_SERVICE_NAME: &str = "gsv-node";
+#[cfg(target_os = "windows")]
+const NODE_WINDOWS_SERVICE_DISPLAY_NAME: &str = "GSV Node";
+
+use gsv::logger::{self, NodeLogger};
+
+fn node_logs_file(
+    lines: usize,
+    follow: bool,
+    from_rotated: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let base_path = logger::node_log_path()?;
+    let log_path = if from_rotated == 0 {
+        base_path.clone()
+    } else {
+        logger::rotated_log_path(&base_path, from_rotated)
+    };
+    if !log_path.exists() {
+        if from_rotated > 0 {
+            print_available_rotated_logs(&base_path);
+        }
+        return Err(format!("Log file not found: {}", log_path.display()).into());
+    }
+
+    let mut cmd = std::process::Command::new("tail");
+    cmd.arg("-n").arg(lines.to_string());
+    if follow {
+        cmd.arg("-F");
+    }
+    cmd.arg(&log_path);
+
+    run_command_passthrough(&mut cmd, "Failed to read node log file")
+}
+
+/// `node logs` without the system `tail` binary: prints the last `lines`
+/// lines, then with `follow` polls the file for appended data.
+fn node_logs_builtin(lines: usize, follow: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let (recent, _) = read_recent_node_log_lines(lines)?;
+    let mut stdout = io::stdout();
+    for line in &recent {
+        writeln!(stdout, "{}", line)?;
+    }
+    stdout.flush()?;
+
+    if !follow {
+        return Ok(());
+    }
+
+    let path = logger::node_log_path()?;
+    let mut file = fs::File::open(&path)?;
+    let mut position = seek_to_end(&mut file)?;
+    let mut pending: Vec<u8> = Vec::new();
+    loop {
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        // Rotation replaces the file, truncation shrinks it; start over either way.
+        let current_len = fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+        if current_len < position {
+            file = match fs::File::open(&path) {
+                Ok(file) => file,
+                Err(_) => continue,
+            };
+            position = 0;
+            pending.clear();
+        }
+
+        let read = io::Read::read_to_end(&mut file, &mut pending)?;
+        if read == 0 {
+            continue;
+        }
+        position += read as u64;
+
+        // Hold back a trailing partial line until its newline arrives.
+        if let Some(end) = pending.iter().rposition(|&b| b == b'\n') {
+            stdout.write_all(&pending[..=end])?;
+            stdout.flush()?;
+            pending.drain(..=end);
+        }
+    }
+}
+
+/// `node logs --json`: prints the last `lines` entries as a coloured
+/// ts/level/event/error table (or compact JSON with `passthrough`), then with
+/// `follow` watches the log file for appended lines.
+fn node_logs_json(
+    lines: usize,
+    follow: bool,
+    passthrough: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use notify::{EventKind, RecursiveMode, Watcher};
+
+    let choice = if io::stdout().is_terminal() {
+        ColorChoice::Auto
+    } else {
+        ColorChoice::Never
+    };
+    let mut stdout = StandardStream::stdout(choice);
+    if !passthrough {
+        writeln!(
+            stdout,
+            "{:<24}  {:<5}  {:<28}  ERROR",
+            "TS", "LEVEL", "EVENT"
+        )?;
+    }
+
+    let (recent, _) = read_recent_node_log_lines(lines)?;
+    for line in &recent {
+        write_node_log_json_line(&mut stdout, line, passthrough)?;
+    }
+    stdout.flush()?;
+
+    if !follow {
+        return Ok(());
+    }
+
+    let path = logger::node_log_path()?;
+    let watch_dir = path
+        .parent()
+        .ok_or("Node log path has no parent directory")?;
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    // Watch the directory so rotation (rename + recreate) is seen too.
+    watcher.watch(watch_dir, RecursiveMode::NonRecursive)?;
+
+    let mut file = fs::File::open(&path)?;
+    let mut position = seek_to_end(&mut file)?;
+    let mut pending: Vec<u8> = Vec::new();
+    for event in rx {
+        let event = event?;
+        if !event.paths.iter().any(|p| p == &path) {
+            continue;
+        }
+
+        let current_len = fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+        if matches!(event.kind, EventKind::Create(_)) || current_len < position {
+            file = match fs::File::open(&path) {
+                Ok(file) => file,
+                Err(_) => continue,
+            };
+            position = 0;
+            pending.clear();
+        }
+
+        position += io::Read::read_to_end(&mut file, &mut pending)? as u64;
+        while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line[..end]);
+            write_node_log_json_line(&mut stdout, line.trim_end_matches('\r'), passthrough)?;
+        }
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+/// Writes one node log line as a table row, colouring the level. Lines that
+/// are not JSON objects are written as-is (or skipped with `passthrough`).
+fn write_node_log_json_line(
+    out: &mut impl WriteColor,
+    line: &str,
+    passthrough: bool,
+) -> io::Result<()> {
+    let entry = serde_json::from_str::<serde_json::Value>(line)
+        .ok()
+        .filter(|entry| entry.is_object());
+    let Some(entry) = entry else {
+        if !passthrough && !line.trim().is_empty() {
+            writeln!(out, "{}", line)?;
+        }
+        return Ok(());
+    };
+    if passthrough {
+        return writeln!(out, "{}", entry);
+    }
 
-use gsv::logger::{self, NodeLogger};
+    let field = |key: &str| match entry.get(key) {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(serde_json::Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    };
+    let level = field("level");
+    let level_color = match level.as_str() {
+        "ERROR" => Some(Color::Red),
+        "WARN" => Some(Color::Yellow),
+        "INFO" => Some(Color::Green),
+        "DEBUG" => Some(Color::Cyan),
+        _ => None,
+    };
 
-fn node_logs_file(lines: usize, follow: bool) -> Result<(), Box<dyn std::error::Error>> {
-    let log_path = logger::node_log_path()?;
-    if !log_path.exists() {
-        return Err(format!("Log file not found: {}", log_path.display()).into());
+    write!(out, "{:<24}  ", field("ts"))?;
+    out.set_color(ColorSpec::new().set_fg(level_color).set_bold(true))?;
+    write!(out, "{:<5}", level)?;
+    out.reset()?;
+    let error = field("error");
+    if error.is_empty() {
+        write!(out, "  {}", field("event"))?;
+    } else {
+        write!(out, "  {:<28}  ", field("event"))?;
+        out.set_color(ColorSpec::new().set_fg(Some(Color::Red)))?;
+        write!(out, "{}", error)?;
+        out.reset()?;
     }
+    writeln!(out)
+}
 
-    let mut cmd = std::process::Command::new("tail");
-    cmd.arg("-n").arg(lines.to_string());
-    if follow {
-        cmd.arg("-F");
+fn node_logs_for_tool(lines: usize, tool: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let (matches, truncated) =
+        read_node_log_lines_filtered(lines, |line| log_line_matches_tool(line, tool))?;
+    println!(
+        "Filter: tool == {:?} ({} line(s){})\n",
+        tool,
+        matches.len(),
+        if truncated {
+            ", older matches omitted"
+        } else {
+            ""
+        }
+    );
+    for line in matches {
+        println!("{}", line);
     }
-    cmd.arg(&log_path);
+    Ok(())
+}
 
-    run_command_passthrough(&mut cmd, "Failed to read node log file")
+fn log_line_matches_tool(line: &str, tool: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(line)
+        .ok()
+        .and_then(|entry| {
+            entry
+                .get("tool")
+                .and_then(|t| t.as_str())
+                .map(|t| t == tool)
+        })
+        .unwrap_or(false)
+}
+
+fn seek_to_end(file: &mut fs::File) -> io::Result<u64> {
+    io::Seek::seek(file, io::SeekFrom::End(0))
+}
+
+fn print_available_rotated_logs(base_path: &Path) {
+    let available: Vec<(usize, u64)> = (1..=logger::node_log_max_files())
+        .filter_map(|index| {
+            fs::metadata(logger::rotated_log_path(base_path, index))
+                .ok()
+                .map(|meta| (index, meta.len()))
+        })
+        .collect();
+
+    if available.is_empty() {
+        eprintln!("No rotated log files next to {}", base_path.display());
+        return;
+    }
+    eprintln!("Available rotated logs:");
+    for (index, size) in available {
+        eprintln!("  {}  {} bytes", index, size);
+    }
 }
 
 const DEFAULT_NODE_LOG_GET_LINES: usize = 100;
@@ -1895,11 +4039,19 @@ const MAX_NODE_EXEC_EVENT_OUTBOX: usize = 2048;
 fn resolve_logs_get_line_limit(lines: Option<usize>) -> usize {
     lines
         .unwrap_or(DEFAULT_NODE_LOG_GET_LINES)
-        .max(1)
-        .min(MAX_NODE_LOG_GET_LINES)
+        .clamp(1, MAX_NODE_LOG_GET_LINES)
 }
 
 fn read_recent_node_log_lines(limit: usize) -> Result<(Vec<String>, bool), String> {
+    read_node_log_lines_filtered(limit, |_| true)
+}
+
+/// Return the last `limit` log lines accepted by `predicate`; the flag reports
+/// whether older matching lines were dropped.
+fn read_node_log_lines_filtered(
+    limit: usize,
+    predicate: impl Fn(&str) -> bool,
+) -> Result<(Vec<String>, bool), String> {
     let path = logger::node_log_path().map_err(|e| format!("Failed to resolve log path: {}", e))?;
     let file =
         fs::File::open(&path).map_err(|e| format!("Failed to open '{}': {}", path.display(), e))?;
@@ -1910,6 +4062,9 @@ fn read_recent_node_log_lines(limit: usize) -> Result<(Vec<String>, bool), Strin
 
     for line in reader.lines() {
         let line = line.map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+        if !predicate(&line) {
+            continue;
+        }
         total_lines += 1;
 
         if recent.len() == limit {
@@ -1987,6 +4142,83 @@ fn persist_node_defaults(
     Ok((node_id, workspace, changed))
 }
 
+/// Saves `node install --log-dir` as an absolute path in local config,
+/// creating the directory so the daemon can write to it.
+fn persist_node_log_dir(log_dir: PathBuf) -> Result<bool, Box<dyn std::error::Error>> {
+    let log_dir = if log_dir.is_absolute() {
+        log_dir
+    } else {
+        std::env::current_dir()?.join(log_dir)
+    };
+    std::fs::create_dir_all(&log_dir)
+        .map_err(|e| format!("Failed to create log dir {}: {}", log_dir.display(), e))?;
+    let log_dir = log_dir.canonicalize().unwrap_or(log_dir);
+
+    let mut local_cfg = CliConfig::load();
+    if local_cfg.node.log_dir.as_ref() == Some(&log_dir) {
+        return Ok(false);
+    }
+    local_cfg.node.log_dir = Some(log_dir);
+    local_cfg.save()?;
+    Ok(true)
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct WorkspaceSummary {
+    dirs: u64,
+    files: u64,
+    bytes: u64,
+}
+
+/// Appends a `tree`-style listing of `dir` down to `max_depth` levels to `out`.
+/// Deeper entries are not printed but still counted in `summary`; symlinks are
+/// not followed and unreadable subdirectories are skipped.
+fn render_workspace_tree(
+    dir: &Path,
+    prefix: &str,
+    depth: usize,
+    max_depth: usize,
+    out: &mut String,
+    summary: &mut WorkspaceSummary,
+) -> std::io::Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.filter_map(Result::ok).collect();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    let count = entries.len();
+    for (index, entry) in entries.into_iter().enumerate() {
+        let last = index + 1 == count;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let branch = if last { "└── " } else { "├── " };
+        let visible = depth < max_depth;
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            summary.dirs += 1;
+            if visible {
+                out.push_str(&format!("{}{}{}/\n", prefix, branch, name));
+            }
+            let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            let _ = render_workspace_tree(
+                &entry.path(),
+                &child_prefix,
+                depth + 1,
+                max_depth,
+                out,
+                summary,
+            );
+        } else {
+            summary.files += 1;
+            summary.bytes += entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+            if visible {
+                out.push_str(&format!("{}{}{}\n", prefix, branch, name));
+            }
+        }
+    }
+    Ok(())
+}
+
 fn persist_gateway_overrides(
     gateway_url_override: Option<&str>,
     gateway_token_override: Option<&str>,
@@ -2035,6 +4267,49 @@ fn persist_cloudflare_account_id(account_id: &str) -> Result<bool, Box<dyn std::
     Ok(true)
 }
 
+const NODE_EXTRA_ARG_FLAGS: &[&str] = &["--debug", "--quiet"];
+const NODE_EXTRA_ARG_LOG_FORMATS: &[&str] = &["json", "text"];
+
+/// Validate `node.extra_args` against the daemon flag whitelist and split it into
+/// individual argv tokens (accepts "--log-format text", "--log-format=text", or
+/// "--log-format" followed by "text").
+fn validate_node_extra_args(extra_args: &[String]) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut raw = extra_args
+        .iter()
+        .flat_map(|arg| arg.split_whitespace())
+        .flat_map(|arg| match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => vec![flag, value],
+            _ => vec![arg],
+        });
+
+    while let Some(arg) = raw.next() {
+        if NODE_EXTRA_ARG_FLAGS.contains(&arg) {
+            tokens.push(arg.to_string());
+        } else if arg == "--log-format" {
+            let value = raw
+                .next()
+                .ok_or("node.extra_args: --log-format requires a value (json or text)")?;
+            if !NODE_EXTRA_ARG_LOG_FORMATS.contains(&value) {
+                return Err(format!(
+                    "node.extra_args: invalid --log-format '{}' (expected json or text)",
+                    value
+                ));
+            }
+            tokens.push(arg.to_string());
+            tokens.push(value.to_string());
+        } else {
+            return Err(format!(
+                "node.extra_args: '{}' is not allowed. Allowed flags: {}, --log-format <json|text>",
+                arg,
+                NODE_EXTRA_ARG_FLAGS.join(", ")
+            ));
+        }
+    }
+
+    Ok(tokens)
+}
+
 fn node_service_is_installed() -> Result<bool, Box<dyn std::error::Error>> {
     #[cfg(target_os = "linux")]
     {
@@ -2046,26 +4321,231 @@ fn node_service_is_installed() -> Result<bool, Box<dyn std::error::Error>> {
         return Ok(launchd_plist_path()?.exists());
     }
 
-    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    #[cfg(target_os = "windows")]
+    {
+        return Ok(windows_service_exists()? || windows_task_exists()?);
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
     {
-        Err("node daemon management is currently supported on macOS and Linux only".into())
+        Err("node daemon management is currently supported on macOS, Linux and Windows only".into())
+    }
+}
+
+#[derive(Debug, Default, serde::Serialize)]
+struct NodeServiceStatus {
+    installed: bool,
+    running: bool,
+    pid: Option<u32>,
+    uptime_seconds: Option<u64>,
+    last_exit_code: Option<i32>,
+    /// Times the service manager restarted the daemon (systemd, launchd)
+    restarts: Option<u32>,
+    /// Raw service state, e.g. "active (running)" on systemd
+    state: Option<String>,
+    /// Operating system the status was read on ("linux", "macos", "windows")
+    platform: &'static str,
+}
+
+fn node_service_status() -> Result<NodeServiceStatus, Box<dyn std::error::Error>> {
+    let mut status = NodeServiceStatus {
+        installed: node_service_is_installed()?,
+        platform: std::env::consts::OS,
+        ..Default::default()
+    };
+    if !status.installed {
+        return Ok(status);
+    }
+
+    #[cfg(target_os = "linux")]
+    systemd_fill_service_status(&mut status)?;
+
+    #[cfg(target_os = "macos")]
+    launchd_fill_service_status(&mut status)?;
+
+    #[cfg(target_os = "windows")]
+    if windows_service_exists()? {
+        windows_fill_service_status(&mut status)?;
+    } else {
+        windows_fill_task_status(&mut status)?;
+    }
+
+    if let Some(pid) = status.pid {
+        status.uptime_seconds = process_elapsed_seconds(pid);
+    }
+    Ok(status)
+}
+
+const NODE_STATUS_WATCH_LOG_LINES: usize = 5;
+
+/// Redraw the daemon status block every `interval` seconds until Ctrl+C.
+fn watch_node_status(interval: u64) -> Result<(), Box<dyn std::error::Error>> {
+    let interval = std::time::Duration::from_secs(interval.max(1));
+    loop {
+        let status = node_service_status()?;
+        let log_lines = read_recent_node_log_lines(NODE_STATUS_WATCH_LOG_LINES)
+            .map(|(lines, _)| lines)
+            .unwrap_or_default();
+
+        print!("\x1B[2J\x1B[H");
+        print!("{}", render_node_status_block(&status, &log_lines));
+        println!(
+            "\nRefreshing every {}s ({}). Press Ctrl+C to stop.",
+            interval.as_secs(),
+            chrono::Local::now().format("%H:%M:%S")
+        );
+        io::stdout().flush()?;
+
+        std::thread::sleep(interval);
+    }
+}
+
+/// Redraw `deploy status` every `interval` seconds until q or Ctrl+C.
+/// Cloudflare API errors are shown and retried on the next refresh.
+async fn watch_deploy_status(
+    account_id: &str,
+    api_token: &str,
+    components: &[String],
+    name_prefix: Option<&str>,
+    interval: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (quit_tx, mut quit_rx) = tokio::sync::mpsc::unbounded_channel();
+    let term = console::Term::stdout();
+    let keys_enabled = term.is_term() && io::stdin().is_terminal();
+    if keys_enabled {
+        // read_key blocks, so it gets a detached thread rather than a tokio task.
+        // On Ctrl+C it restores the terminal and raises SIGINT for ctrl_c() below.
+        std::thread::spawn(move || loop {
+            match term.read_key() {
+                Ok(console::Key::Char('q' | 'Q')) => {
+                    let _ = quit_tx.send(());
+                    break;
+                }
+                Ok(_) => {}
+                Err(_) => break,
+            }
+        });
+    }
+
+    loop {
+        print!("\x1B[2J\x1B[H");
+        println!("Cloudflare account ID: {}", account_id);
+        if let Err(error) =
+            deploy::print_deploy_status(account_id, api_token, components, name_prefix).await
+        {
+            println!("\nError: {}", error);
+        }
+        println!(
+            "\nRefreshing every {}s ({}). Press {}Ctrl+C to stop.",
+            interval,
+            chrono::Local::now().format("%H:%M:%S"),
+            if keys_enabled { "q or " } else { "" }
+        );
+        io::stdout().flush()?;
+
+        // recv() yields None once the key thread is gone (no TTY, or read_key
+        // failed); only an actual `q` ends the watch.
+        tokio::select! {
+            _ = tokio::time::sleep(std::time::Duration::from_secs(interval)) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+            Some(()) = quit_rx.recv() => return Ok(()),
+        }
+    }
+}
+
+fn render_node_status_block(status: &NodeServiceStatus, log_lines: &[String]) -> String {
+    let mut out = String::new();
+    let state = if !status.installed {
+        "not installed"
+    } else if status.running {
+        "running"
+    } else {
+        "stopped"
+    };
+    out.push_str(&format!("Node daemon: {}\n", state));
+    if let Some(pid) = status.pid {
+        out.push_str(&format!("  PID: {}\n", pid));
+    }
+    if let Some(uptime) = status.uptime_seconds {
+        out.push_str(&format!(
+            "  Uptime: {}h {}m {}s\n",
+            uptime / 3600,
+            (uptime % 3600) / 60,
+            uptime % 60
+        ));
+    }
+    if let Some(code) = status.last_exit_code {
+        out.push_str(&format!("  Last exit code: {}\n", code));
+    }
+    if let Some(restarts) = status.restarts.filter(|restarts| *restarts > 0) {
+        out.push_str(&format!("  Restarts: {}\n", restarts));
+    }
+    if !log_lines.is_empty() {
+        out.push_str("\nRecent logs:\n");
+        for line in log_lines {
+            out.push_str(&format!("  {}\n", line));
+        }
+    }
+    out
+}
+
+/// Elapsed run time of a process via `ps -o etime=` (portable across Linux and macOS).
+fn process_elapsed_seconds(pid: u32) -> Option<u64> {
+    let output = std::process::Command::new("ps")
+        .arg("-o")
+        .arg("etime=")
+        .arg("-p")
+        .arg(pid.to_string())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
     }
+    parse_ps_elapsed(String::from_utf8_lossy(&output.stdout).trim())
+}
+
+/// Parse `ps` elapsed time in `[[dd-]hh:]mm:ss` form.
+fn parse_ps_elapsed(value: &str) -> Option<u64> {
+    let (days, clock) = match value.split_once('-') {
+        Some((days, clock)) => (days.parse::<u64>().ok()?, clock),
+        None => (0, value),
+    };
+    let parts = clock
+        .split(':')
+        .map(|part| part.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [m, s] => (0, *m, *s),
+        [h, m, s] => (*h, *m, *s),
+        _ => return None,
+    };
+    Some(((days * 24 + hours) * 60 + minutes) * 60 + seconds)
 }
 
 fn restart_node_service() -> Result<(), Box<dyn std::error::Error>> {
     #[cfg(target_os = "linux")]
     {
-        return systemd_restart_service();
+        systemd_restart_service()
     }
 
     #[cfg(target_os = "macos")]
     {
-        return launchd_start_service();
+        launchd_start_service()
     }
 
-    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    #[cfg(target_os = "windows")]
     {
-        Err("node daemon management is currently supported on macOS and Linux only".into())
+        if windows_service_exists()? {
+            let _ = stop_windows_service();
+            return start_windows_service();
+        }
+        let _ = windows_end_task();
+        windows_run_task()
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    {
+        Err("node daemon management is currently supported on macOS, Linux and Windows only".into())
     }
 }
 
@@ -2073,10 +4553,14 @@ fn run_node_default_managed(
     cfg: &CliConfig,
     node_id: Option<String>,
     workspace: Option<PathBuf>,
+    health_addr: Option<std::net::SocketAddr>,
     gateway_url_override: Option<&str>,
     gateway_token_override: Option<&str>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     if node_service_is_installed()? {
+        if health_addr.is_some() {
+            return Err("The node service is already installed; use `gsv node install --health-port ...` to change its health endpoint, or --foreground to run without it".into());
+        }
         let gateway_overrides_changed =
             persist_gateway_overrides(gateway_url_override, gateway_token_override)?;
         let (node_id, workspace, node_defaults_changed) =
@@ -2084,7 +4568,7 @@ fn run_node_default_managed(
         if gateway_overrides_changed || node_defaults_changed {
             restart_node_service()?;
         } else {
-            run_node_service(NodeAction::Start, cfg, None, None)?;
+            run_node_service(NodeAction::Start, cfg, None, None, OutputFormat::Text)?;
         }
         if gateway_overrides_changed {
             println!("Saved gateway connection overrides to local config.");
@@ -2099,10 +4583,17 @@ fn run_node_default_managed(
             NodeAction::Install {
                 id: node_id,
                 workspace,
+                no_start: false,
+                log_dir: None,
+                health_port: health_addr.map(|addr| addr.port()),
+                health_host: health_addr.map(|addr| addr.ip()),
+                env: Vec::new(),
+                windows_service: false,
             },
             cfg,
             gateway_url_override,
             gateway_token_override,
+            OutputFormat::Text,
         )?;
     }
 
@@ -2114,35 +4605,97 @@ fn run_node_service(
     cfg: &CliConfig,
     gateway_url_override: Option<&str>,
     gateway_token_override: Option<&str>,
+    output: OutputFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
     match action {
-        NodeAction::Install { id, workspace } => {
+        NodeAction::Install {
+            id,
+            workspace,
+            no_start,
+            log_dir,
+            health_port,
+            health_host,
+            env,
+            windows_service,
+        } => {
+            let service_env = parse_service_env(&env)?;
+            #[cfg(target_os = "windows")]
+            if !service_env.is_empty() {
+                return Err(
+                    "--env is not supported for the Windows scheduled task or service".into(),
+                );
+            }
+            #[cfg(not(target_os = "windows"))]
+            if windows_service {
+                return Err("--windows-service is only supported on Windows".into());
+            }
             let gateway_overrides_changed =
                 persist_gateway_overrides(gateway_url_override, gateway_token_override)?;
             let (node_id, workspace, node_defaults_changed) =
                 persist_node_defaults(cfg, id, workspace)?;
+            let log_dir_changed = match log_dir {
+                Some(dir) => persist_node_log_dir(dir)?,
+                None => false,
+            };
+            // The Windows task and service have no environment block; --env is rejected above.
+            let log_dir = CliConfig::load().node.log_dir;
+            #[cfg(not(target_os = "windows"))]
+            let environment: Vec<(String, String)> = log_dir
+                .iter()
+                .map(|dir| {
+                    (
+                        logger::NODE_LOG_DIR_ENV.to_string(),
+                        dir.display().to_string(),
+                    )
+                })
+                .chain(service_env)
+                .collect();
+
+            let mut extra_args = validate_node_extra_args(&cfg.node.extra_args)?;
+            // The daemon must read the same profile it was installed from.
+            if let Some(profile) = config::active_profile() {
+                extra_args.extend(["--profile".to_string(), profile.to_string()]);
+            }
+            if let Some(port) = health_port {
+                extra_args.extend(["--health-port".to_string(), port.to_string()]);
+            }
+            if let Some(host) = health_host {
+                extra_args.extend(["--health-host".to_string(), host.to_string()]);
+            }
 
             let exe_path = std::env::current_exe()?;
             let exe_path = exe_path.canonicalize().unwrap_or(exe_path);
 
             #[cfg(target_os = "linux")]
-            install_systemd_user_service(&exe_path)?;
+            install_systemd_user_service(&exe_path, &extra_args, &environment, !no_start)?;
 
             #[cfg(target_os = "macos")]
-            install_launchd_user_service(&exe_path)?;
+            install_launchd_user_service(&exe_path, &extra_args, &environment, !no_start)?;
+
+            #[cfg(target_os = "windows")]
+            if windows_service {
+                install_windows_service(&exe_path, &extra_args, !no_start)?;
+            } else if windows_service_exists()? {
+                return Err("The node is installed as a Windows service; rerun with --windows-service, or run 'gsv node uninstall' from an elevated terminal first".into());
+            } else {
+                install_windows_scheduled_task(&exe_path, &extra_args, !no_start)?;
+            }
 
-            #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+            #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
             {
                 return Err(
-                    "node daemon management is currently supported on macOS and Linux only".into(),
+                    "node daemon management is currently supported on macOS, Linux and Windows only".into(),
                 );
             }
 
-            if gateway_overrides_changed || node_defaults_changed {
-                restart_node_service()?;
+            if no_start {
+                println!("Node daemon installed (not started). Run 'gsv node start' when ready.");
+            } else {
+                if gateway_overrides_changed || node_defaults_changed || log_dir_changed {
+                    restart_node_service()?;
+                }
+                println!("Node daemon installed and started.");
             }
-
-            println!("Node daemon installed and started.");
             if gateway_overrides_changed {
                 println!("Saved gateway connection overrides to local config.");
             }
@@ -2151,6 +4704,9 @@ fn run_node_service(
                 node_id,
                 workspace.display()
             );
+            if let Some(dir) = log_dir.as_deref() {
+                println!("Saved node.log_dir={}", dir.display());
+            }
             println!("\nCheck status:");
             println!("  gsv node status");
             println!("View logs:");
@@ -2163,10 +4719,16 @@ fn run_node_service(
             #[cfg(target_os = "macos")]
             uninstall_launchd_user_service()?;
 
-            #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+            #[cfg(target_os = "windows")]
+            {
+                uninstall_windows_service()?;
+                uninstall_windows_scheduled_task()?;
+            }
+
+            #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
             {
                 return Err(
-                    "node daemon management is currently supported on macOS and Linux only".into(),
+                    "node daemon management is currently supported on macOS, Linux and Windows only".into(),
                 );
             }
 
@@ -2189,10 +4751,17 @@ fn run_node_service(
             #[cfg(target_os = "macos")]
             launchd_start_service()?;
 
-            #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+            #[cfg(target_os = "windows")]
+            if windows_service_exists()? {
+                start_windows_service()?;
+            } else {
+                windows_run_task()?;
+            }
+
+            #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
             {
                 return Err(
-                    "node daemon management is currently supported on macOS and Linux only".into(),
+                    "node daemon management is currently supported on macOS, Linux and Windows only".into(),
                 );
             }
 
@@ -2205,31 +4774,121 @@ fn run_node_service(
             #[cfg(target_os = "macos")]
             launchd_stop_service()?;
 
-            #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+            #[cfg(target_os = "windows")]
+            if windows_service_exists()? {
+                stop_windows_service()?;
+            } else {
+                windows_end_task()?;
+            }
+
+            #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
             {
                 return Err(
-                    "node daemon management is currently supported on macOS and Linux only".into(),
+                    "node daemon management is currently supported on macOS, Linux and Windows only".into(),
                 );
             }
 
             println!("Node daemon stopped.");
         }
-        NodeAction::Status => {
+        NodeAction::Status {
+            watch: true,
+            interval,
+            ..
+        } => {
+            watch_node_status(interval)?;
+        }
+        NodeAction::Status { json: true, .. } => {
+            let status = node_service_status()?;
+            println!("{}", serde_json::to_string_pretty(&status)?);
+            let exit_code = if !status.installed {
+                2
+            } else if !status.running {
+                1
+            } else {
+                0
+            };
+            if exit_code != 0 {
+                std::process::exit(exit_code);
+            }
+        }
+        NodeAction::Status { json: false, .. } => {
             #[cfg(target_os = "linux")]
             systemd_status_service()?;
 
             #[cfg(target_os = "macos")]
             launchd_status_service()?;
 
-            #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+            #[cfg(target_os = "windows")]
+            if windows_service_exists()? {
+                status_windows_service()?;
+            } else {
+                windows_task_status()?;
+            }
+
+            #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
             {
                 return Err(
-                    "node daemon management is currently supported on macOS and Linux only".into(),
+                    "node daemon management is currently supported on macOS, Linux and Windows only".into(),
                 );
             }
         }
-        NodeAction::Logs { lines, follow } => {
-            node_logs_file(lines, follow)?;
+        NodeAction::Logs {
+            lines,
+            follow,
+            from_rotated,
+            no_system_tail,
+            tool,
+            json,
+        } => {
+            if json {
+                node_logs_json(lines, follow, output == OutputFormat::Json)?;
+            } else if let Some(tool) = tool {
+                node_logs_for_tool(lines, &tool)?;
+            } else if no_system_tail {
+                node_logs_builtin(lines, follow)?;
+            } else {
+                node_logs_file(lines, follow, from_rotated)?;
+            }
+        }
+        NodeAction::Workspace {
+            action: WorkspaceAction::Show { depth },
+        } => {
+            let workspace =
+                cfg.node.workspace.clone().ok_or(
+                    "node.workspace is not set. Set it with: gsv node workspace set <path>",
+                )?;
+            if !workspace.is_dir() {
+                return Err(format!("Workspace {} is not a directory", workspace.display()).into());
+            }
+            let mut tree = String::new();
+            let mut summary = WorkspaceSummary::default();
+            render_workspace_tree(&workspace, "", 0, depth, &mut tree, &mut summary)?;
+            println!("{}/", workspace.display());
+            print!("{}", tree);
+            println!();
+            println!(
+                "{} directories, {} files, {}",
+                summary.dirs,
+                summary.files,
+                deploy::format_byte_size(summary.bytes)
+            );
+        }
+        NodeAction::Workspace {
+            action: WorkspaceAction::Set { path },
+        } => {
+            let workspace = path
+                .canonicalize()
+                .map_err(|e| format!("Workspace {} does not exist: {}", path.display(), e))?;
+            if !workspace.is_dir() {
+                return Err(format!("Workspace {} is not a directory", workspace.display()).into());
+            }
+            let mut local_cfg = CliConfig::load();
+            local_cfg.node.workspace = Some(workspace.clone());
+            local_cfg.save()?;
+            println!("Set node.workspace = {}", workspace.display());
+            if node_service_status().is_ok_and(|status| status.running) {
+                println!("Restart the node daemon to use it: gsv node stop && gsv node start");
+            }
         }
     }
 
@@ -2302,17 +4961,74 @@ fn try_enable_linger() -> Result<(), Box<dyn std::error::Error>> {
     }
 }
 
+/// Parses `node install --env KEY=VALUE` entries. Keys must be valid
+/// environment variable names; values may not span lines.
+fn parse_service_env(entries: &[String]) -> Result<Vec<(String, String)>, String> {
+    entries
+        .iter()
+        .map(|entry| {
+            let (key, value) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("--env '{}': expected KEY=VALUE", entry))?;
+            let valid_key = key
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid_key {
+                return Err(format!(
+                    "--env '{}': '{}' is not a valid variable name (letters, digits and _)",
+                    entry, key
+                ));
+            }
+            if value.contains(['\n', '\r']) {
+                return Err(format!("--env {}: value must be a single line", key));
+            }
+            Ok((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// `Environment=` lines for the systemd unit, quoted so values may contain spaces.
+#[cfg(any(target_os = "linux", test))]
+fn systemd_environment_lines(environment: &[(String, String)]) -> String {
+    environment
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "Environment=\"{}={}\"\n",
+                key,
+                value
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('%', "%%")
+            )
+        })
+        .collect()
+}
+
 #[cfg(target_os = "linux")]
-fn install_systemd_user_service(exe_path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+fn install_systemd_user_service(
+    exe_path: &Path,
+    extra_args: &[String],
+    environment: &[(String, String)],
+    start: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     let unit_path = systemd_user_unit_path()?;
     if let Some(parent) = unit_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
 
     let exe_path = exe_path.display().to_string().replace('"', "\\\"");
+    let environment = systemd_environment_lines(environment);
     let unit = format!(
-        "[Unit]\nDescription=GSV Node daemon\nAfter=network-online.target\nWants=network-online.target\n\n[Service]\nType=simple\nExecStart=\"{}\" node --foreground\nRestart=always\nRestartSec=3\nKillSignal=SIGTERM\n\n[Install]\nWantedBy=default.target\n",
-        exe_path
+        "[Unit]\nDescription=GSV Node daemon\nAfter=network-online.target\nWants=network-online.target\n\n[Service]\nType=simple\n{}ExecStart=\"{}\" node --foreground{}\nRestart=always\nRestartSec=3\nKillSignal=SIGTERM\n\n[Install]\nWantedBy=default.target\n",
+        environment,
+        exe_path,
+        extra_args
+            .iter()
+            .map(|arg| format!(" {}", arg))
+            .collect::<String>()
     );
     std::fs::write(&unit_path, unit)?;
 
@@ -2322,12 +5038,13 @@ fn install_systemd_user_service(exe_path: &PathBuf) -> Result<(), Box<dyn std::e
             .arg("daemon-reload"),
         "Failed to reload systemd user daemon",
     )?;
+    let mut enable = std::process::Command::new("systemctl");
+    enable.arg("--user").arg("enable");
+    if start {
+        enable.arg("--now");
+    }
     run_command_capture(
-        std::process::Command::new("systemctl")
-            .arg("--user")
-            .arg("enable")
-            .arg("--now")
-            .arg(NODE_SYSTEMD_UNIT_NAME),
+        enable.arg(NODE_SYSTEMD_UNIT_NAME),
         "Failed to enable/start node service",
     )?;
 
@@ -2419,6 +5136,51 @@ fn systemd_stop_service() -> Result<(), Box<dyn std::error::Error>> {
     )
 }
 
+#[cfg(target_os = "linux")]
+fn systemd_fill_service_status(
+    status: &mut NodeServiceStatus,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = std::process::Command::new("systemctl")
+        .arg("--user")
+        .arg("show")
+        .arg("--property=ActiveState,SubState,MainPID,ExecMainStatus,NRestarts")
+        .arg(NODE_SYSTEMD_UNIT_NAME)
+        .output()?;
+    if !output.status.success() {
+        return Err("Failed to read node service status".into());
+    }
+
+    parse_systemd_show(&String::from_utf8_lossy(&output.stdout), status);
+    Ok(())
+}
+
+/// Reads `systemctl show --property=...` output (`Key=value` lines).
+#[cfg(any(target_os = "linux", test))]
+fn parse_systemd_show(stdout: &str, status: &mut NodeServiceStatus) {
+    let mut active_state = None;
+    let mut sub_state = None;
+    for line in stdout.lines() {
+        match line.split_once('=') {
+            Some(("ActiveState", value)) => {
+                status.running = value == "active";
+                active_state = Some(value);
+            }
+            Some(("SubState", value)) => sub_state = Some(value),
+            Some(("MainPID", value)) => {
+                status.pid = value.parse::<u32>().ok().filter(|pid| *pid > 0)
+            }
+            Some(("ExecMainStatus", value)) => status.last_exit_code = value.parse().ok(),
+            Some(("NRestarts", value)) => status.restarts = value.parse().ok(),
+            _ => {}
+        }
+    }
+    status.state = match (active_state, sub_state) {
+        (Some(active), Some(sub)) => Some(format!("{} ({})", active, sub)),
+        (Some(active), None) => Some(active.to_string()),
+        _ => None,
+    };
+}
+
 #[cfg(target_os = "linux")]
 fn systemd_status_service() -> Result<(), Box<dyn std::error::Error>> {
     run_command_passthrough(
@@ -2463,7 +5225,7 @@ fn launchd_target() -> Result<String, Box<dyn std::error::Error>> {
     Ok(format!("{}/{}", launchd_domain()?, NODE_LAUNCHD_LABEL))
 }
 
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", target_os = "windows", test))]
 fn xml_escape(value: &str) -> String {
     value
         .replace('&', "&amp;")
@@ -2473,8 +5235,35 @@ fn xml_escape(value: &str) -> String {
         .replace('\'', "&apos;")
 }
 
+/// The plist `EnvironmentVariables` dict, or nothing when there are no variables.
+#[cfg(any(target_os = "macos", test))]
+fn launchd_environment_dict(environment: &[(String, String)]) -> String {
+    if environment.is_empty() {
+        return String::new();
+    }
+    let entries: String = environment
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "    <key>{}</key>\n    <string>{}</string>\n",
+                xml_escape(key),
+                xml_escape(value)
+            )
+        })
+        .collect();
+    format!(
+        "  <key>EnvironmentVariables</key>\n  <dict>\n{}  </dict>\n",
+        entries
+    )
+}
+
 #[cfg(target_os = "macos")]
-fn install_launchd_user_service(exe_path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+fn install_launchd_user_service(
+    exe_path: &PathBuf,
+    extra_args: &[String],
+    environment: &[(String, String)],
+    start: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     let plist_path = launchd_plist_path()?;
     if let Some(parent) = plist_path.parent() {
         std::fs::create_dir_all(parent)?;
@@ -2486,33 +5275,40 @@ fn install_launchd_user_service(exe_path: &PathBuf) -> Result<(), Box<dyn std::e
     }
 
     let plist = format!(
-        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n<plist version=\"1.0\">\n<dict>\n  <key>Label</key>\n  <string>{}</string>\n  <key>ProgramArguments</key>\n  <array>\n    <string>{}</string>\n    <string>node</string>\n    <string>--foreground</string>\n  </array>\n  <key>RunAtLoad</key>\n  <true/>\n  <key>KeepAlive</key>\n  <true/>\n</dict>\n</plist>\n",
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n<plist version=\"1.0\">\n<dict>\n  <key>Label</key>\n  <string>{}</string>\n  <key>ProgramArguments</key>\n  <array>\n    <string>{}</string>\n    <string>node</string>\n    <string>--foreground</string>\n{}  </array>\n{}  <key>RunAtLoad</key>\n  <true/>\n  <key>KeepAlive</key>\n  <true/>\n</dict>\n</plist>\n",
         NODE_LAUNCHD_LABEL,
         xml_escape(&exe_path.display().to_string()),
+        extra_args
+            .iter()
+            .map(|arg| format!("    <string>{}</string>\n", xml_escape(arg)))
+            .collect::<String>(),
+        launchd_environment_dict(environment),
     );
     std::fs::write(&plist_path, plist)?;
 
-    let domain = launchd_domain()?;
-    let _ = std::process::Command::new("launchctl")
-        .arg("bootout")
-        .arg(&domain)
-        .arg(&plist_path)
-        .status();
-
-    run_command_capture(
-        std::process::Command::new("launchctl")
-            .arg("bootstrap")
-            .arg(&domain)
-            .arg(&plist_path),
-        "Failed to bootstrap launchd service",
-    )?;
-    run_command_capture(
-        std::process::Command::new("launchctl")
-            .arg("kickstart")
-            .arg("-k")
-            .arg(launchd_target()?),
-        "Failed to start launchd service",
-    )?;
+    let domain = launchd_domain()?;
+    let _ = std::process::Command::new("launchctl")
+        .arg("bootout")
+        .arg(&domain)
+        .arg(&plist_path)
+        .status();
+
+    if start {
+        run_command_capture(
+            std::process::Command::new("launchctl")
+                .arg("bootstrap")
+                .arg(&domain)
+                .arg(&plist_path),
+            "Failed to bootstrap launchd service",
+        )?;
+        run_command_capture(
+            std::process::Command::new("launchctl")
+                .arg("kickstart")
+                .arg("-k")
+                .arg(launchd_target()?),
+            "Failed to start launchd service",
+        )?;
+    }
 
     println!("Installed launchd agent: {}", plist_path.display());
     println!("Logs: {}", log_path.display());
@@ -2587,6 +5383,38 @@ fn launchd_stop_service() -> Result<(), Box<dyn std::error::Error>> {
     )
 }
 
+#[cfg(target_os = "macos")]
+fn launchd_fill_service_status(
+    status: &mut NodeServiceStatus,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = std::process::Command::new("launchctl")
+        .arg("print")
+        .arg(launchd_target()?)
+        .output()?;
+    if !output.status.success() {
+        // Plist exists but the agent is not loaded.
+        return Ok(());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        match line.trim().split_once(" = ") {
+            Some(("state", value)) => {
+                status.running = value == "running";
+                status.state = Some(value.to_string());
+            }
+            Some(("pid", value)) => status.pid = value.parse().ok(),
+            Some(("last exit code", value)) => status.last_exit_code = value.parse().ok(),
+            // `runs` counts every launch, including the first one.
+            Some(("runs", value)) => {
+                status.restarts = value.parse::<u32>().ok().map(|runs| runs.saturating_sub(1))
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
 #[cfg(target_os = "macos")]
 fn launchd_status_service() -> Result<(), Box<dyn std::error::Error>> {
     run_command_passthrough(
@@ -2597,22 +5425,583 @@ fn launchd_status_service() -> Result<(), Box<dyn std::error::Error>> {
     )
 }
 
-#[allow(dead_code)]
-fn capabilities_for_tool(tool_name: &str) -> Result<Vec<&'static str>, String> {
-    match tool_name {
-        "Read" => Ok(vec!["filesystem.read"]),
-        "Write" => Ok(vec!["filesystem.write"]),
-        "Edit" => Ok(vec![
-            "filesystem.edit",
-            "filesystem.read",
-            "filesystem.write",
-        ]),
-        "Glob" => Ok(vec!["filesystem.list"]),
-        "Grep" => Ok(vec!["text.search", "filesystem.read"]),
-        "Bash" => Ok(vec!["shell.exec"]),
-        "Process" => Ok(vec!["shell.exec"]),
-        _ => Err(format!("No capability mapping for tool '{}'", tool_name)),
+/// Task Scheduler XML for the node task: a logon trigger and principal for
+/// `user` (no stored password), no run-time limit, and restart-on-failure
+/// every minute, which `schtasks /Create` flags cannot express.
+#[cfg(any(target_os = "windows", test))]
+fn windows_task_xml(exe_path: &str, extra_args: &[String], user: &str) -> String {
+    let arguments = std::iter::once("node --foreground".to_string())
+        .chain(extra_args.iter().map(|arg| {
+            if arg.contains(' ') {
+                format!("\"{}\"", arg)
+            } else {
+                arg.clone()
+            }
+        }))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-16\"?>\n<Task version=\"1.2\" xmlns=\"http://schemas.microsoft.com/windows/2004/02/mit/task\">\n  <RegistrationInfo>\n    <Description>GSV Node daemon</Description>\n  </RegistrationInfo>\n  <Triggers>\n    <LogonTrigger>\n      <Enabled>true</Enabled>\n      <UserId>{user}</UserId>\n    </LogonTrigger>\n  </Triggers>\n  <Principals>\n    <Principal id=\"Author\">\n      <UserId>{user}</UserId>\n      <LogonType>InteractiveToken</LogonType>\n      <RunLevel>LeastPrivilege</RunLevel>\n    </Principal>\n  </Principals>\n  <Settings>\n    <MultipleInstancesPolicy>IgnoreNew</MultipleInstancesPolicy>\n    <DisallowStartIfOnBatteries>false</DisallowStartIfOnBatteries>\n    <StopIfGoingOnBatteries>false</StopIfGoingOnBatteries>\n    <ExecutionTimeLimit>PT0S</ExecutionTimeLimit>\n    <RestartOnFailure>\n      <Interval>{interval}</Interval>\n      <Count>{count}</Count>\n    </RestartOnFailure>\n    <Enabled>true</Enabled>\n  </Settings>\n  <Actions Context=\"Author\">\n    <Exec>\n      <Command>{command}</Command>\n      <Arguments>{arguments}</Arguments>\n    </Exec>\n  </Actions>\n</Task>\n",
+        user = xml_escape(user),
+        interval = NODE_WINDOWS_TASK_RESTART_INTERVAL,
+        count = NODE_WINDOWS_TASK_RESTART_COUNT,
+        command = xml_escape(exe_path),
+        arguments = xml_escape(&arguments),
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn install_windows_scheduled_task(
+    exe_path: &PathBuf,
+    extra_args: &[String],
+    start: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let xml = windows_task_xml(
+        &exe_path.display().to_string(),
+        extra_args,
+        &windows_task_user(),
+    );
+    // schtasks /XML expects UTF-16 with a byte order mark.
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend(xml.encode_utf16().flat_map(u16::to_le_bytes));
+    let xml_path = std::env::temp_dir().join("gsv-node-task.xml");
+    std::fs::write(&xml_path, bytes)?;
+
+    let created = run_command_capture(
+        std::process::Command::new("schtasks")
+            .arg("/Create")
+            .arg("/TN")
+            .arg(NODE_WINDOWS_TASK_NAME)
+            .arg("/XML")
+            .arg(&xml_path)
+            .arg("/F"),
+        "Failed to create scheduled task",
+    );
+    let _ = std::fs::remove_file(&xml_path);
+    created?;
+
+    if start {
+        windows_run_task()?;
+    }
+
+    println!(
+        "Installed scheduled task: {} (trigger: {})",
+        NODE_WINDOWS_TASK_NAME, NODE_WINDOWS_TASK_TRIGGER
+    );
+    Ok(())
+}
+
+/// `DOMAIN\user` for the current account, as Task Scheduler expects for `UserId`.
+#[cfg(target_os = "windows")]
+fn windows_task_user() -> String {
+    let user = std::env::var("USERNAME").unwrap_or_else(|_| whoami::username());
+    match std::env::var("USERDOMAIN") {
+        Ok(domain) if !domain.is_empty() => format!("{}\\{}", domain, user),
+        _ => user,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn uninstall_windows_scheduled_task() -> Result<(), Box<dyn std::error::Error>> {
+    let _ = windows_end_task();
+    if !windows_task_exists()? {
+        return Ok(());
+    }
+
+    run_command_capture(
+        std::process::Command::new("schtasks")
+            .arg("/Delete")
+            .arg("/TN")
+            .arg(NODE_WINDOWS_TASK_NAME)
+            .arg("/F"),
+        "Failed to delete scheduled task",
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn windows_task_exists() -> Result<bool, Box<dyn std::error::Error>> {
+    let output = std::process::Command::new("schtasks")
+        .arg("/Query")
+        .arg("/TN")
+        .arg(NODE_WINDOWS_TASK_NAME)
+        .output()?;
+    Ok(output.status.success())
+}
+
+#[cfg(target_os = "windows")]
+fn windows_run_task() -> Result<(), Box<dyn std::error::Error>> {
+    run_command_capture(
+        std::process::Command::new("schtasks")
+            .arg("/Run")
+            .arg("/TN")
+            .arg(NODE_WINDOWS_TASK_NAME),
+        "Failed to start scheduled task",
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn windows_end_task() -> Result<(), Box<dyn std::error::Error>> {
+    run_command_capture(
+        std::process::Command::new("schtasks")
+            .arg("/End")
+            .arg("/TN")
+            .arg(NODE_WINDOWS_TASK_NAME),
+        "Failed to stop scheduled task",
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn windows_fill_task_status(
+    status: &mut NodeServiceStatus,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = std::process::Command::new("schtasks")
+        .arg("/Query")
+        .arg("/TN")
+        .arg(NODE_WINDOWS_TASK_NAME)
+        .arg("/V")
+        .arg("/FO")
+        .arg("LIST")
+        .output()?;
+    if !output.status.success() {
+        return Ok(());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        match line.split_once(':').map(|(k, v)| (k.trim(), v.trim())) {
+            Some(("Status", value)) => {
+                status.running = value == "Running";
+                status.state = Some(value.to_string());
+            }
+            Some(("Last Result", value)) => status.last_exit_code = value.parse().ok(),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn windows_task_status() -> Result<(), Box<dyn std::error::Error>> {
+    run_command_passthrough(
+        std::process::Command::new("schtasks")
+            .arg("/Query")
+            .arg("/TN")
+            .arg(NODE_WINDOWS_TASK_NAME)
+            .arg("/V")
+            .arg("/FO")
+            .arg("LIST"),
+        "Failed to read scheduled task status",
+    )
+}
+
+/// Everything `run_node` needs, handed from `gsv node --as-windows-service` to
+/// the service main, which the SCM dispatcher runs on its own thread.
+#[cfg(target_os = "windows")]
+struct NodeServiceLaunch {
+    urls: Vec<String>,
+    token: Option<String>,
+    node_id: String,
+    workspace: PathBuf,
+    logger: NodeLogger,
+    cfg: CliConfig,
+    health_addr: Option<std::net::SocketAddr>,
+    runtime: tokio::runtime::Handle,
+}
+
+#[cfg(target_os = "windows")]
+static NODE_SERVICE_LAUNCH: std::sync::Mutex<Option<NodeServiceLaunch>> =
+    std::sync::Mutex::new(None);
+
+#[cfg(target_os = "windows")]
+windows_service::define_windows_service!(ffi_node_service_main, node_service_main);
+
+/// Hand control to the service control manager. Blocks until the service stops.
+#[cfg(target_os = "windows")]
+fn run_as_windows_service(launch: NodeServiceLaunch) -> Result<(), Box<dyn std::error::Error>> {
+    *NODE_SERVICE_LAUNCH.lock().unwrap() = Some(launch);
+    tokio::task::block_in_place(|| {
+        windows_service::service_dispatcher::start(
+            NODE_WINDOWS_SERVICE_NAME,
+            ffi_node_service_main,
+        )
+    })
+    .map_err(|e| {
+        format!(
+            "Failed to connect to the service control manager ({}). --as-windows-service is only for the installed service; use 'gsv node --foreground' instead",
+            e
+        )
+        .into()
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn node_service_main(_arguments: Vec<std::ffi::OsString>) {
+    use windows_service::service::{
+        ServiceControl, ServiceControlAccept, ServiceExitCode, ServiceState, ServiceStatus,
+        ServiceType,
+    };
+    use windows_service::service_control_handler::{self, ServiceControlHandlerResult};
+
+    let Some(launch) = NODE_SERVICE_LAUNCH.lock().unwrap().take() else {
+        return;
+    };
+
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    let mut shutdown_tx = Some(shutdown_tx);
+    let event_handler = move |control| match control {
+        ServiceControl::Stop | ServiceControl::Shutdown => {
+            if let Some(tx) = shutdown_tx.take() {
+                let _ = tx.send(());
+            }
+            ServiceControlHandlerResult::NoError
+        }
+        ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+        _ => ServiceControlHandlerResult::NotImplemented,
+    };
+    let Ok(status_handle) =
+        service_control_handler::register(NODE_WINDOWS_SERVICE_NAME, event_handler)
+    else {
+        return;
+    };
+
+    let service_status = |state, controls_accepted, exit_code| ServiceStatus {
+        service_type: ServiceType::OWN_PROCESS,
+        current_state: state,
+        controls_accepted,
+        exit_code,
+        checkpoint: 0,
+        wait_hint: std::time::Duration::default(),
+        process_id: None,
+    };
+    let _ = status_handle.set_service_status(service_status(
+        ServiceState::Running,
+        ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN,
+        ServiceExitCode::Win32(0),
+    ));
+
+    let NodeServiceLaunch {
+        urls,
+        token,
+        node_id,
+        workspace,
+        logger,
+        cfg,
+        health_addr,
+        runtime,
+    } = launch;
+    let failed = runtime.block_on(async {
+        tokio::select! {
+            result = run_node(&urls, token, node_id, workspace, logger, &cfg, health_addr) => {
+                result.is_err()
+            }
+            _ = shutdown_rx => false,
+        }
+    });
+
+    // A non-zero exit code makes the SCM apply the restart failure actions.
+    let exit_code = if failed {
+        ServiceExitCode::ServiceSpecific(1)
+    } else {
+        ServiceExitCode::Win32(0)
+    };
+    let _ = status_handle.set_service_status(service_status(
+        ServiceState::Stopped,
+        ServiceControlAccept::empty(),
+        exit_code,
+    ));
+}
+
+/// Adds a hint to SCM errors that are fixed by elevating or granting rights.
+#[cfg(target_os = "windows")]
+fn windows_service_error(
+    context: &str,
+    error: windows_service::Error,
+) -> Box<dyn std::error::Error> {
+    const ERROR_ACCESS_DENIED: i32 = 5;
+    const ERROR_SERVICE_LOGON_FAILED: i32 = 1069;
+    let hint = match &error {
+        windows_service::Error::Winapi(e) => match e.raw_os_error() {
+            Some(ERROR_ACCESS_DENIED) => {
+                ". Run this from an elevated terminal (Run as administrator)"
+            }
+            Some(ERROR_SERVICE_LOGON_FAILED) => {
+                ". Check the password, and that the account has the 'Log on as a service' right"
+            }
+            _ => "",
+        },
+        _ => "",
+    };
+    format!("{}: {}{}", context, error, hint).into()
+}
+
+#[cfg(target_os = "windows")]
+fn open_windows_service(
+    access: windows_service::service::ServiceAccess,
+) -> Result<Option<windows_service::service::Service>, Box<dyn std::error::Error>> {
+    use windows_service::service_manager::{ServiceManager, ServiceManagerAccess};
+    const ERROR_SERVICE_DOES_NOT_EXIST: i32 = 1060;
+
+    let manager = ServiceManager::local_computer(None::<&str>, ServiceManagerAccess::CONNECT)
+        .map_err(|e| {
+            windows_service_error("Failed to connect to the service control manager", e)
+        })?;
+    match manager.open_service(NODE_WINDOWS_SERVICE_NAME, access) {
+        Ok(service) => Ok(Some(service)),
+        Err(windows_service::Error::Winapi(e))
+            if e.raw_os_error() == Some(ERROR_SERVICE_DOES_NOT_EXIST) =>
+        {
+            Ok(None)
+        }
+        Err(e) => Err(windows_service_error("Failed to open the node service", e)),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn windows_service_exists() -> Result<bool, Box<dyn std::error::Error>> {
+    Ok(open_windows_service(windows_service::service::ServiceAccess::QUERY_STATUS)?.is_some())
+}
+
+/// Register the node as an auto-start SCM service running as the installing
+/// user, so it reads the same config and workspace as `gsv node`. Creating
+/// a service needs an elevated terminal, like `sc.exe create`.
+#[cfg(target_os = "windows")]
+fn install_windows_service(
+    exe_path: &Path,
+    extra_args: &[String],
+    start: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use windows_service::service::{
+        ServiceAccess, ServiceAction, ServiceActionType, ServiceErrorControl,
+        ServiceFailureActions, ServiceFailureResetPeriod, ServiceInfo, ServiceStartType,
+        ServiceType,
+    };
+    use windows_service::service_manager::{ServiceManager, ServiceManagerAccess};
+    const ERROR_SERVICE_EXISTS: i32 = 1073;
+
+    let manager = ServiceManager::local_computer(
+        None::<&str>,
+        ServiceManagerAccess::CONNECT | ServiceManagerAccess::CREATE_SERVICE,
+    )
+    .map_err(|e| windows_service_error("Failed to connect to the service control manager", e))?;
+
+    let account = windows_task_user();
+    let account_password = password(format!("Windows password for {}", account)).interact()?;
+    let launch_arguments = ["node", "--foreground", "--as-windows-service"]
+        .into_iter()
+        .map(std::ffi::OsString::from)
+        .chain(extra_args.iter().map(std::ffi::OsString::from))
+        .collect();
+    let service_info = ServiceInfo {
+        name: NODE_WINDOWS_SERVICE_NAME.into(),
+        display_name: NODE_WINDOWS_SERVICE_DISPLAY_NAME.into(),
+        service_type: ServiceType::OWN_PROCESS,
+        start_type: ServiceStartType::AutoStart,
+        error_control: ServiceErrorControl::Normal,
+        executable_path: exe_path.to_path_buf(),
+        launch_arguments,
+        dependencies: Vec::new(),
+        account_name: Some(account.clone().into()),
+        account_password: Some(account_password.into()),
+    };
+    let access = ServiceAccess::CHANGE_CONFIG | ServiceAccess::START | ServiceAccess::QUERY_STATUS;
+    let service = match manager.create_service(&service_info, access) {
+        Ok(service) => service,
+        Err(windows_service::Error::Winapi(e))
+            if e.raw_os_error() == Some(ERROR_SERVICE_EXISTS) =>
+        {
+            let service = manager
+                .open_service(NODE_WINDOWS_SERVICE_NAME, access)
+                .map_err(|e| windows_service_error("Failed to open the node service", e))?;
+            service
+                .change_config(&service_info)
+                .map_err(|e| windows_service_error("Failed to update the node service", e))?;
+            service
+        }
+        Err(e) => {
+            return Err(windows_service_error(
+                "Failed to create the node service",
+                e,
+            ))
+        }
+    };
+    service
+        .set_description("GSV Node daemon")
+        .map_err(|e| windows_service_error("Failed to update the node service", e))?;
+
+    // Same policy as the scheduled task: restart a minute after any failed exit.
+    let restart = ServiceAction {
+        action_type: ServiceActionType::Restart,
+        delay: std::time::Duration::from_secs(60),
+    };
+    service
+        .update_failure_actions(ServiceFailureActions {
+            reset_period: ServiceFailureResetPeriod::After(std::time::Duration::from_secs(86400)),
+            reboot_msg: None,
+            command: None,
+            actions: Some(vec![restart.clone(), restart.clone(), restart]),
+        })
+        .map_err(|e| windows_service_error("Failed to set the node service restart policy", e))?;
+    service
+        .set_failure_actions_on_non_crash_failures(true)
+        .map_err(|e| windows_service_error("Failed to set the node service restart policy", e))?;
+
+    // Only one of the two backends may run the node.
+    uninstall_windows_scheduled_task()?;
+
+    if start {
+        start_windows_service()?;
+    }
+
+    println!(
+        "Installed Windows service: {} (starts at boot, runs as {})",
+        NODE_WINDOWS_SERVICE_NAME, account
+    );
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn uninstall_windows_service() -> Result<(), Box<dyn std::error::Error>> {
+    use windows_service::service::{ServiceAccess, ServiceState};
+
+    let Some(service) = open_windows_service(
+        ServiceAccess::QUERY_STATUS | ServiceAccess::STOP | ServiceAccess::DELETE,
+    )?
+    else {
+        return Ok(());
+    };
+    let state = service
+        .query_status()
+        .map_err(|e| windows_service_error("Failed to query the node service", e))?
+        .current_state;
+    if state != ServiceState::Stopped {
+        let _ = service.stop();
+    }
+    service
+        .delete()
+        .map_err(|e| windows_service_error("Failed to delete the node service", e))
+}
+
+#[cfg(target_os = "windows")]
+fn start_windows_service() -> Result<(), Box<dyn std::error::Error>> {
+    use windows_service::service::{ServiceAccess, ServiceState};
+
+    let service = open_windows_service(ServiceAccess::QUERY_STATUS | ServiceAccess::START)?
+        .ok_or("The node service is not installed")?;
+    let state = service
+        .query_status()
+        .map_err(|e| windows_service_error("Failed to query the node service", e))?
+        .current_state;
+    if state == ServiceState::Running || state == ServiceState::StartPending {
+        return Ok(());
+    }
+    service
+        .start::<&str>(&[])
+        .map_err(|e| windows_service_error("Failed to start the node service", e))
+}
+
+#[cfg(target_os = "windows")]
+fn stop_windows_service() -> Result<(), Box<dyn std::error::Error>> {
+    use windows_service::service::{ServiceAccess, ServiceState};
+
+    let service = open_windows_service(ServiceAccess::QUERY_STATUS | ServiceAccess::STOP)?
+        .ok_or("The node service is not installed")?;
+    let status = service
+        .query_status()
+        .map_err(|e| windows_service_error("Failed to query the node service", e))?;
+    if status.current_state == ServiceState::Stopped {
+        return Ok(());
+    }
+    service
+        .stop()
+        .map(|_| ())
+        .map_err(|e| windows_service_error("Failed to stop the node service", e))
+}
+
+#[cfg(target_os = "windows")]
+fn windows_fill_service_status(
+    status: &mut NodeServiceStatus,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use windows_service::service::{ServiceAccess, ServiceExitCode, ServiceState};
+
+    let Some(service) = open_windows_service(ServiceAccess::QUERY_STATUS)? else {
+        return Ok(());
+    };
+    let service_status = service
+        .query_status()
+        .map_err(|e| windows_service_error("Failed to query the node service", e))?;
+    status.running = service_status.current_state == ServiceState::Running;
+    status.state = Some(format!("{:?}", service_status.current_state));
+    status.pid = service_status.process_id;
+    status.last_exit_code = match service_status.exit_code {
+        ServiceExitCode::Win32(code) | ServiceExitCode::ServiceSpecific(code) => {
+            i32::try_from(code).ok()
+        }
+    };
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn status_windows_service() -> Result<(), Box<dyn std::error::Error>> {
+    use windows_service::service::ServiceAccess;
+
+    let service = open_windows_service(ServiceAccess::QUERY_STATUS | ServiceAccess::QUERY_CONFIG)?
+        .ok_or("The node service is not installed")?;
+    let status = service
+        .query_status()
+        .map_err(|e| windows_service_error("Failed to query the node service", e))?;
+    let config = service
+        .query_config()
+        .map_err(|e| windows_service_error("Failed to query the node service", e))?;
+
+    println!(
+        "Service:    {} ({})",
+        NODE_WINDOWS_SERVICE_NAME,
+        config.display_name.to_string_lossy()
+    );
+    println!("State:      {:?}", status.current_state);
+    if let Some(pid) = status.process_id {
+        println!("PID:        {}", pid);
+    }
+    println!("Start type: {:?}", config.start_type);
+    if let Some(account) = config.account_name {
+        println!("Account:    {}", account.to_string_lossy());
+    }
+    println!("Exit code:  {:?}", status.exit_code);
+    Ok(())
+}
+
+/// Capabilities advertised for a tool. `node.custom_tools` entries take precedence
+/// over the built-in mapping; None for tools with neither.
+fn capabilities_for_tool(tool_name: &str, cfg: Option<&CliConfig>) -> Option<Vec<String>> {
+    if let Some(custom) = cfg.and_then(|cfg| {
+        cfg.node
+            .custom_tools
+            .iter()
+            .find(|tool| tool.name == tool_name)
+    }) {
+        return Some(custom.capabilities.clone());
     }
+
+    let builtin: &[&str] = match tool_name {
+        "Read" => &["filesystem.read"],
+        "Write" => &["filesystem.write"],
+        "Edit" => &["filesystem.edit", "filesystem.read", "filesystem.write"],
+        "Glob" => &["filesystem.list"],
+        "Find" => &["filesystem.list"],
+        "Diff" => &["filesystem.read"],
+        "Move" => &["filesystem.write"],
+        "Copy" => &["filesystem.read", "filesystem.write"],
+        "Grep" => &["text.search", "filesystem.read"],
+        "Bash" => &["shell.exec"],
+        "Process" => &["shell.exec"],
+        _ => return None,
+    };
+    Some(
+        builtin
+            .iter()
+            .map(|capability| capability.to_string())
+            .collect(),
+    )
 }
 
 fn is_valid_probe_bin(bin: &str) -> bool {
@@ -2629,9 +6018,9 @@ fn is_executable_file(path: &Path) -> bool {
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
-        return fs::metadata(path)
+        fs::metadata(path)
             .map(|meta| (meta.permissions().mode() & 0o111) != 0)
-            .unwrap_or(false);
+            .unwrap_or(false)
     }
     #[cfg(not(unix))]
     {
@@ -2730,27 +6119,60 @@ where
     env_keys
 }
 
+fn strict_tool_names_enabled() -> bool {
+    std::env::var("GSV_NODE_STRICT_TOOL_NAMES")
+        .map(|value| value.trim() == "1")
+        .unwrap_or(false)
+}
+
+/// Builds the runtime info for `tool_defs`. Duplicate tool names are removed
+/// from `tool_defs` in place (the first occurrence, i.e. the built-in, wins)
+/// unless `strict_tool_names` is set, in which case they are an error.
 fn build_execution_node_runtime(
-    tool_defs: &[ToolDefinition],
+    tool_defs: &mut Vec<ToolDefinition>,
+    cfg: Option<&CliConfig>,
+    strict_tool_names: bool,
+    logger: &NodeLogger,
 ) -> Result<NodeRuntimeInfo, Box<dyn std::error::Error>> {
     let mut seen_tool_names = HashSet::new();
     let mut host_capabilities = HashSet::new();
     let mut tool_capabilities: HashMap<String, Vec<String>> = HashMap::new();
 
-    for tool in tool_defs {
-        if !seen_tool_names.insert(tool.name.clone()) {
-            return Err(format!("Duplicate tool name: {}", tool.name).into());
+    let mut duplicate = None;
+    tool_defs.retain(|tool| {
+        if seen_tool_names.insert(tool.name.clone()) {
+            return true;
         }
+        duplicate.get_or_insert_with(|| tool.name.clone());
+        logger.warn(
+            "tools.duplicate.dropped",
+            json!({
+                "tool": tool.name.clone(),
+                "definition": tool,
+            }),
+        );
+        false
+    });
+    if let Some(name) = duplicate.filter(|_| strict_tool_names) {
+        return Err(format!("Duplicate tool name: {}", name).into());
+    }
 
-        let capabilities = capabilities_for_tool(&tool.name)?;
-        for capability in &capabilities {
-            host_capabilities.insert((*capability).to_string());
+    for tool in tool_defs.iter() {
+        let mut normalized_caps = capabilities_for_tool(&tool.name, cfg).unwrap_or_else(|| {
+            // Unmapped tools are deprecated; they should be declared under
+            // [[node.custom_tools]].
+            logger.warn(
+                "tools.capabilities.unmapped",
+                json!({
+                    "tool": tool.name.clone(),
+                    "hint": "declare capabilities under [[node.custom_tools]]",
+                }),
+            );
+            Vec::new()
+        });
+        for capability in &normalized_caps {
+            host_capabilities.insert(capability.clone());
         }
-
-        let mut normalized_caps: Vec<String> = capabilities
-            .into_iter()
-            .map(|capability| capability.to_string())
-            .collect();
         normalized_caps.sort();
         normalized_caps.dedup();
         tool_capabilities.insert(tool.name.clone(), normalized_caps);
@@ -2893,49 +6315,280 @@ where
             }
         }
     }
-}
+}
+
+async fn flush_exec_event_outbox(
+    conn: &Arc<Connection>,
+    outbox: &Arc<Mutex<VecDeque<NodeExecEventParams>>>,
+    logger: &NodeLogger,
+) -> usize {
+    flush_exec_event_outbox_with_sender(outbox, logger, |event| {
+        let conn = Arc::clone(conn);
+        async move {
+            let params = match serde_json::to_value(&event) {
+                Ok(value) => value,
+                Err(error) => return ExecEventSendOutcome::Drop(error.to_string()),
+            };
+
+            match conn.request("node.exec.event", Some(params)).await {
+                Ok(response) if response.ok => ExecEventSendOutcome::Sent,
+                Ok(response) => {
+                    let message = response
+                        .error
+                        .map(|error| error.message)
+                        .unwrap_or_else(|| "unknown response".to_string());
+                    ExecEventSendOutcome::Retry(message)
+                }
+                Err(error) => ExecEventSendOutcome::Retry(error.to_string()),
+            }
+        }
+    })
+    .await
+}
+
+async fn handle_tool_invoke(
+    conn: Arc<Connection>,
+    tools: Arc<Vec<Box<dyn Tool>>>,
+    logger: NodeLogger,
+    payload: serde_json::Value,
+) {
+    let invoke = match serde_json::from_value::<ToolInvokePayload>(payload) {
+        Ok(invoke) => invoke,
+        Err(e) => {
+            logger.warn(
+                "tool.invoke.parse_failed",
+                json!({
+                    "error": e.to_string(),
+                }),
+            );
+            return;
+        }
+    };
+
+    let tool_name = invoke.tool.clone();
+    let call_id = invoke.call_id.clone();
+    logger.info(
+        "tool.invoke",
+        json!({
+            "tool": tool_name.clone(),
+            "callId": call_id.clone(),
+        }),
+    );
+    logger.debug(
+        "tool.invoke.args",
+        json!({
+            "tool": tool_name.clone(),
+            "callId": call_id.clone(),
+            "args": invoke.args.clone(),
+        }),
+    );
+
+    let streaming = invoke.args.get("stream").and_then(|v| v.as_bool()) == Some(true);
+    let tool = tools.iter().find(|t| t.definition().name == invoke.tool);
+    let timeout_seconds = tool.and_then(|t| t.definition().timeout_seconds);
+    let execution = async {
+        match tool {
+            Some(tool) if streaming => {
+                let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+                let forward_conn = conn.clone();
+                let forward_call_id = call_id.clone();
+                let forwarder = tokio::spawn(async move {
+                    while let Some(partial) = progress_rx.recv().await {
+                        let params = ToolResultParams {
+                            call_id: forward_call_id.clone(),
+                            result: Some(partial),
+                            error: None,
+                            done: Some(false),
+                        };
+                        let _ = forward_conn
+                            .request("tool.result", Some(serde_json::to_value(&params).unwrap()))
+                            .await;
+                    }
+                });
+                let result = tool
+                    .execute_streaming(invoke.args.clone(), progress_tx)
+                    .await;
+                // The sender is dropped once execution returns; wait so partials precede the final result.
+                let _ = forwarder.await;
+                result
+            }
+            Some(tool) => tool.execute(invoke.args.clone()).await,
+            None => Err(format!("Tool not found: {}", invoke.tool)),
+        }
+    };
+    let result = match timeout_seconds {
+        Some(secs) => {
+            match tokio::time::timeout(std::time::Duration::from_secs(secs), execution).await {
+                Ok(result) => result,
+                Err(_) => {
+                    logger.warn(
+                        "tool.execute.timeout",
+                        json!({
+                            "tool": tool_name.clone(),
+                            "callId": call_id.clone(),
+                            "timeoutSeconds": secs,
+                        }),
+                    );
+                    Err(format!("timeout after {}s", secs))
+                }
+            }
+        }
+        None => execution.await,
+    };
+
+    match &result {
+        Ok(_) => {
+            logger.info(
+                "tool.execute.ok",
+                json!({
+                    "tool": tool_name.clone(),
+                    "callId": call_id.clone(),
+                }),
+            );
+        }
+        Err(err) => {
+            logger.warn(
+                "tool.execute.error",
+                json!({
+                    "tool": tool_name.clone(),
+                    "callId": call_id.clone(),
+                    "error": err,
+                }),
+            );
+        }
+    }
 
-async fn flush_exec_event_outbox(
-    conn: &Arc<Connection>,
-    outbox: &Arc<Mutex<VecDeque<NodeExecEventParams>>>,
-    logger: &NodeLogger,
-) -> usize {
-    flush_exec_event_outbox_with_sender(outbox, logger, |event| {
-        let conn = Arc::clone(conn);
-        async move {
-            let params = match serde_json::to_value(&event) {
-                Ok(value) => value,
-                Err(error) => return ExecEventSendOutcome::Drop(error.to_string()),
-            };
+    let params = match result {
+        Ok(res) => ToolResultParams {
+            call_id: invoke.call_id,
+            result: Some(res),
+            error: None,
+            done: streaming.then_some(true),
+        },
+        Err(e) => ToolResultParams {
+            call_id: invoke.call_id,
+            result: None,
+            error: Some(e),
+            done: streaming.then_some(true),
+        },
+    };
 
-            match conn.request("node.exec.event", Some(params)).await {
-                Ok(response) if response.ok => ExecEventSendOutcome::Sent,
-                Ok(response) => {
-                    let message = response
-                        .error
-                        .map(|error| error.message)
-                        .unwrap_or_else(|| "unknown response".to_string());
-                    ExecEventSendOutcome::Retry(message)
-                }
-                Err(error) => ExecEventSendOutcome::Retry(error.to_string()),
-            }
+    if let Err(e) = conn
+        .request("tool.result", Some(serde_json::to_value(&params).unwrap()))
+        .await
+    {
+        logger.error(
+            "tool.result.send_failed",
+            json!({
+                "tool": tool_name,
+                "callId": call_id,
+                "error": e.to_string(),
+            }),
+        );
+    }
+}
+
+async fn handle_logs_get(conn: Arc<Connection>, logger: NodeLogger, payload: serde_json::Value) {
+    let request = match serde_json::from_value::<LogsGetPayload>(payload) {
+        Ok(request) => request,
+        Err(e) => {
+            logger.warn(
+                "logs.get.parse_failed",
+                json!({
+                    "error": e.to_string(),
+                }),
+            );
+            return;
         }
-    })
-    .await
+    };
+
+    let requested_lines = request.lines.unwrap_or(DEFAULT_NODE_LOG_GET_LINES);
+    let resolved_lines = resolve_logs_get_line_limit(request.lines);
+    if requested_lines != resolved_lines {
+        logger.warn(
+            "logs.get.limit_clamped",
+            json!({
+                "callId": request.call_id,
+                "requestedLines": requested_lines,
+                "resolvedLines": resolved_lines,
+                "maxLines": MAX_NODE_LOG_GET_LINES,
+            }),
+        );
+    }
+
+    logger.info(
+        "logs.get",
+        json!({
+            "callId": request.call_id,
+            "requestedLines": requested_lines,
+            "resolvedLines": resolved_lines,
+            "filter": request.filter,
+        }),
+    );
+
+    let read_result = match request.filter.as_deref().filter(|f| !f.is_empty()) {
+        Some(filter) => read_node_log_lines_filtered(resolved_lines, |line| line.contains(filter)),
+        None => read_recent_node_log_lines(resolved_lines),
+    };
+    let response = match read_result {
+        Ok((lines, truncated)) => LogsResultParams {
+            call_id: request.call_id.clone(),
+            lines: Some(lines),
+            truncated: Some(truncated),
+            error: None,
+        },
+        Err(error) => LogsResultParams {
+            call_id: request.call_id.clone(),
+            lines: None,
+            truncated: None,
+            error: Some(error),
+        },
+    };
+
+    if let Some(error) = response.error.clone() {
+        logger.warn(
+            "logs.get.error",
+            json!({
+                "callId": request.call_id,
+                "error": error,
+            }),
+        );
+    }
+
+    if let Err(e) = conn
+        .request(
+            "logs.result",
+            Some(serde_json::to_value(&response).unwrap()),
+        )
+        .await
+    {
+        logger.error(
+            "logs.result.send_failed",
+            json!({
+                "callId": response.call_id,
+                "error": e.to_string(),
+            }),
+        );
+    }
 }
 
+/// Connects to `urls[0]`, moving on to the next URL after each failed attempt
+/// and wrapping around, so `gateway.urls` fallbacks take over while the
+/// primary is down.
 async fn run_node(
-    url: &str,
+    urls: &[String],
     token: Option<String>,
     node_id: String,
     workspace: PathBuf,
+    logger: NodeLogger,
+    cfg: &CliConfig,
+    health_addr: Option<std::net::SocketAddr>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let logger = NodeLogger::new(&node_id, &workspace)?;
     let log_path = logger::node_log_path()?;
     logger.info(
         "node.start",
         json!({
-            "url": url,
+            "url": urls[0],
             "logPath": log_path.display().to_string(),
             "logMaxBytes": logger::node_log_max_bytes(),
             "logMaxFiles": logger::node_log_max_files(),
@@ -2945,6 +6598,26 @@ async fn run_node(
     let shutdown = wait_for_shutdown_signal();
     tokio::pin!(shutdown);
 
+    let connected = Arc::new(AtomicBool::new(false));
+    let health_server = match health_addr {
+        Some(addr) => {
+            let listener = tokio::net::TcpListener::bind(addr)
+                .await
+                .map_err(|e| format!("Failed to bind --health-port {}: {}", addr, e))?;
+            logger.info(
+                "health.listen",
+                json!({ "host": addr.ip().to_string(), "port": addr.port() }),
+            );
+            Some(tokio::spawn(serve_node_health(
+                listener,
+                node_id.clone(),
+                connected.clone(),
+                std::time::Instant::now(),
+            )))
+        }
+        None => None,
+    };
+
     let exec_event_outbox: Arc<Mutex<VecDeque<NodeExecEventParams>>> =
         Arc::new(Mutex::new(VecDeque::new()));
     let outbox_for_exec_events = exec_event_outbox.clone();
@@ -2976,14 +6649,26 @@ async fn run_node(
     });
 
     let transfer_coordinator = Arc::new(TransferCoordinator::new());
+    let mut reconnect = ReconnectBackoff::from_config_and_env(&cfg.node.reconnect);
+    let mut url_index = 0;
 
     loop {
+        let url = urls[url_index % urls.len()].as_str();
+        connected.store(false, Ordering::Relaxed);
         logger.info("connect.attempt", json!({ "url": url }));
 
-        let tools = all_tools_with_workspace(workspace.clone());
-        let tool_defs: Vec<_> = tools.iter().map(|t| t.definition()).collect();
+        let tools = tool_filter(
+            all_tools_with_workspace(workspace.clone(), Some(cfg.node.tool_timeouts.clone())),
+            cfg,
+        )?;
+        let mut tool_defs: Vec<_> = tools.iter().map(|t| t.definition()).collect();
+        let node_runtime = build_execution_node_runtime(
+            &mut tool_defs,
+            Some(cfg),
+            strict_tool_names_enabled(),
+            &logger,
+        )?;
         let tool_names: Vec<String> = tool_defs.iter().map(|t| t.name.clone()).collect();
-        let node_runtime = build_execution_node_runtime(&tool_defs)?;
 
         logger.info(
             "tools.register",
@@ -2993,8 +6678,10 @@ async fn run_node(
             }),
         );
 
-        let tools_for_handler: Arc<Vec<Box<dyn Tool>>> =
-            Arc::new(all_tools_with_workspace(workspace.clone()));
+        let tools_for_handler: Arc<Vec<Box<dyn Tool>>> = Arc::new(tool_filter(
+            all_tools_with_workspace(workspace.clone(), Some(cfg.node.tool_timeouts.clone())),
+            cfg,
+        )?);
 
         let conn = match Connection::connect_with_options(
             url,
@@ -3009,14 +6696,17 @@ async fn run_node(
         {
             Ok(c) => c,
             Err(e) => {
+                url_index += 1;
+                let delay = reconnect.next_delay();
                 logger.error(
                     "connect.failed",
                     json!({
+                        "url": url,
                         "error": e.to_string(),
-                        "retrySeconds": 3,
+                        "retrySeconds": delay.as_secs_f64(),
                     }),
                 );
-                tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+                tokio::time::sleep(delay).await;
                 continue;
             }
         };
@@ -3028,186 +6718,51 @@ async fn run_node(
         })
         .await;
 
+        let conn_for_invoke = conn.clone();
+        let logger_for_invoke = logger.clone();
+        conn.subscribe("tool.invoke", move |frame| {
+            if let Frame::Evt(evt) = frame {
+                if let Some(payload) = evt.payload {
+                    tokio::spawn(handle_tool_invoke(
+                        conn_for_invoke.clone(),
+                        tools_for_handler.clone(),
+                        logger_for_invoke.clone(),
+                        payload,
+                    ));
+                }
+            }
+        })
+        .await;
+
+        let conn_for_logs = conn.clone();
+        let logger_for_logs = logger.clone();
+        conn.subscribe("logs.get", move |frame| {
+            if let Frame::Evt(evt) = frame {
+                if let Some(payload) = evt.payload {
+                    tokio::spawn(handle_logs_get(
+                        conn_for_logs.clone(),
+                        logger_for_logs.clone(),
+                        payload,
+                    ));
+                }
+            }
+        })
+        .await;
+
         let conn_clone = conn.clone();
-        let tools_clone = tools_for_handler.clone();
         let logger_clone = logger.clone();
         let coordinator_for_events = transfer_coordinator.clone();
         let workspace_for_transfers = workspace.clone();
 
         conn.set_event_handler(move |frame| {
             let conn = conn_clone.clone();
-            let tools = tools_clone.clone();
             let logger = logger_clone.clone();
             let coordinator = coordinator_for_events.clone();
             let transfer_workspace = workspace_for_transfers.clone();
 
             tokio::spawn(async move {
                 if let Frame::Evt(evt) = frame {
-                    if evt.event == "tool.invoke" {
-                        if let Some(payload) = evt.payload {
-                            let invoke = match serde_json::from_value::<ToolInvokePayload>(payload)
-                            {
-                                Ok(invoke) => invoke,
-                                Err(e) => {
-                                    logger.warn(
-                                        "tool.invoke.parse_failed",
-                                        json!({
-                                            "error": e.to_string(),
-                                        }),
-                                    );
-                                    return;
-                                }
-                            };
-
-                            let tool_name = invoke.tool.clone();
-                            let call_id = invoke.call_id.clone();
-                            logger.info(
-                                "tool.invoke",
-                                json!({
-                                    "tool": tool_name.clone(),
-                                    "callId": call_id.clone(),
-                                }),
-                            );
-
-                            let result =
-                                match tools.iter().find(|t| t.definition().name == invoke.tool) {
-                                    Some(tool) => tool.execute(invoke.args.clone()).await,
-                                    None => Err(format!("Tool not found: {}", invoke.tool)),
-                                };
-
-                            match &result {
-                                Ok(_) => {
-                                    logger.info(
-                                        "tool.execute.ok",
-                                        json!({
-                                            "tool": tool_name.clone(),
-                                            "callId": call_id.clone(),
-                                        }),
-                                    );
-                                }
-                                Err(err) => {
-                                    logger.warn(
-                                        "tool.execute.error",
-                                        json!({
-                                            "tool": tool_name.clone(),
-                                            "callId": call_id.clone(),
-                                            "error": err,
-                                        }),
-                                    );
-                                }
-                            }
-
-                            let params = match result {
-                                Ok(res) => ToolResultParams {
-                                    call_id: invoke.call_id,
-                                    result: Some(res),
-                                    error: None,
-                                },
-                                Err(e) => ToolResultParams {
-                                    call_id: invoke.call_id,
-                                    result: None,
-                                    error: Some(e),
-                                },
-                            };
-
-                            if let Err(e) = conn
-                                .request(
-                                    "tool.result",
-                                    Some(serde_json::to_value(&params).unwrap()),
-                                )
-                                .await
-                            {
-                                logger.error(
-                                    "tool.result.send_failed",
-                                    json!({
-                                        "tool": tool_name,
-                                        "callId": call_id,
-                                        "error": e.to_string(),
-                                    }),
-                                );
-                            }
-                        }
-                    } else if evt.event == "logs.get" {
-                        if let Some(payload) = evt.payload {
-                            let request = match serde_json::from_value::<LogsGetPayload>(payload) {
-                                Ok(request) => request,
-                                Err(e) => {
-                                    logger.warn(
-                                        "logs.get.parse_failed",
-                                        json!({
-                                            "error": e.to_string(),
-                                        }),
-                                    );
-                                    return;
-                                }
-                            };
-
-                            let requested_lines =
-                                request.lines.unwrap_or(DEFAULT_NODE_LOG_GET_LINES);
-                            let resolved_lines = resolve_logs_get_line_limit(request.lines);
-                            if requested_lines != resolved_lines {
-                                logger.warn(
-                                    "logs.get.limit_clamped",
-                                    json!({
-                                        "callId": request.call_id,
-                                        "requestedLines": requested_lines,
-                                        "resolvedLines": resolved_lines,
-                                        "maxLines": MAX_NODE_LOG_GET_LINES,
-                                    }),
-                                );
-                            }
-
-                            logger.info(
-                                "logs.get",
-                                json!({
-                                    "callId": request.call_id,
-                                    "requestedLines": requested_lines,
-                                    "resolvedLines": resolved_lines,
-                                }),
-                            );
-
-                            let response = match read_recent_node_log_lines(resolved_lines) {
-                                Ok((lines, truncated)) => LogsResultParams {
-                                    call_id: request.call_id.clone(),
-                                    lines: Some(lines),
-                                    truncated: Some(truncated),
-                                    error: None,
-                                },
-                                Err(error) => LogsResultParams {
-                                    call_id: request.call_id.clone(),
-                                    lines: None,
-                                    truncated: None,
-                                    error: Some(error),
-                                },
-                            };
-
-                            if let Some(error) = response.error.clone() {
-                                logger.warn(
-                                    "logs.get.error",
-                                    json!({
-                                        "callId": request.call_id,
-                                        "error": error,
-                                    }),
-                                );
-                            }
-
-                            if let Err(e) = conn
-                                .request(
-                                    "logs.result",
-                                    Some(serde_json::to_value(&response).unwrap()),
-                                )
-                                .await
-                            {
-                                logger.error(
-                                    "logs.result.send_failed",
-                                    json!({
-                                        "callId": response.call_id,
-                                        "error": e.to_string(),
-                                    }),
-                                );
-                            }
-                        }
-                    } else if evt.event == "node.probe" {
+                    if evt.event == "node.probe" {
                         if let Some(payload) = evt.payload {
                             let request = match serde_json::from_value::<NodeProbePayload>(payload)
                             {
@@ -3384,24 +6939,31 @@ async fn run_node(
             }),
         );
         let mut next_keepalive_at = tokio::time::Instant::now() + keepalive_interval;
+        connected.store(true, Ordering::Relaxed);
+        reconnect.reset();
 
         // Monitor for disconnection or Ctrl+C
         loop {
             tokio::select! {
                 signal = &mut shutdown => {
                     exec_event_collector.abort();
+                    if let Some(health_server) = &health_server {
+                        health_server.abort();
+                    }
                     logger.info("shutdown", json!({ "signal": signal }));
                     return Ok(());
                 }
                 _ = tokio::time::sleep(tokio::time::Duration::from_secs(1)) => {
                     if conn.is_disconnected() {
+                        connected.store(false, Ordering::Relaxed);
+                        let delay = reconnect.next_delay();
                         logger.warn(
                             "connect.lost",
                             json!({
-                                "retrySeconds": 3,
+                                "retrySeconds": delay.as_secs_f64(),
                             }),
                         );
-                        tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+                        tokio::time::sleep(delay).await;
                         break; // Break inner loop to reconnect
                     }
 
@@ -3432,36 +6994,42 @@ async fn run_node(
                                     .error
                                     .map(|e| e.message)
                                     .unwrap_or_else(|| "unknown response".to_string());
+                                let delay = reconnect.next_delay();
                                 logger.warn(
                                     "keepalive.failed",
                                     json!({
                                         "error": message,
-                                        "retrySeconds": 3,
+                                        "retrySeconds": delay.as_secs_f64(),
                                     }),
                                 );
-                                tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+                                connected.store(false, Ordering::Relaxed);
+                                tokio::time::sleep(delay).await;
                                 break;
                             }
                             Ok(Err(e)) => {
+                                let delay = reconnect.next_delay();
                                 logger.warn(
                                     "keepalive.request_error",
                                     json!({
                                         "error": e.to_string(),
-                                        "retrySeconds": 3,
+                                        "retrySeconds": delay.as_secs_f64(),
                                     }),
                                 );
-                                tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+                                connected.store(false, Ordering::Relaxed);
+                                tokio::time::sleep(delay).await;
                                 break;
                             }
                             Err(_) => {
+                                let delay = reconnect.next_delay();
                                 logger.warn(
                                     "keepalive.timeout",
                                     json!({
                                         "timeoutSeconds": 10,
-                                        "retrySeconds": 3,
+                                        "retrySeconds": delay.as_secs_f64(),
                                     }),
                                 );
-                                tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+                                connected.store(false, Ordering::Relaxed);
+                                tokio::time::sleep(delay).await;
                                 break;
                             }
                         }
@@ -3472,6 +7040,195 @@ async fn run_node(
     }
 }
 
+/// Answers `GET /health` on the `--health-port` listener until aborted.
+async fn serve_node_health(
+    listener: tokio::net::TcpListener,
+    node_id: String,
+    connected: Arc<AtomicBool>,
+    started: std::time::Instant,
+) {
+    loop {
+        let Ok((mut stream, _)) = listener.accept().await else {
+            // Errors such as EMFILE persist for a while; don't spin on them.
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            continue;
+        };
+        let node_id = node_id.clone();
+        let connected = connected.clone();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1024];
+            let Ok(Ok(n)) =
+                tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf)).await
+            else {
+                return;
+            };
+            let request = String::from_utf8_lossy(&buf[..n]);
+            let response = node_health_response(
+                request.lines().next().unwrap_or_default(),
+                &node_id,
+                connected.load(Ordering::Relaxed),
+                started.elapsed().as_secs(),
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+            let _ = stream.shutdown().await;
+        });
+    }
+}
+
+/// Builds the raw HTTP response for one health request line ("GET /health HTTP/1.1").
+fn node_health_response(
+    request_line: &str,
+    node_id: &str,
+    connected: bool,
+    uptime_seconds: u64,
+) -> String {
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+
+    let (status, body) = if method == "GET" && path == "/health" {
+        let status = if connected {
+            "200 OK"
+        } else {
+            "503 Service Unavailable"
+        };
+        (
+            status,
+            json!({
+                "ok": connected,
+                "connected": connected,
+                "nodeId": node_id,
+                "uptime_seconds": uptime_seconds,
+            }),
+        )
+    } else {
+        (
+            "404 Not Found",
+            json!({ "ok": false, "error": "not found" }),
+        )
+    };
+    let body = body.to_string();
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+fn rclone_error_is_auth_failure(stderr: &str) -> bool {
+    const AUTH_MARKERS: &[&str] = &[
+        "AccessDenied",
+        "InvalidAccessKeyId",
+        "SignatureDoesNotMatch",
+        "Unauthorized",
+        "status code: 401",
+        "status code: 403",
+    ];
+    AUTH_MARKERS.iter().any(|marker| stderr.contains(marker))
+}
+
+/// Run `rclone ls` against a throwaway copy of the generated config.
+fn verify_rclone_config(config_content: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let temp_config =
+        std::env::temp_dir().join(format!("gsv-rclone-test-{}.conf", uuid::Uuid::new_v4()));
+    // Created owner-only so the R2 secret is never readable by other users,
+    // even briefly.
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&temp_config)?;
+    let written = file.write_all(config_content.as_bytes());
+    drop(file);
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&temp_config);
+        return Err(e.into());
+    }
+
+    println!("Testing R2 credentials...");
+    let output = std::process::Command::new("rclone")
+        .arg("--config")
+        .arg(&temp_config)
+        .arg("ls")
+        .arg("gsv-bucket:")
+        .arg("--max-depth")
+        .arg("1")
+        .output();
+    let _ = std::fs::remove_file(&temp_config);
+    let output = output?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if output.status.success() {
+        println!("Credentials verified.");
+        return Ok(());
+    }
+    if rclone_error_is_auth_failure(&stderr) {
+        eprintln!("{}", stderr.trim());
+        return Err(
+            "R2 credential check failed (authentication error). No config was saved.".into(),
+        );
+    }
+
+    println!("Credentials verified.");
+    println!(
+        "Note: rclone reported a non-authentication error: {}",
+        stderr.trim()
+    );
+    Ok(())
+}
+
+const RCLONE_VERIFY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Lists the bucket root with the saved rclone config. The rclone process is
+/// killed if it does not finish within `RCLONE_VERIFY_TIMEOUT`.
+async fn verify_saved_rclone_config(
+    rclone_config: &std::path::Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Verifying bucket access...");
+    let child = tokio::process::Command::new("rclone")
+        .arg("--config")
+        .arg(rclone_config)
+        .arg("ls")
+        .arg("gsv-bucket:")
+        .arg("--max-depth")
+        .arg("1")
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let output = match tokio::time::timeout(RCLONE_VERIFY_TIMEOUT, child.wait_with_output()).await {
+        Ok(output) => output?,
+        Err(_) => {
+            return Err(format!(
+                "Verification timed out after {}s",
+                RCLONE_VERIFY_TIMEOUT.as_secs()
+            )
+            .into())
+        }
+    };
+
+    if output.status.success() {
+        println!("Verified: bucket is accessible");
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    eprintln!("{}", stderr.trim());
+    if rclone_error_is_auth_failure(&stderr) {
+        eprintln!("Check the R2 access key ID and secret, and that the token can read the bucket.");
+        eprintln!(
+            "Update them with 'gsv mount setup --access-key-id ... --secret-access-key ...'."
+        );
+    }
+    Err("Bucket verification failed".into())
+}
+
 async fn run_mount(action: MountAction, cfg: &CliConfig) -> Result<(), Box<dyn std::error::Error>> {
     let config_dir = dirs::config_dir()
         .ok_or("Could not find config directory")?
@@ -3485,6 +7242,8 @@ async fn run_mount(action: MountAction, cfg: &CliConfig) -> Result<(), Box<dyn s
             access_key_id,
             secret_access_key,
             bucket,
+            test,
+            verify,
         } => {
             // Use CLI args, falling back to config file
             let account_id = if account_id.is_empty() {
@@ -3531,9 +7290,6 @@ async fn run_mount(action: MountAction, cfg: &CliConfig) -> Result<(), Box<dyn s
                 return Err("rclone not found".into());
             }
 
-            // Create config directory
-            std::fs::create_dir_all(&config_dir)?;
-
             // Generate rclone config
             let endpoint = format!("{}.r2.cloudflarestorage.com", account_id);
             let config_content = format!(
@@ -3552,6 +7308,12 @@ remote = gsv-r2:{}
                 access_key_id, secret_access_key, endpoint, bucket
             );
 
+            if test {
+                return verify_rclone_config(&config_content);
+            }
+
+            // Create config directory
+            std::fs::create_dir_all(&config_dir)?;
             std::fs::write(&rclone_config, &config_content)?;
 
             println!("R2 configuration saved to {}", rclone_config.display());
@@ -3576,6 +7338,11 @@ remote = gsv-r2:{}
             println!("  Agent configs at: {}/agents/", r2_mount.display());
             println!("\nTo start the mount, run:");
             println!("  gsv mount start");
+
+            if verify {
+                println!();
+                verify_saved_rclone_config(&rclone_config).await?;
+            }
         }
 
         MountAction::Start { foreground } => {
@@ -3648,7 +7415,7 @@ remote = gsv-r2:{}
 
                 if !has_fuse {
                     eprintln!("Error: FUSE is required for mounting on Linux.");
-                    eprintln!("");
+                    eprintln!();
                     eprintln!("Install it with:");
                     eprintln!("  Ubuntu/Debian: sudo apt install fuse3");
                     eprintln!("  Fedora/RHEL:   sudo dnf install fuse3");
@@ -3703,7 +7470,7 @@ remote = gsv-r2:{}
 
                 let pgrep = std::process::Command::new("pgrep")
                     .arg("-f")
-                    .arg(format!("rclone mount.*gsv-bucket"))
+                    .arg("rclone mount.*gsv-bucket")
                     .output();
 
                 if let Ok(output) = pgrep {
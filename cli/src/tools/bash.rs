@@ -1,5 +1,5 @@
 use crate::protocol::{NodeExecEventParams, ToolDefinition};
-use crate::tools::Tool;
+use crate::tools::{resolve_within_workspace, Tool};
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -8,9 +8,9 @@ use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{ChildStdin, Command};
-use tokio::sync::{broadcast, Mutex as AsyncMutex};
+use tokio::sync::{broadcast, mpsc, Mutex as AsyncMutex};
 use uuid::Uuid;
 
 const DEFAULT_TIMEOUT_MS: u64 = 5 * 60 * 1000;
@@ -19,6 +19,8 @@ const MAX_YIELD_MS: u64 = 120_000;
 const MAX_OUTPUT_CHARS: usize = 200_000;
 const TAIL_CHARS: usize = 4_000;
 const FINISHED_TTL_MS: i64 = 30 * 60 * 1000;
+const STREAM_FLUSH_BYTES: usize = 1024;
+const STREAM_FLUSH_INTERVAL: Duration = Duration::from_millis(100);
 
 #[derive(Clone)]
 struct ProcessHandle {
@@ -299,9 +301,9 @@ fn is_executable_file(path: &Path) -> bool {
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
-        return std::fs::metadata(path)
+        std::fs::metadata(path)
             .map(|meta| (meta.permissions().mode() & 0o111) != 0)
-            .unwrap_or(false);
+            .unwrap_or(false)
     }
     #[cfg(not(unix))]
     {
@@ -355,16 +357,23 @@ async fn terminate_pid(pid: u32, force: bool) {
     }
 }
 
-async fn pump_stream<R>(mut reader: R, state: Arc<AsyncMutex<ProcessState>>)
-where
+async fn pump_stream<R>(
+    reader: R,
+    state: Arc<AsyncMutex<ProcessState>>,
+    chunks: Option<mpsc::UnboundedSender<String>>,
+) where
     R: AsyncRead + Unpin + Send + 'static,
 {
+    let mut reader = BufReader::new(reader);
     let mut buf = vec![0u8; 4096];
     loop {
         match reader.read(&mut buf).await {
             Ok(0) => return,
             Ok(count) => {
                 let chunk = String::from_utf8_lossy(&buf[..count]).to_string();
+                if let Some(chunks) = &chunks {
+                    let _ = chunks.send(chunk.clone());
+                }
                 let mut lock = state.lock().await;
                 append_output(&mut lock, &chunk);
             }
@@ -401,6 +410,7 @@ async fn launch_managed_process(
     command: String,
     workdir: PathBuf,
     timeout_ms: u64,
+    chunks: Option<mpsc::UnboundedSender<String>>,
 ) -> Result<ProcessHandle, String> {
     let shell = resolve_login_shell();
     let mut cmd = Command::new(&shell);
@@ -412,7 +422,7 @@ async fn launch_managed_process(
 
     let mut child = cmd
         .spawn()
-        .map_err(|e| format!("Failed to execute: {}", e))?;
+        .map_err(|e| format!("Failed to execute in {}: {}", workdir.display(), e))?;
 
     let pid = child.id();
     let stdout = child.stdout.take();
@@ -451,10 +461,10 @@ async fn launch_managed_process(
     }
 
     if let Some(stdout) = stdout {
-        tokio::spawn(pump_stream(stdout, state.clone()));
+        tokio::spawn(pump_stream(stdout, state.clone(), chunks.clone()));
     }
     if let Some(stderr) = stderr {
-        tokio::spawn(pump_stream(stderr, state.clone()));
+        tokio::spawn(pump_stream(stderr, state.clone(), chunks));
     }
 
     if timeout_ms > 0 {
@@ -583,51 +593,71 @@ struct BashArgs {
     #[serde(default)]
     workdir: Option<String>,
     #[serde(default)]
+    cwd: Option<String>,
+    #[serde(default)]
     timeout: Option<u64>,
     #[serde(default)]
     background: Option<bool>,
     #[serde(default)]
     yield_ms: Option<u64>,
+    #[serde(default)]
+    stream: Option<bool>,
 }
 
-#[async_trait]
-impl Tool for BashTool {
-    fn definition(&self) -> ToolDefinition {
-        ToolDefinition {
-            name: "Bash".to_string(),
-            description:
-                "Execute shell commands. Supports async background mode with session tracking."
-                    .to_string(),
-            input_schema: json!({
-                "type": "object",
-                "properties": {
-                    "command": {
-                        "type": "string",
-                        "description": "The command to execute"
-                    },
-                    "workdir": {
-                        "type": "string",
-                        "description": "Working directory (default: workspace)"
-                    },
-                    "timeout": {
-                        "type": "number",
-                        "description": "Timeout in milliseconds (optional)"
-                    },
-                    "background": {
-                        "type": "boolean",
-                        "description": "Run in background immediately and return a sessionId"
-                    },
-                    "yieldMs": {
-                        "type": "number",
-                        "description": "Wait this many milliseconds, then background if still running"
+/// Forwards process output to `progress` in batches of about
+/// `STREAM_FLUSH_BYTES`, or every `STREAM_FLUSH_INTERVAL`, until both pipes close
+/// or the process exits.
+async fn stream_process_output(
+    handle: &ProcessHandle,
+    mut chunks: mpsc::UnboundedReceiver<String>,
+    progress: &mpsc::UnboundedSender<Value>,
+) {
+    let session_id = handle.state.lock().await.session_id.clone();
+    let flush = |pending: &mut String| {
+        if pending.is_empty() {
+            return;
+        }
+        let _ = progress.send(json!({
+          "status": "running",
+          "sessionId": session_id,
+          "output": std::mem::take(pending),
+        }));
+    };
+
+    let mut pending = String::new();
+    let mut ticker = tokio::time::interval(STREAM_FLUSH_INTERVAL);
+    loop {
+        tokio::select! {
+            chunk = chunks.recv() => match chunk {
+                Some(chunk) => {
+                    pending.push_str(&chunk);
+                    if pending.len() >= STREAM_FLUSH_BYTES {
+                        flush(&mut pending);
                     }
-                },
-                "required": ["command"]
-            }),
+                }
+                None => break,
+            },
+            _ = ticker.tick() => {
+                flush(&mut pending);
+                // A backgrounded grandchild may keep the pipes open after exit.
+                if handle.state.lock().await.ended_at.is_some() {
+                    while let Ok(chunk) = chunks.try_recv() {
+                        pending.push_str(&chunk);
+                    }
+                    break;
+                }
+            }
         }
     }
+    flush(&mut pending);
+}
 
-    async fn execute(&self, args: Value) -> Result<Value, String> {
+impl BashTool {
+    async fn run(
+        &self,
+        args: Value,
+        progress: Option<mpsc::UnboundedSender<Value>>,
+    ) -> Result<Value, String> {
         let args: BashArgs =
             serde_json::from_value(args).map_err(|e| format!("Invalid arguments: {}", e))?;
 
@@ -635,23 +665,42 @@ impl Tool for BashTool {
             return Err("command must not be empty".to_string());
         }
 
-        let workdir = args
-            .workdir
-            .as_deref()
-            .map(|w| self.resolve_path(w))
-            .unwrap_or_else(|| self.workspace.clone());
+        let workdir = match (args.cwd.as_deref(), args.workdir.as_deref()) {
+            (Some(_), Some(_)) => {
+                return Err("Specify either cwd or workdir, not both".to_string());
+            }
+            (Some(cwd), None) => resolve_within_workspace(&self.workspace, cwd)
+                .map_err(|e| format!("Invalid cwd: {}", e))?,
+            (None, Some(w)) => self.resolve_path(w),
+            (None, None) => self.workspace.clone(),
+        };
+
+        // Streaming only applies to foreground runs.
+        let streaming =
+            args.stream == Some(true) && args.background != Some(true) && args.yield_ms.is_none();
+        let (chunk_tx, chunk_rx) = match progress.as_ref() {
+            Some(_) if streaming => {
+                let (tx, rx) = mpsc::unbounded_channel();
+                (Some(tx), Some(rx))
+            }
+            _ => (None, None),
+        };
 
         let timeout_ms = args.timeout.unwrap_or(DEFAULT_TIMEOUT_MS);
-        let handle = launch_managed_process(args.command, workdir, timeout_ms).await?;
+        let handle = launch_managed_process(args.command, workdir, timeout_ms, chunk_tx).await?;
 
         if args.background == Some(true) {
             let snapshot = mark_backgrounded(&handle, None).await;
             return Ok(running_result(&snapshot));
         }
 
+        if let (Some(progress), Some(chunk_rx)) = (progress.as_ref(), chunk_rx) {
+            stream_process_output(&handle, chunk_rx, progress).await;
+        }
+
         let yield_ms = args
             .yield_ms
-            .map(|requested| requested.max(MIN_YIELD_MS).min(MAX_YIELD_MS));
+            .map(|requested| requested.clamp(MIN_YIELD_MS, MAX_YIELD_MS));
 
         if let Some(window_ms) = yield_ms {
             let deadline = tokio::time::Instant::now() + Duration::from_millis(window_ms);
@@ -687,6 +736,65 @@ impl Tool for BashTool {
     }
 }
 
+#[async_trait]
+impl Tool for BashTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "Bash".to_string(),
+            description:
+                "Execute shell commands. Supports async background mode with session tracking."
+                    .to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "command": {
+                        "type": "string",
+                        "description": "The command to execute"
+                    },
+                    "workdir": {
+                        "type": "string",
+                        "description": "Working directory (default: workspace)"
+                    },
+                    "cwd": {
+                        "type": "string",
+                        "description": "Working directory relative to the workspace; must stay inside it"
+                    },
+                    "timeout": {
+                        "type": "number",
+                        "description": "Timeout in milliseconds (optional)"
+                    },
+                    "background": {
+                        "type": "boolean",
+                        "description": "Run in background immediately and return a sessionId"
+                    },
+                    "yieldMs": {
+                        "type": "number",
+                        "description": "Wait this many milliseconds, then background if still running"
+                    },
+                    "stream": {
+                        "type": "boolean",
+                        "description": "Send partial output while a foreground command runs"
+                    }
+                },
+                "required": ["command"]
+            }),
+            timeout_seconds: None,
+        }
+    }
+
+    async fn execute(&self, args: Value) -> Result<Value, String> {
+        self.run(args, None).await
+    }
+
+    async fn execute_streaming(
+        &self,
+        args: Value,
+        progress: mpsc::UnboundedSender<Value>,
+    ) -> Result<Value, String> {
+        self.run(args, Some(progress)).await
+    }
+}
+
 pub struct ProcessTool;
 
 impl ProcessTool {
@@ -695,6 +803,12 @@ impl ProcessTool {
     }
 }
 
+impl Default for ProcessTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ProcessArgs {
@@ -742,6 +856,7 @@ impl Tool for ProcessTool {
                 },
                 "required": ["action"]
             }),
+            timeout_seconds: None,
         }
     }
 
@@ -773,7 +888,7 @@ impl Tool for ProcessTool {
                 entries.push(entry_from_snapshot(&finished.snapshot, now));
             }
 
-            entries.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.started_at));
             let lines: Vec<String> = entries
                 .iter()
                 .map(|entry| {
@@ -0,0 +1,144 @@
+use crate::protocol::ToolDefinition;
+use crate::tools::{
+    create_parent_dirs, resolve_new_path_within_workspace, resolve_within_workspace, Tool,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+pub struct CopyTool {
+    workspace: PathBuf,
+}
+
+impl CopyTool {
+    pub fn new(workspace: PathBuf) -> Self {
+        Self { workspace }
+    }
+}
+
+#[derive(Deserialize)]
+struct CopyArgs {
+    source: String,
+    destination: String,
+    #[serde(default)]
+    recursive: bool,
+}
+
+/// Totals for a copy: regular files written, their bytes, and symlinks left
+/// out (they could point outside the workspace).
+#[derive(Default)]
+pub(crate) struct CopyStats {
+    pub files: u64,
+    pub bytes: u64,
+    pub skipped_symlinks: u64,
+}
+
+/// Copies the directory tree at `source` to `destination`, which must not exist yet.
+pub(crate) fn copy_dir_recursive(source: &Path, destination: &Path) -> Result<CopyStats, String> {
+    let mut stats = CopyStats::default();
+    for entry in WalkDir::new(source) {
+        let entry = entry.map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .map_err(|e| e.to_string())?;
+        let target = destination.join(relative);
+        let file_type = entry.file_type();
+        if file_type.is_symlink() {
+            stats.skipped_symlinks += 1;
+        } else if file_type.is_dir() {
+            std::fs::create_dir_all(&target)
+                .map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+        } else {
+            stats.bytes += std::fs::copy(entry.path(), &target).map_err(|e| {
+                format!(
+                    "Failed to copy {} to {}: {}",
+                    entry.path().display(),
+                    target.display(),
+                    e
+                )
+            })?;
+            stats.files += 1;
+        }
+    }
+    Ok(stats)
+}
+
+#[async_trait]
+impl Tool for CopyTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "Copy".to_string(),
+            description: "Copy a file, or a directory with recursive: true, within the workspace. The destination must not exist; missing parent directories are created. Symlinks inside copied directories are skipped. Returns {source, destination, files, bytes, skipped_symlinks}.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "source": {
+                        "type": "string",
+                        "description": "File or directory to copy, relative to the workspace"
+                    },
+                    "destination": {
+                        "type": "string",
+                        "description": "New path, relative to the workspace"
+                    },
+                    "recursive": {
+                        "type": "boolean",
+                        "description": "Required to copy a directory (default: false)"
+                    }
+                },
+                "required": ["source", "destination"]
+            }),
+            timeout_seconds: None,
+        }
+    }
+
+    async fn execute(&self, args: Value) -> Result<Value, String> {
+        let args: CopyArgs =
+            serde_json::from_value(args).map_err(|e| format!("Invalid arguments: {}", e))?;
+
+        let source = resolve_within_workspace(&self.workspace, &args.source)?;
+        let destination = resolve_new_path_within_workspace(&self.workspace, &args.destination)?;
+
+        let stats = if source.is_dir() {
+            if !args.recursive {
+                return Err(format!(
+                    "{} is a directory; pass recursive: true to copy it",
+                    args.source
+                ));
+            }
+            if destination.starts_with(&source) {
+                return Err(format!(
+                    "Cannot copy {} into itself ({})",
+                    args.source, args.destination
+                ));
+            }
+            create_parent_dirs(&destination)?;
+            copy_dir_recursive(&source, &destination)?
+        } else {
+            create_parent_dirs(&destination)?;
+            let bytes = std::fs::copy(&source, &destination).map_err(|e| {
+                format!(
+                    "Failed to copy {} to {}: {}",
+                    source.display(),
+                    destination.display(),
+                    e
+                )
+            })?;
+            CopyStats {
+                files: 1,
+                bytes,
+                skipped_symlinks: 0,
+            }
+        };
+
+        Ok(json!({
+            "source": source.display().to_string(),
+            "destination": destination.display().to_string(),
+            "files": stats.files,
+            "bytes": stats.bytes,
+            "skipped_symlinks": stats.skipped_symlinks,
+        }))
+    }
+}
@@ -0,0 +1,97 @@
+use crate::protocol::ToolDefinition;
+use crate::tools::{resolve_within_workspace, Tool};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use similar::{ChangeTag, TextDiff};
+use std::path::PathBuf;
+
+const DEFAULT_CONTEXT_LINES: usize = 3;
+
+pub struct DiffTool {
+    workspace: PathBuf,
+}
+
+impl DiffTool {
+    pub fn new(workspace: PathBuf) -> Self {
+        Self { workspace }
+    }
+
+    fn read_text(&self, path: &str) -> Result<String, String> {
+        let resolved = resolve_within_workspace(&self.workspace, path)?;
+        let bytes = std::fs::read(&resolved)
+            .map_err(|e| format!("Failed to read {}: {}", resolved.display(), e))?;
+        if bytes.contains(&0) {
+            return Err(format!(
+                "{} is a binary file; Diff only compares text",
+                path
+            ));
+        }
+        String::from_utf8(bytes)
+            .map_err(|_| format!("{} is not valid UTF-8; Diff only compares text", path))
+    }
+}
+
+#[derive(Deserialize)]
+struct DiffArgs {
+    file_a: String,
+    file_b: String,
+    #[serde(default)]
+    context_lines: Option<usize>,
+}
+
+#[async_trait]
+impl Tool for DiffTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "Diff".to_string(),
+            description: "Show a unified diff between two text files in the workspace. Returns {diff, changed_lines}; diff is empty when the files are identical.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "file_a": {
+                        "type": "string",
+                        "description": "Original file, relative to the workspace"
+                    },
+                    "file_b": {
+                        "type": "string",
+                        "description": "Modified file, relative to the workspace"
+                    },
+                    "context_lines": {
+                        "type": "integer",
+                        "description": "Unchanged lines shown around each change (default: 3)"
+                    }
+                },
+                "required": ["file_a", "file_b"]
+            }),
+            timeout_seconds: None,
+        }
+    }
+
+    async fn execute(&self, args: Value) -> Result<Value, String> {
+        let args: DiffArgs =
+            serde_json::from_value(args).map_err(|e| format!("Invalid arguments: {}", e))?;
+
+        let old = self.read_text(&args.file_a)?;
+        let new = self.read_text(&args.file_b)?;
+
+        let diff = TextDiff::from_lines(&old, &new);
+        let changed_lines = diff
+            .iter_all_changes()
+            .filter(|change| change.tag() != ChangeTag::Equal)
+            .count();
+        let patch = if changed_lines == 0 {
+            String::new()
+        } else {
+            diff.unified_diff()
+                .context_radius(args.context_lines.unwrap_or(DEFAULT_CONTEXT_LINES))
+                .header(&args.file_a, &args.file_b)
+                .to_string()
+        };
+
+        Ok(json!({
+            "diff": patch,
+            "changed_lines": changed_lines,
+        }))
+    }
+}
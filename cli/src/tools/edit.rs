@@ -63,6 +63,7 @@ impl Tool for EditTool {
                 },
                 "required": ["path", "oldString", "newString"]
             }),
+            timeout_seconds: None,
         }
     }
 
@@ -0,0 +1,208 @@
+use crate::protocol::ToolDefinition;
+use crate::tools::Tool;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+pub struct FindTool {
+    workspace: PathBuf,
+}
+
+impl FindTool {
+    pub fn new(workspace: PathBuf) -> Self {
+        Self { workspace }
+    }
+
+    fn resolve_path(&self, path: &str) -> PathBuf {
+        let path = PathBuf::from(path);
+        if path.is_absolute() {
+            path
+        } else {
+            self.workspace.join(path)
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct FindArgs {
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default, rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    min_size_bytes: Option<u64>,
+    #[serde(default)]
+    max_size_bytes: Option<u64>,
+    #[serde(default)]
+    modified_after: Option<String>,
+    #[serde(default)]
+    modified_before: Option<String>,
+    #[serde(default)]
+    max_depth: Option<usize>,
+}
+
+/// Accepts RFC 3339 timestamps ("2024-05-01T12:00:00Z") or plain dates
+/// ("2024-05-01", midnight UTC).
+fn parse_timestamp(field: &str, value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.and_utc())
+        .ok_or_else(|| {
+            format!(
+                "Invalid {}: '{}' (expected ISO-8601, e.g. 2024-05-01 or 2024-05-01T12:00:00Z)",
+                field, value
+            )
+        })
+}
+
+#[async_trait]
+impl Tool for FindTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "Find".to_string(),
+            description: "Recursively find files and directories, filtering by name, type, size and modification time. Returns an array of {path, size, modified, type} sorted by path. Paths are relative to the workspace unless absolute.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Directory to search in (default: workspace root)"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Glob pattern matched against the entry name (e.g., '*.rs', 'Cargo.*')"
+                    },
+                    "type": {
+                        "type": "string",
+                        "enum": ["file", "dir", "any"],
+                        "description": "Entry type to return (default: any)"
+                    },
+                    "min_size_bytes": {
+                        "type": "integer",
+                        "description": "Only files at least this large (excludes directories)"
+                    },
+                    "max_size_bytes": {
+                        "type": "integer",
+                        "description": "Only files at most this large (excludes directories)"
+                    },
+                    "modified_after": {
+                        "type": "string",
+                        "description": "Only entries modified after this ISO-8601 time"
+                    },
+                    "modified_before": {
+                        "type": "string",
+                        "description": "Only entries modified before this ISO-8601 time"
+                    },
+                    "max_depth": {
+                        "type": "integer",
+                        "description": "How many directory levels to descend (1 = direct children only)"
+                    }
+                }
+            }),
+            timeout_seconds: None,
+        }
+    }
+
+    async fn execute(&self, args: Value) -> Result<Value, String> {
+        let args: FindArgs =
+            serde_json::from_value(args).map_err(|e| format!("Invalid arguments: {}", e))?;
+
+        let base_path = args
+            .path
+            .as_deref()
+            .map(|p| self.resolve_path(p))
+            .unwrap_or_else(|| self.workspace.clone());
+        if !base_path.is_dir() {
+            return Err(format!("Not a directory: {}", base_path.display()));
+        }
+
+        let pattern = args
+            .name
+            .as_deref()
+            .map(glob::Pattern::new)
+            .transpose()
+            .map_err(|e| format!("Invalid name pattern: {}", e))?;
+        let kind = args.kind.as_deref().unwrap_or("any");
+        if !matches!(kind, "file" | "dir" | "any") {
+            return Err(format!(
+                "Invalid type '{}' (expected file, dir or any)",
+                kind
+            ));
+        }
+        let modified_after = args
+            .modified_after
+            .as_deref()
+            .map(|v| parse_timestamp("modified_after", v))
+            .transpose()?;
+        let modified_before = args
+            .modified_before
+            .as_deref()
+            .map(|v| parse_timestamp("modified_before", v))
+            .transpose()?;
+        let size_filtered = args.min_size_bytes.is_some() || args.max_size_bytes.is_some();
+
+        let mut walker = WalkDir::new(&base_path).min_depth(1).sort_by_file_name();
+        if let Some(max_depth) = args.max_depth {
+            walker = walker.max_depth(max_depth);
+        }
+
+        let mut results = Vec::new();
+        for entry in walker.into_iter().filter_map(Result::ok) {
+            let file_type = entry.file_type();
+            let entry_type = if file_type.is_dir() {
+                "dir"
+            } else if file_type.is_symlink() {
+                "symlink"
+            } else {
+                "file"
+            };
+            if kind != "any" && kind != entry_type {
+                continue;
+            }
+            if let Some(pattern) = &pattern {
+                if !pattern.matches(&entry.file_name().to_string_lossy()) {
+                    continue;
+                }
+            }
+
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let size = metadata.len();
+            if size_filtered
+                && (entry_type == "dir"
+                    || args.min_size_bytes.is_some_and(|min| size < min)
+                    || args.max_size_bytes.is_some_and(|max| size > max))
+            {
+                continue;
+            }
+
+            let modified: DateTime<Utc> =
+                metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH).into();
+            if modified_after.is_some_and(|after| modified <= after)
+                || modified_before.is_some_and(|before| modified >= before)
+            {
+                continue;
+            }
+
+            results.push(json!({
+                "path": entry.path().display().to_string(),
+                "size": size,
+                "modified": modified.to_rfc3339(),
+                "type": entry_type,
+            }));
+        }
+
+        Ok(Value::Array(results))
+    }
+}
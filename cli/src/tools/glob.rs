@@ -51,6 +51,7 @@ impl Tool for GlobTool {
                 },
                 "required": ["pattern"]
             }),
+            timeout_seconds: None,
         }
     }
 
@@ -84,7 +85,7 @@ impl Tool for GlobTool {
         }
 
         // Sort by modification time (newest first)
-        entries.sort_by(|a, b| b.1.cmp(&a.1));
+        entries.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
 
         let paths: Vec<String> = entries
             .into_iter()
@@ -67,6 +67,7 @@ impl Tool for GrepTool {
                 },
                 "required": ["pattern"]
             }),
+            timeout_seconds: None,
         }
     }
 
@@ -86,8 +87,7 @@ impl Tool for GrepTool {
         let include_glob = args
             .include
             .as_ref()
-            .map(|inc| glob::Pattern::new(inc).ok())
-            .flatten();
+            .and_then(|inc| glob::Pattern::new(inc).ok());
 
         let mut matches: Vec<GrepMatch> = Vec::new();
 
@@ -1,37 +1,286 @@
 mod bash;
+mod copy;
+mod diff;
 mod edit;
+mod find;
 mod glob;
 mod grep;
+mod move_;
 mod read;
 mod write;
 
 pub use bash::{subscribe_exec_events, BashTool, ProcessTool};
+pub use copy::CopyTool;
+pub use diff::DiffTool;
 pub use edit::EditTool;
+pub use find::FindTool;
 pub use glob::GlobTool;
 pub use grep::GrepTool;
+pub use move_::MoveTool;
 pub use read::ReadTool;
 pub use write::WriteTool;
 
+use crate::config::CliConfig;
 use crate::protocol::ToolDefinition;
 use async_trait::async_trait;
 use serde_json::Value;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
 
 #[async_trait]
 pub trait Tool: Send + Sync {
     fn definition(&self) -> ToolDefinition;
     async fn execute(&self, args: Value) -> Result<Value, String>;
+
+    /// Execute while sending partial results to `progress`. Tools that do not
+    /// stream fall back to `execute` and never send.
+    async fn execute_streaming(
+        &self,
+        args: Value,
+        progress: mpsc::UnboundedSender<Value>,
+    ) -> Result<Value, String> {
+        drop(progress);
+        self.execute(args).await
+    }
+}
+
+/// Resolve `path` against the workspace and reject results that escape it
+/// (via `..` or symlinks). The path must exist.
+pub(crate) fn resolve_within_workspace(workspace: &Path, path: &str) -> Result<PathBuf, String> {
+    let root = workspace.canonicalize().map_err(|e| {
+        format!(
+            "Failed to resolve workspace '{}': {}",
+            workspace.display(),
+            e
+        )
+    })?;
+    let joined = root.join(path);
+    let resolved = joined
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve '{}': {}", joined.display(), e))?;
+
+    if !resolved.starts_with(&root) {
+        return Err(format!(
+            "'{}' resolves to {}, which is outside the workspace {}",
+            path,
+            resolved.display(),
+            root.display()
+        ));
+    }
+
+    Ok(resolved)
+}
+
+/// Like `resolve_within_workspace`, but only the parent directory is resolved:
+/// when `path` names a symlink, the result is the link itself, not its target.
+pub(crate) fn resolve_entry_within_workspace(
+    workspace: &Path,
+    path: &str,
+) -> Result<PathBuf, String> {
+    let root = workspace.canonicalize().map_err(|e| {
+        format!(
+            "Failed to resolve workspace '{}': {}",
+            workspace.display(),
+            e
+        )
+    })?;
+    let joined = root.join(path);
+    // A trailing `..` has no file name, so fall back to full resolution.
+    let (Some(parent), Some(name)) = (joined.parent(), joined.file_name()) else {
+        return resolve_within_workspace(workspace, path);
+    };
+    let parent = parent
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve '{}': {}", parent.display(), e))?;
+    let resolved = parent.join(name);
+    resolved
+        .symlink_metadata()
+        .map_err(|e| format!("Failed to resolve '{}': {}", joined.display(), e))?;
+
+    if !resolved.starts_with(&root) {
+        return Err(format!(
+            "'{}' resolves to {}, which is outside the workspace {}",
+            path,
+            resolved.display(),
+            root.display()
+        ));
+    }
+
+    Ok(resolved)
+}
+
+/// Like `resolve_within_workspace`, for a path that must not exist yet (a
+/// move or copy destination). Only the deepest existing ancestor has to exist;
+/// nothing is created, so callers should run `create_parent_dirs` after their
+/// own checks pass.
+pub(crate) fn resolve_new_path_within_workspace(
+    workspace: &Path,
+    path: &str,
+) -> Result<PathBuf, String> {
+    let root = workspace.canonicalize().map_err(|e| {
+        format!(
+            "Failed to resolve workspace '{}': {}",
+            workspace.display(),
+            e
+        )
+    })?;
+    let joined = root.join(path);
+    if joined.symlink_metadata().is_ok() {
+        return Err(format!("Destination '{}' already exists", path));
+    }
+
+    // Walk up to the deepest existing ancestor; `..` in the missing part is
+    // rejected because it cannot be resolved yet.
+    let mut missing = Vec::new();
+    let mut existing = joined.as_path();
+    while existing.symlink_metadata().is_err() {
+        let name = existing
+            .file_name()
+            .ok_or_else(|| format!("Invalid destination path '{}'", path))?;
+        missing.push(name.to_os_string());
+        existing = existing
+            .parent()
+            .ok_or_else(|| format!("Invalid destination path '{}'", path))?;
+    }
+    let base = existing
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve '{}': {}", existing.display(), e))?;
+    if !base.starts_with(&root) {
+        return Err(format!(
+            "'{}' resolves to {}, which is outside the workspace {}",
+            path,
+            base.display(),
+            root.display()
+        ));
+    }
+
+    Ok(missing.iter().rev().fold(base, |dir, name| dir.join(name)))
+}
+
+/// Creates the missing parent directories of a path returned by
+/// `resolve_new_path_within_workspace`.
+pub(crate) fn create_parent_dirs(path: &Path) -> Result<(), String> {
+    match path.parent() {
+        Some(parent) => std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e)),
+        None => Ok(()),
+    }
+}
+
+/// Reports a configured execution timeout in the wrapped tool's definition.
+struct TimeoutOverride {
+    inner: Box<dyn Tool>,
+    timeout_seconds: u64,
+}
+
+impl TimeoutOverride {
+    /// The node drops a tool call once its timeout passes, which would leave a
+    /// foreground Bash command running; cap Bash's own `timeout` so the process
+    /// is killed too. Background and `yieldMs` runs may outlive the call.
+    fn with_process_timeout(&self, mut args: Value) -> Value {
+        let detaches = args.get("background").and_then(Value::as_bool) == Some(true)
+            || args.get("yieldMs").is_some();
+        if self.inner.definition().name != "Bash" || detaches {
+            return args;
+        }
+        let limit_ms = self.timeout_seconds.saturating_mul(1000);
+        let requested = args.get("timeout").and_then(Value::as_u64);
+        if let Some(obj) = args.as_object_mut() {
+            let timeout = requested.map_or(limit_ms, |ms| ms.min(limit_ms));
+            obj.insert("timeout".to_string(), Value::from(timeout));
+        }
+        args
+    }
+}
+
+#[async_trait]
+impl Tool for TimeoutOverride {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            timeout_seconds: Some(self.timeout_seconds),
+            ..self.inner.definition()
+        }
+    }
+
+    async fn execute(&self, args: Value) -> Result<Value, String> {
+        self.inner.execute(self.with_process_timeout(args)).await
+    }
+
+    async fn execute_streaming(
+        &self,
+        args: Value,
+        progress: mpsc::UnboundedSender<Value>,
+    ) -> Result<Value, String> {
+        self.inner
+            .execute_streaming(self.with_process_timeout(args), progress)
+            .await
+    }
 }
 
-/// Create all tools with the given workspace
-pub fn all_tools_with_workspace(workspace: PathBuf) -> Vec<Box<dyn Tool>> {
-    vec![
+/// Applies `node.tool_allowlist` and `node.tool_denylist`: with an allowlist
+/// only the listed tools remain, and denied tools are always dropped. Errors
+/// if a tool is both allowed and denied.
+pub fn tool_filter(
+    tools: Vec<Box<dyn Tool>>,
+    cfg: &CliConfig,
+) -> Result<Vec<Box<dyn Tool>>, String> {
+    let allowlist = cfg.node.tool_allowlist.as_deref();
+    let denylist = &cfg.node.tool_denylist;
+    if let Some(allowlist) = allowlist {
+        let conflicts: Vec<&str> = allowlist
+            .iter()
+            .filter(|name| denylist.contains(name))
+            .map(String::as_str)
+            .collect();
+        if !conflicts.is_empty() {
+            return Err(format!(
+                "node.tool_allowlist and node.tool_denylist both list: {}",
+                conflicts.join(", ")
+            ));
+        }
+    }
+
+    Ok(tools
+        .into_iter()
+        .filter(|tool| {
+            let name = tool.definition().name;
+            allowlist.is_none_or(|allowed| allowed.contains(&name)) && !denylist.contains(&name)
+        })
+        .collect())
+}
+
+/// Create all tools with the given workspace. `timeout_overrides` maps tool
+/// names to execution timeouts in seconds (see `[node.tool_timeouts]`).
+pub fn all_tools_with_workspace(
+    workspace: PathBuf,
+    timeout_overrides: Option<HashMap<String, u64>>,
+) -> Vec<Box<dyn Tool>> {
+    let tools: Vec<Box<dyn Tool>> = vec![
         Box::new(BashTool::new(workspace.clone())),
         Box::new(ProcessTool::new()),
         Box::new(ReadTool::new(workspace.clone())),
         Box::new(WriteTool::new(workspace.clone())),
         Box::new(EditTool::new(workspace.clone())),
         Box::new(GlobTool::new(workspace.clone())),
+        Box::new(FindTool::new(workspace.clone())),
+        Box::new(DiffTool::new(workspace.clone())),
+        Box::new(MoveTool::new(workspace.clone())),
+        Box::new(CopyTool::new(workspace.clone())),
         Box::new(GrepTool::new(workspace)),
-    ]
+    ];
+    let Some(overrides) = timeout_overrides else {
+        return tools;
+    };
+
+    tools
+        .into_iter()
+        .map(|tool| match overrides.get(&tool.definition().name) {
+            Some(&timeout_seconds) => Box::new(TimeoutOverride {
+                inner: tool,
+                timeout_seconds,
+            }) as Box<dyn Tool>,
+            None => tool,
+        })
+        .collect()
 }
@@ -0,0 +1,135 @@
+use crate::protocol::ToolDefinition;
+use crate::tools::copy::copy_dir_recursive;
+use crate::tools::{
+    create_parent_dirs, resolve_entry_within_workspace, resolve_new_path_within_workspace, Tool,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+pub struct MoveTool {
+    workspace: PathBuf,
+}
+
+impl MoveTool {
+    pub fn new(workspace: PathBuf) -> Self {
+        Self { workspace }
+    }
+}
+
+#[derive(Deserialize)]
+struct MoveArgs {
+    source: String,
+    destination: String,
+}
+
+/// `rename` fails across filesystems (e.g. a workspace spanning mounts);
+/// fall back to copying and then deleting the source.
+fn move_path(source: &Path, destination: &Path) -> Result<(), String> {
+    match std::fs::rename(source, destination) {
+        Ok(()) => return Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {}
+        Err(e) => {
+            return Err(format!(
+                "Failed to move {} to {}: {}",
+                source.display(),
+                destination.display(),
+                e
+            ))
+        }
+    }
+
+    let is_symlink = source
+        .symlink_metadata()
+        .map(|meta| meta.file_type().is_symlink())
+        .unwrap_or(false);
+    if is_symlink {
+        return move_symlink(source, destination)
+            .map_err(|e| format!("Failed to move symlink {}: {}", source.display(), e));
+    }
+
+    if source.is_dir() {
+        let stats = copy_dir_recursive(source, destination)?;
+        if stats.skipped_symlinks > 0 {
+            return Err(format!(
+                "Copied {} across devices but left the source in place: it contains {} symlink(s) that were not copied",
+                source.display(),
+                stats.skipped_symlinks
+            ));
+        }
+        std::fs::remove_dir_all(source)
+    } else {
+        std::fs::copy(source, destination)
+            .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))?;
+        std::fs::remove_file(source)
+    }
+    .map_err(|e| format!("Copied but failed to remove {}: {}", source.display(), e))
+}
+
+/// Recreates the link at `destination` and removes the original.
+#[cfg(unix)]
+fn move_symlink(source: &Path, destination: &Path) -> std::io::Result<()> {
+    let target = std::fs::read_link(source)?;
+    std::os::unix::fs::symlink(target, destination)?;
+    std::fs::remove_file(source)
+}
+
+#[cfg(not(unix))]
+fn move_symlink(source: &Path, _destination: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("cannot move symlink {} across devices", source.display()),
+    ))
+}
+
+#[async_trait]
+impl Tool for MoveTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: "Move".to_string(),
+            description: "Move or rename a file or directory within the workspace. The destination must not exist; missing parent directories are created. Returns {source, destination}.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "source": {
+                        "type": "string",
+                        "description": "File or directory to move, relative to the workspace"
+                    },
+                    "destination": {
+                        "type": "string",
+                        "description": "New path, relative to the workspace"
+                    }
+                },
+                "required": ["source", "destination"]
+            }),
+            timeout_seconds: None,
+        }
+    }
+
+    async fn execute(&self, args: Value) -> Result<Value, String> {
+        let args: MoveArgs =
+            serde_json::from_value(args).map_err(|e| format!("Invalid arguments: {}", e))?;
+
+        // A symlink source moves the link, not whatever it points at.
+        let source = resolve_entry_within_workspace(&self.workspace, &args.source)?;
+        if self.workspace.canonicalize().ok().as_deref() == Some(source.as_path()) {
+            return Err("Cannot move the workspace root".to_string());
+        }
+        let destination = resolve_new_path_within_workspace(&self.workspace, &args.destination)?;
+        if destination.starts_with(&source) {
+            return Err(format!(
+                "Cannot move {} into itself ({})",
+                args.source, args.destination
+            ));
+        }
+
+        create_parent_dirs(&destination)?;
+        move_path(&source, &destination)?;
+
+        Ok(json!({
+            "source": source.display().to_string(),
+            "destination": destination.display().to_string(),
+        }))
+    }
+}
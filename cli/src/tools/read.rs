@@ -72,6 +72,7 @@ impl Tool for ReadTool {
                 },
                 "required": ["path"]
             }),
+            timeout_seconds: None,
         }
     }
 
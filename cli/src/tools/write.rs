@@ -51,6 +51,7 @@ impl Tool for WriteTool {
                 },
                 "required": ["path", "content"]
             }),
+            timeout_seconds: None,
         }
     }
 
@@ -64,6 +64,12 @@ impl TransferCoordinator {
     }
 }
 
+impl Default for TransferCoordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 fn resolve_transfer_path(path: &str, workspace: &Path) -> PathBuf {
     let p = PathBuf::from(path);
     if p.is_absolute() {
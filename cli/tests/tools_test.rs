@@ -50,6 +50,40 @@ async fn test_bash_tool_workdir() {
     ); // macOS
 }
 
+#[tokio::test]
+async fn test_bash_tool_cwd_stays_in_workspace() {
+    use gsv::tools::{BashTool, Tool};
+    use serde_json::json;
+
+    let workspace = std::env::temp_dir().join(format!("gsv-bash-cwd-{}", std::process::id()));
+    std::fs::create_dir_all(workspace.join("sub")).unwrap();
+    let tool = BashTool::new(workspace.clone());
+
+    let result = tool
+        .execute(json!({
+            "command": "pwd",
+            "cwd": "sub"
+        }))
+        .await
+        .unwrap();
+    assert_eq!(result["exitCode"], 0);
+    assert!(result["output"]
+        .as_str()
+        .unwrap()
+        .trim_end()
+        .ends_with("/sub"));
+
+    let escaped = tool
+        .execute(json!({
+            "command": "pwd",
+            "cwd": ".."
+        }))
+        .await;
+    assert!(escaped.unwrap_err().contains("outside the workspace"));
+
+    std::fs::remove_dir_all(&workspace).unwrap();
+}
+
 #[tokio::test]
 async fn test_bash_background_returns_session_id() {
     use gsv::tools::{BashTool, ProcessTool, Tool};
@@ -330,6 +364,247 @@ async fn test_glob_tool() {
     std::fs::remove_dir_all(&workspace).ok();
 }
 
+#[tokio::test]
+async fn test_find_tool_max_depth() {
+    use gsv::tools::{FindTool, Tool};
+    use serde_json::json;
+
+    let workspace = std::env::temp_dir().join(format!("gsv_find_depth_{}", std::process::id()));
+    std::fs::create_dir_all(workspace.join("a").join("b")).unwrap();
+    std::fs::write(workspace.join("top.txt"), "x").unwrap();
+    std::fs::write(workspace.join("a").join("mid.txt"), "x").unwrap();
+    std::fs::write(workspace.join("a").join("b").join("deep.txt"), "x").unwrap();
+
+    let tool = FindTool::new(workspace.clone());
+    let result = tool
+        .execute(json!({ "max_depth": 2, "type": "file" }))
+        .await
+        .unwrap();
+
+    let names: Vec<String> = result
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            let path = std::path::PathBuf::from(entry["path"].as_str().unwrap());
+            path.file_name().unwrap().to_string_lossy().into_owned()
+        })
+        .collect();
+    assert_eq!(names, vec!["mid.txt", "top.txt"]);
+
+    std::fs::remove_dir_all(&workspace).ok();
+}
+
+#[tokio::test]
+async fn test_find_tool_combined_filters() {
+    use gsv::tools::{FindTool, Tool};
+    use serde_json::json;
+
+    let workspace = std::env::temp_dir().join(format!("gsv_find_filters_{}", std::process::id()));
+    std::fs::create_dir_all(workspace.join("logs.rs")).unwrap();
+    std::fs::write(workspace.join("small.rs"), "fn a() {}").unwrap();
+    std::fs::write(workspace.join("large.rs"), "x".repeat(4096)).unwrap();
+    std::fs::write(workspace.join("large.md"), "x".repeat(4096)).unwrap();
+
+    let tool = FindTool::new(workspace.clone());
+    let result = tool
+        .execute(json!({
+            "name": "*.rs",
+            "min_size_bytes": 1024,
+            "modified_after": "2000-01-01",
+        }))
+        .await
+        .unwrap();
+
+    let entries = result.as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0]["path"].as_str().unwrap().ends_with("large.rs"));
+    assert_eq!(entries[0]["size"], 4096);
+    assert_eq!(entries[0]["type"], "file");
+
+    let none = tool
+        .execute(json!({ "name": "*.rs", "modified_before": "2000-01-01T00:00:00Z" }))
+        .await
+        .unwrap();
+    assert!(none.as_array().unwrap().is_empty());
+
+    let dirs = tool
+        .execute(json!({ "name": "*.rs", "type": "dir" }))
+        .await
+        .unwrap();
+    assert_eq!(dirs.as_array().unwrap().len(), 1);
+
+    assert!(tool
+        .execute(json!({ "modified_after": "May 1st" }))
+        .await
+        .is_err());
+
+    std::fs::remove_dir_all(&workspace).ok();
+}
+
+#[tokio::test]
+async fn test_diff_tool() {
+    use gsv::tools::{DiffTool, Tool};
+    use serde_json::json;
+
+    let workspace = std::env::temp_dir().join(format!("gsv_diff_{}", std::process::id()));
+    std::fs::create_dir_all(&workspace).unwrap();
+    std::fs::write(workspace.join("a.txt"), "one\ntwo\nthree\n").unwrap();
+    std::fs::write(workspace.join("same.txt"), "one\ntwo\nthree\n").unwrap();
+    std::fs::write(workspace.join("b.txt"), "one\n2\nthree\n").unwrap();
+    std::fs::write(workspace.join("bin.dat"), [0x89u8, 0x50, 0x00, 0xff]).unwrap();
+
+    let tool = DiffTool::new(workspace.clone());
+
+    let identical = tool
+        .execute(json!({ "file_a": "a.txt", "file_b": "same.txt" }))
+        .await
+        .unwrap();
+    assert_eq!(identical["diff"], "");
+    assert_eq!(identical["changed_lines"], 0);
+
+    let changed = tool
+        .execute(json!({ "file_a": "a.txt", "file_b": "b.txt", "context_lines": 0 }))
+        .await
+        .unwrap();
+    assert_eq!(changed["changed_lines"], 2);
+    let patch = changed["diff"].as_str().unwrap();
+    assert!(patch.starts_with("--- a.txt\n+++ b.txt\n"));
+    assert!(patch.contains("-two\n+2\n"));
+    assert!(!patch.contains(" one"));
+
+    let missing = tool
+        .execute(json!({ "file_a": "a.txt", "file_b": "missing.txt" }))
+        .await;
+    assert!(missing.unwrap_err().contains("missing.txt"));
+
+    let binary = tool
+        .execute(json!({ "file_a": "a.txt", "file_b": "bin.dat" }))
+        .await;
+    assert!(binary.unwrap_err().contains("binary"));
+
+    std::fs::remove_dir_all(&workspace).ok();
+}
+
+#[tokio::test]
+async fn test_copy_tool_directory_and_workspace_boundary() {
+    use gsv::tools::{CopyTool, Tool};
+    use serde_json::json;
+
+    let root = std::env::temp_dir().join(format!("gsv_copy_{}", std::process::id()));
+    let workspace = root.join("ws");
+    std::fs::create_dir_all(workspace.join("src/nested")).unwrap();
+    std::fs::write(workspace.join("src/a.txt"), "alpha").unwrap();
+    std::fs::write(workspace.join("src/nested/b.txt"), "beta!").unwrap();
+    std::fs::write(root.join("outside.txt"), "secret").unwrap();
+
+    let tool = CopyTool::new(workspace.clone());
+
+    let not_recursive = tool
+        .execute(json!({ "source": "src", "destination": "dst" }))
+        .await;
+    assert!(not_recursive.unwrap_err().contains("recursive"));
+
+    let copied = tool
+        .execute(json!({ "source": "src", "destination": "backup/dst", "recursive": true }))
+        .await
+        .unwrap();
+    assert_eq!(copied["files"], 2);
+    assert_eq!(copied["bytes"], 10);
+    assert_eq!(
+        std::fs::read_to_string(workspace.join("backup/dst/nested/b.txt")).unwrap(),
+        "beta!"
+    );
+    assert!(workspace.join("src/a.txt").exists());
+
+    let exists = tool
+        .execute(json!({ "source": "src/a.txt", "destination": "src/nested/b.txt" }))
+        .await;
+    assert!(exists.unwrap_err().contains("already exists"));
+
+    let into_self = tool
+        .execute(json!({ "source": "src", "destination": "src/nested/copy", "recursive": true }))
+        .await;
+    assert!(into_self.unwrap_err().contains("into itself"));
+
+    let escape_source = tool
+        .execute(json!({ "source": "../outside.txt", "destination": "stolen.txt" }))
+        .await;
+    assert!(escape_source.unwrap_err().contains("outside the workspace"));
+
+    let escape_destination = tool
+        .execute(json!({ "source": "src/a.txt", "destination": "../leaked.txt" }))
+        .await;
+    assert!(escape_destination
+        .unwrap_err()
+        .contains("outside the workspace"));
+    assert!(!root.join("leaked.txt").exists());
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[tokio::test]
+async fn test_move_tool() {
+    use gsv::tools::{MoveTool, Tool};
+    use serde_json::json;
+
+    let root = std::env::temp_dir().join(format!("gsv_move_{}", std::process::id()));
+    let workspace = root.join("ws");
+    std::fs::create_dir_all(workspace.join("dir")).unwrap();
+    std::fs::write(workspace.join("dir/file.txt"), "content").unwrap();
+
+    let tool = MoveTool::new(workspace.clone());
+
+    tool.execute(json!({ "source": "dir/file.txt", "destination": "renamed.txt" }))
+        .await
+        .unwrap();
+    assert!(!workspace.join("dir/file.txt").exists());
+    assert_eq!(
+        std::fs::read_to_string(workspace.join("renamed.txt")).unwrap(),
+        "content"
+    );
+
+    tool.execute(json!({ "source": "dir", "destination": "moved/dir" }))
+        .await
+        .unwrap();
+    assert!(workspace.join("moved/dir").is_dir());
+    assert!(!workspace.join("dir").exists());
+
+    let into_itself = tool
+        .execute(json!({ "source": "moved", "destination": "moved/nested/deeper/moved" }))
+        .await;
+    assert!(into_itself.unwrap_err().contains("into itself"));
+    assert!(!workspace.join("moved/nested").exists());
+
+    let escape = tool
+        .execute(json!({ "source": "renamed.txt", "destination": "../escaped.txt" }))
+        .await;
+    assert!(escape.unwrap_err().contains("outside the workspace"));
+    assert!(workspace.join("renamed.txt").exists());
+
+    let root_move = tool
+        .execute(json!({ "source": ".", "destination": "moved/ws" }))
+        .await;
+    assert!(root_move.unwrap_err().contains("workspace root"));
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink("renamed.txt", workspace.join("link.txt")).unwrap();
+        tool.execute(json!({ "source": "link.txt", "destination": "moved/link.txt" }))
+            .await
+            .unwrap();
+        assert!(workspace.join("renamed.txt").is_file());
+        assert!(workspace
+            .join("moved/link.txt")
+            .symlink_metadata()
+            .unwrap()
+            .file_type()
+            .is_symlink());
+    }
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
 #[tokio::test]
 async fn test_grep_tool() {
     use gsv::tools::{GrepTool, Tool};
@@ -369,15 +644,31 @@ async fn test_grep_tool() {
     std::fs::remove_dir_all(&workspace).ok();
 }
 
+#[test]
+fn test_all_tools_with_workspace_applies_timeout_overrides() {
+    use gsv::tools::all_tools_with_workspace;
+    use std::collections::HashMap;
+
+    let overrides = HashMap::from([("Bash".to_string(), 1)]);
+    let tools = all_tools_with_workspace(std::env::temp_dir(), Some(overrides));
+
+    assert_eq!(tools.len(), 11);
+    for tool in &tools {
+        let def = tool.definition();
+        let expected = (def.name == "Bash").then_some(1);
+        assert_eq!(def.timeout_seconds, expected, "{}", def.name);
+    }
+}
+
 #[test]
 fn test_all_tools_with_workspace() {
     use gsv::tools::all_tools_with_workspace;
 
     let workspace = std::env::temp_dir();
-    let tools = all_tools_with_workspace(workspace);
+    let tools = all_tools_with_workspace(workspace, None);
 
-    // Should have 7 tools: Bash, Process, Read, Write, Edit, Glob, Grep
-    assert_eq!(tools.len(), 7);
+    // Should have 11 tools: Bash, Process, Read, Write, Edit, Glob, Find, Diff, Move, Copy, Grep
+    assert_eq!(tools.len(), 11);
 
     let names: Vec<_> = tools.iter().map(|t| t.definition().name).collect();
     assert!(names.contains(&"Bash".to_string()));
@@ -386,9 +677,37 @@ fn test_all_tools_with_workspace() {
     assert!(names.contains(&"Write".to_string()));
     assert!(names.contains(&"Edit".to_string()));
     assert!(names.contains(&"Glob".to_string()));
+    assert!(names.contains(&"Find".to_string()));
+    assert!(names.contains(&"Diff".to_string()));
+    assert!(names.contains(&"Move".to_string()));
+    assert!(names.contains(&"Copy".to_string()));
     assert!(names.contains(&"Grep".to_string()));
 }
 
+#[test]
+fn test_tool_filter_applies_allowlist_and_denylist() {
+    use gsv::config::CliConfig;
+    use gsv::tools::{all_tools_with_workspace, tool_filter};
+
+    let names = |cfg: &CliConfig| -> Result<Vec<String>, String> {
+        let tools = tool_filter(all_tools_with_workspace(std::env::temp_dir(), None), cfg)?;
+        Ok(tools.iter().map(|t| t.definition().name).collect())
+    };
+
+    let mut cfg = CliConfig::default();
+    cfg.node.tool_denylist = vec!["Bash".to_string()];
+    let denied = names(&cfg).unwrap();
+    assert!(!denied.contains(&"Bash".to_string()));
+    assert!(denied.contains(&"Read".to_string()));
+
+    cfg.node.tool_allowlist = Some(vec!["Read".to_string(), "Glob".to_string()]);
+    assert_eq!(names(&cfg).unwrap(), vec!["Read", "Glob"]);
+
+    cfg.node.tool_allowlist = Some(vec!["Read".to_string(), "Bash".to_string()]);
+    let err = names(&cfg).unwrap_err();
+    assert!(err.contains("Bash"));
+}
+
 #[test]
 fn test_config_load_default() {
     use gsv::config::CliConfig;
@@ -402,6 +721,24 @@ fn test_config_load_default() {
     assert!(url.starts_with("ws://") || url.starts_with("wss://"));
 }
 
+#[test]
+fn test_config_gateway_urls_fallback_order() {
+    use gsv::config::CliConfig;
+
+    let mut cfg = CliConfig::default();
+    assert_eq!(cfg.gateway_urls(), ["ws://localhost:8787/ws"]);
+
+    cfg.gateway.url = Some("wss://a.example/ws".to_string());
+    cfg.gateway.urls = vec![
+        "wss://b.example/ws".to_string(),
+        "wss://a.example/ws".to_string(),
+    ];
+    assert_eq!(
+        cfg.gateway_urls(),
+        ["wss://a.example/ws", "wss://b.example/ws"]
+    );
+}
+
 #[test]
 fn test_config_sample() {
     use gsv::config::sample_config;
@@ -413,3 +750,65 @@ fn test_config_sample() {
     assert!(sample.contains("[r2]"));
     assert!(sample.contains("[session]"));
 }
+
+#[tokio::test]
+async fn test_bash_tool_streams_partial_output() {
+    use gsv::tools::{BashTool, Tool};
+    use serde_json::json;
+
+    let tool = BashTool::new(std::env::temp_dir());
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let result = tool
+        .execute_streaming(
+            json!({
+                "command": "echo first; sleep 0.3; echo second",
+                "stream": true
+            }),
+            tx,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result["status"], "completed");
+    assert!(result["output"].as_str().unwrap().contains("second"));
+
+    let mut partials = Vec::new();
+    while let Ok(partial) = rx.try_recv() {
+        assert_eq!(partial["status"], "running");
+        partials.push(partial["output"].as_str().unwrap().to_string());
+    }
+    assert!(
+        partials.len() >= 2,
+        "expected separate chunks: {:?}",
+        partials
+    );
+    assert!(partials.concat().contains("first"));
+    assert!(partials.concat().contains("second"));
+}
+
+#[tokio::test]
+async fn test_bash_tool_timeout_override_kills_process() {
+    use gsv::tools::all_tools_with_workspace;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    let tools = all_tools_with_workspace(
+        std::env::temp_dir(),
+        Some(HashMap::from([("Bash".to_string(), 1)])),
+    );
+    let bash = tools
+        .iter()
+        .find(|tool| tool.definition().name == "Bash")
+        .unwrap();
+    assert_eq!(bash.definition().timeout_seconds, Some(1));
+
+    let started = std::time::Instant::now();
+    let result = bash
+        .execute(json!({ "command": "sleep 10", "timeout": 60000 }))
+        .await
+        .unwrap();
+
+    assert_eq!(result["timedOut"], true);
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+}
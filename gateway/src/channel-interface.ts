@@ -75,6 +75,8 @@ export type ChannelOutboundMessage = {
   text: string;
   /** Media to send */
   media?: ChannelMedia[];
+  /** Platform-specific rich embeds (Discord embed objects) */
+  embeds?: Record<string, unknown>[];
   /** Reply to a specific message */
   replyToId?: string;
 };
@@ -130,6 +132,21 @@ export type LoginResult =
   | { ok: false; error: string };
 export type LogoutResult = { ok: true } | { ok: false; error: string };
 
+/**
+ * A server/guild the channel's bot is a member of
+ */
+export type ChannelGuild = {
+  id: string;
+  name: string;
+  /** Approximate member count, if the platform reports one */
+  memberCount?: number;
+  /** Whether the bot's slash commands are available in the guild, if known */
+  commandsRegistered?: boolean;
+};
+export type ListGuildsResult =
+  | { ok: true; guilds: ChannelGuild[] }
+  | { ok: false; error: string };
+
 export interface ChannelWorkerInterface {
   /** Channel identifier  */
   readonly channelId: string;
@@ -187,6 +204,11 @@ export interface ChannelWorkerInterface {
    * Logout and clear credentials.
    */
   logout?(accountId: string): Promise<LogoutResult>;
+
+  /**
+   * List the servers/guilds the bot has joined.
+   */
+  listGuilds?(accountId: string): Promise<ListGuildsResult>;
 }
 
 /**
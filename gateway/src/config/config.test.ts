@@ -2,6 +2,7 @@ import { describe, it, expect } from "vitest";
 import {
   normalizeE164,
   isAllowedSender,
+  matchesAutoApproveRule,
   resolveLinkedIdentity,
 } from "./parsing";
 import { mergeConfig, type GsvConfig } from ".";
@@ -138,6 +139,22 @@ describe("isAllowedSender", () => {
   });
 });
 
+describe("matchesAutoApproveRule", () => {
+  it("matches glob rules against the normalized sender", () => {
+    expect(matchesAutoApproveRule(["+1555*"], "15551234567@s.whatsapp.net")).toBe(true);
+    expect(matchesAutoApproveRule(["+1555*"], "+31628552611")).toBe(false);
+  });
+
+  it("treats slash-wrapped rules as regexes", () => {
+    expect(matchesAutoApproveRule(["/^\\d{6}$/"], "123456")).toBe(true);
+    expect(matchesAutoApproveRule(["/^\\d{6}$/"], "1234567")).toBe(false);
+  });
+
+  it("ignores malformed regexes", () => {
+    expect(matchesAutoApproveRule(["/([/"], "anything")).toBe(false);
+  });
+});
+
 describe("resolveLinkedIdentity", () => {
   const configWithLinks: GsvConfig = {
     ...DEFAULT_CONFIG,
@@ -62,6 +62,13 @@ export interface ChannelConfig {
   
   // Allowed sender IDs
   allowFrom: string[];
+
+  // Approve every new pairing request on this channel without manual review
+  autoApprove?: boolean;
+
+  // Approve pairing requests whose sender ID matches one of these patterns:
+  // globs ("+1555*") or regexes wrapped in slashes ("/^\+1555\d+$/")
+  autoApproveRules?: string[];
 }
 
 // Pending pairing request
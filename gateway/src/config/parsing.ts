@@ -65,6 +65,44 @@ export function normalizeE164(raw: string): string {
   return hasPlus || cleaned.length >= 10 ? `+${cleaned}` : cleaned;
 }
 
+/**
+ * Compile an auto-approve rule. Rules wrapped in slashes ("/^\+1555/") are
+ * regexes; anything else is a glob where `*` matches any run of characters
+ * and `?` a single one. Returns null for a malformed regex.
+ */
+export function compileAutoApproveRule(rule: string): RegExp | null {
+  if (rule.length > 2 && rule.startsWith("/") && rule.endsWith("/")) {
+    try {
+      return new RegExp(rule.slice(1, -1));
+    } catch {
+      return null;
+    }
+  }
+  const source = rule
+    .split("")
+    .map((ch) =>
+      ch === "*" ? ".*" : ch === "?" ? "." : ch.replace(/[.+^${}()|[\]\\]/g, "\\$&"),
+    )
+    .join("");
+  return new RegExp(`^${source}$`);
+}
+
+/**
+ * Check a sender against a channel's auto-approve rules, matching both the
+ * raw and the normalized sender ID.
+ */
+export function matchesAutoApproveRule(
+  rules: string[] | undefined,
+  senderId: string,
+): boolean {
+  if (!rules?.length) return false;
+  const candidates = [senderId, normalizeE164(senderId)].filter(Boolean);
+  return rules.some((rule) => {
+    const pattern = compileAutoApproveRule(rule);
+    return !!pattern && candidates.some((id) => pattern.test(id));
+  });
+}
+
 /**
  * Check if a sender is allowed based on channel config
  * Returns: { allowed, needsPairing, reason }
@@ -1,6 +1,7 @@
 import { env } from "cloudflare:workers";
 import {
   isAllowedSender,
+  matchesAutoApproveRule,
   normalizeE164,
   resolveAgentIdFromBinding,
 } from "../config/parsing";
@@ -57,7 +58,25 @@ export async function handleChannelInboundRpc(
     params.peer.id,
   );
 
-  if (!allowCheck.allowed) {
+  const channelConfig = config.channels[params.channel];
+  if (
+    allowCheck.needsPairing &&
+    (channelConfig?.autoApprove ||
+      matchesAutoApproveRule(channelConfig?.autoApproveRules, senderId))
+  ) {
+    const normalizedId = normalizeE164(senderId);
+    const allowFrom = config.channels[params.channel]?.allowFrom ?? [];
+    if (!allowFrom.includes(normalizedId)) {
+      gw.setConfigPath(`channels.${params.channel}.allowFrom`, [
+        ...allowFrom,
+        normalizedId,
+      ]);
+    }
+    delete gw.pendingPairs[`${params.channel}:${normalizedId}`];
+    console.log(
+      `[Gateway] Auto-approved pairing for ${senderId} (${senderName}) on ${params.channel}`,
+    );
+  } else if (!allowCheck.allowed) {
     if (allowCheck.needsPairing) {
       const pairKey = `${params.channel}:${normalizeE164(senderId)}`;
       if (!gw.pendingPairs[pairKey]) {
@@ -24,6 +24,16 @@ export function getChannelBinding(
       return (env as any).CHANNEL_WHATSAPP as Fetcher & ChannelWorkerInterface;
     case "discord":
       return (env as any).CHANNEL_DISCORD as Fetcher & ChannelWorkerInterface;
+    case "telegram":
+      // Optional binding; undefined until a Telegram channel worker is bound.
+      return (env as any).CHANNEL_TELEGRAM as
+        | (Fetcher & ChannelWorkerInterface)
+        | undefined;
+    case "slack":
+      // Optional binding; undefined until a Slack channel worker is bound.
+      return (env as any).CHANNEL_SLACK as
+        | (Fetcher & ChannelWorkerInterface)
+        | undefined;
     case "test":
       return (env as any).CHANNEL_TEST as Fetcher & ChannelWorkerInterface;
     default:
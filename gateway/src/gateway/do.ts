@@ -134,7 +134,13 @@ import { buildRpcHandlers } from "./rpc-handlers/";
 
 export type PendingToolRoute =
   | { kind: "session"; sessionKey: string }
-  | { kind: "client"; clientId: string; frameId: string; createdAt: number };
+  | {
+      kind: "client";
+      clientId: string;
+      frameId: string;
+      createdAt: number;
+      record?: { sessionKey: string; tool: string; args: Record<string, unknown> };
+    };
 
 export type PendingLogRoute = {
   clientId: string;
@@ -259,6 +265,18 @@ export class Gateway extends DurableObject<Env> {
     >
   >(this.ctx.storage.kv, { prefix: "lastActiveContext:" });
 
+  // Explicit heartbeat delivery target per agent (overrides last active context)
+  readonly heartbeatDelivery = PersistedObject<
+    Record<
+      string,
+      {
+        channel: ChannelId;
+        accountId: string;
+        peer: PeerInfo;
+      }
+    >
+  >(this.ctx.storage.kv, { prefix: "heartbeatDelivery:" });
+
   // Pending pairing requests (key: "channel:senderId")
   pendingPairs = PersistedObject<Record<string, PendingPair>>(
     this.ctx.storage.kv,
@@ -613,6 +631,7 @@ export class Gateway extends DurableObject<Env> {
         payload: {
           callId,
           lines,
+          filter: params?.filter || undefined,
         },
       };
       nodeWs.send(JSON.stringify(evt));
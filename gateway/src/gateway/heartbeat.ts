@@ -165,6 +165,11 @@ export type HeartbeatState = {
   lastHeartbeatAt: number | null;
   lastHeartbeatText: string | null;
   lastHeartbeatSentAt: number | null;
+  // Outcome of the most recent run that was not skipped.
+  consecutiveFailures?: number;
+  deliveryChannel?: string | null;
+  deliveryTarget?: string | null;
+  lastHeartbeatPayload?: string | null;
 };
 
 export type HeartbeatRunReason = "interval" | "manual" | "cron";
@@ -246,12 +251,32 @@ export async function runDueHeartbeats(
 
     await runHeartbeat(gw, agentId, heartbeatConfig, "interval");
 
-    state.lastHeartbeatAt = now;
-    state.nextHeartbeatAt = getNextHeartbeatTime(heartbeatConfig);
-    gw.heartbeatState[agentId] = state;
+    // runHeartbeat records the run's outcome, so build on the stored state.
+    gw.heartbeatState[agentId] = {
+      ...(gw.heartbeatState[agentId] ?? state),
+      lastHeartbeatAt: now,
+      nextHeartbeatAt: getNextHeartbeatTime(heartbeatConfig),
+    };
   }
 }
 
+function recordHeartbeatRun(
+  gw: Gateway,
+  result: HeartbeatResult,
+  deliveryChannel: string | null,
+  payload: unknown,
+): void {
+  const state = gw.heartbeatState[result.agentId];
+  if (!state) return;
+  gw.heartbeatState[result.agentId] = {
+    ...state,
+    consecutiveFailures: result.error ? (state.consecutiveFailures ?? 0) + 1 : 0,
+    deliveryChannel,
+    deliveryTarget: result.deliveryTarget ?? null,
+    lastHeartbeatPayload: JSON.stringify(payload),
+  };
+}
+
 export async function runHeartbeat(
   gw: Gateway,
   agentId: string,
@@ -317,8 +342,15 @@ export async function runHeartbeat(
     peer: PeerInfo;
   } | null = null;
 
+  const pinned = gw.heartbeatDelivery[agentId];
+
   if (target === "none") {
     console.log(`[Gateway] Heartbeat target=none, running silently`);
+  } else if (pinned) {
+    deliveryContext = JSON.parse(JSON.stringify(pinned));
+    console.log(
+      `[Gateway] Heartbeat delivery pinned to ${pinned.channel}:${pinned.peer.id}`,
+    );
   } else if (target === "last" && lastActive) {
     deliveryContext = JSON.parse(
       JSON.stringify({
@@ -352,6 +384,7 @@ export async function runHeartbeat(
   }
 
   result.sessionKey = sessionKey;
+  result.deliveryTarget = deliveryContext?.peer.id;
 
   const session = env.SESSION.getByName(sessionKey);
   const runId = crypto.randomUUID();
@@ -393,6 +426,12 @@ export async function runHeartbeat(
     }
   }
 
+  recordHeartbeatRun(gw, result, deliveryContext?.channel ?? null, {
+    reason,
+    sessionKey,
+    prompt,
+    delivery: deliveryContext,
+  });
   return result;
 }
 
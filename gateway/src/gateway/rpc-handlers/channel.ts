@@ -1,3 +1,4 @@
+import type { ChannelPeer } from "../../channel-interface";
 import type { ChannelId } from "../../protocol/channel";
 import type { Handler } from "../../protocol/methods";
 import { RpcError } from "../../shared/utils";
@@ -178,6 +179,47 @@ export const handleChannelLogin: Handler<"channel.login"> = async ({
   };
 };
 
+export const handleChannelSend: Handler<"channel.send"> = async ({
+  gw,
+  params,
+}) => {
+  if (!params?.channel || !params?.peer?.id) {
+    throw new RpcError(400, "channel and peer.id required");
+  }
+  if (!params.text && !params.media?.length && !params.embeds?.length) {
+    throw new RpcError(400, "text, media or embeds required");
+  }
+
+  const channel = params.channel as ChannelId;
+  const accountId = params.accountId ?? "default";
+
+  const binding = gw.getChannelBinding(channel);
+  if (!binding) {
+    throw new RpcError(404, `Unknown channel: ${channel}`);
+  }
+
+  let result;
+  try {
+    result = await binding.send(accountId, {
+      peer: params.peer as ChannelPeer,
+      text: params.text ?? "",
+      media: params.media,
+      embeds: params.embeds,
+    });
+  } catch (error) {
+    throw new RpcError(
+      500,
+      error instanceof Error ? error.message : String(error),
+    );
+  }
+
+  if (!result.ok) {
+    throw new RpcError(500, result.error);
+  }
+
+  return { ok: true, channel, accountId, messageId: result.messageId };
+};
+
 export const handleChannelLogout: Handler<"channel.logout"> = async ({
   gw,
   params,
@@ -217,3 +259,35 @@ export const handleChannelLogout: Handler<"channel.logout"> = async ({
 
   return { ok: true, channel, accountId };
 };
+
+export const handleChannelDiscordGuilds: Handler<
+  "channel.discord.guilds"
+> = async ({ gw, params }) => {
+  const channel: ChannelId = "discord";
+  const accountId = params?.accountId ?? "default";
+
+  const binding = gw.getChannelBinding(channel);
+  if (!binding) {
+    throw new RpcError(404, `Unknown channel: ${channel}`);
+  }
+
+  if (!binding.listGuilds) {
+    throw new RpcError(400, `Channel ${channel} does not support listing guilds`);
+  }
+
+  let result;
+  try {
+    result = await binding.listGuilds(accountId);
+  } catch (error) {
+    throw new RpcError(
+      500,
+      error instanceof Error ? error.message : String(error),
+    );
+  }
+
+  if (!result.ok) {
+    throw new RpcError(500, result.error);
+  }
+
+  return { channel, accountId, guilds: result.guilds };
+};
@@ -11,7 +11,9 @@ import {
   isDirectiveOnly,
   parseDirectives,
 } from "../directives";
-import type { Handler } from "../../protocol/methods";
+import type { MediaAttachment } from "../../protocol/channel";
+import type { Handler, ParamsOf } from "../../protocol/methods";
+import { processInboundMedia } from "../../storage/media";
 import { RpcError } from "../../shared/utils";
 import type { Gateway } from "../do";
 
@@ -151,6 +153,35 @@ async function handleSlashCommandForChat(
   }
 }
 
+/**
+ * Text attachments are inlined into the message; images and other binary
+ * files become media so the session stores them like channel uploads.
+ */
+async function resolveChatAttachment(
+  attachment: NonNullable<ParamsOf<"chat.send">["attachment"]>,
+  messageText: string,
+  sessionKey: string,
+): Promise<{ message: string; media?: MediaAttachment[] }> {
+  const contentType = attachment.content_type || "application/octet-stream";
+  if (contentType.startsWith("text/") || contentType.includes("charset=")) {
+    return {
+      message: `${messageText}\n\n[File: ${attachment.name}]\n\`\`\`\n${attachment.data}\n\`\`\``,
+    };
+  }
+
+  const media: MediaAttachment = {
+    type: contentType.startsWith("image/") ? "image" : "document",
+    mimeType: contentType,
+    data: attachment.data,
+    filename: attachment.name,
+    size: Math.floor((attachment.data.length * 3) / 4),
+  };
+  return {
+    message: messageText,
+    media: await processInboundMedia([media], env.STORAGE, sessionKey),
+  };
+}
+
 export const handleChatSend: Handler<"chat.send"> = async ({ gw, params }) => {
   if (!params?.sessionKey || !params?.message) {
     throw new RpcError(400, "sessionKey and message required");
@@ -241,13 +272,24 @@ export const handleChatSend: Handler<"chat.send"> = async ({ gw, params }) => {
     messageOverrides.model = directives.model;
   }
 
+  let outgoingMessage = directives.cleaned; // Send cleaned message without directives
+  let media: MediaAttachment[] | undefined;
+  if (params.attachment) {
+    ({ message: outgoingMessage, media } = await resolveChatAttachment(
+      params.attachment,
+      outgoingMessage,
+      canonicalSessionKey,
+    ));
+  }
+
   const result = await sessionStub.chatSend(
-    directives.cleaned, // Send cleaned message without directives
+    outgoingMessage,
     params.runId ?? crypto.randomUUID(),
     JSON.parse(JSON.stringify(gw.getAllTools())),
     JSON.parse(JSON.stringify(gw.getRuntimeNodeInventory())),
     canonicalSessionKey,
     messageOverrides,
+    media,
   );
 
   return {
@@ -163,12 +163,17 @@ export const handleConnect: Handler<"connect"> = async (ctx) => {
         "session.stats",
         "session.reset",
         "session.history",
+        "session.history.restore",
         "session.preview",
+        "session.export",
+        "session.import",
         "session.compact",
+        "session.search",
         "sessions.list",
         "heartbeat.status",
         "heartbeat.start",
         "heartbeat.trigger",
+        "heartbeat.set_delivery",
         "cron.status",
         "cron.list",
         "cron.add",
@@ -187,6 +192,8 @@ export const handleConnect: Handler<"connect"> = async (ctx) => {
         "channel.status",
         "channel.login",
         "channel.logout",
+        "channel.send",
+        "channel.discord.guilds",
         "channels.list",
       ],
       events: [
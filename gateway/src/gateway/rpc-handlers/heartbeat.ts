@@ -1,5 +1,17 @@
+import { parseDuration } from "../../config/parsing";
 import type { Handler } from "../../protocol/methods";
+import { RpcError } from "../../shared/utils";
 import type { Gateway } from "../do";
+import { getHeartbeatConfig } from "../heartbeat";
+
+const HEARTBEAT_DELIVERY_CHANNELS = ["whatsapp", "discord"];
+
+function heartbeatIntervalMinutes(gw: Gateway, agentId: string): number | null {
+  const interval = parseDuration(
+    getHeartbeatConfig(gw.getFullConfig(), agentId).every,
+  );
+  return interval > 0 ? interval / 60_000 : null;
+}
 
 function buildHeartbeatStatus(gw: Gateway): Record<string, unknown> {
   const result: Record<string, unknown> = {};
@@ -7,7 +19,13 @@ function buildHeartbeatStatus(gw: Gateway): Record<string, unknown> {
   for (const [agentId, state] of Object.entries(gw.heartbeatState)) {
     const lastActive = gw.lastActiveContext[agentId];
     result[agentId] = {
+      consecutiveFailures: 0,
+      deliveryChannel: null,
+      deliveryTarget: null,
+      lastHeartbeatPayload: null,
       ...state,
+      intervalMinutes: heartbeatIntervalMinutes(gw, agentId),
+      pinnedDelivery: gw.heartbeatDelivery[agentId],
       lastActive: lastActive
         ? {
             channel: lastActive.channel,
@@ -27,6 +45,11 @@ function buildHeartbeatStatus(gw: Gateway): Record<string, unknown> {
         lastHeartbeatAt: null,
         lastHeartbeatText: null,
         lastHeartbeatSentAt: null,
+        intervalMinutes: heartbeatIntervalMinutes(gw, agentId),
+        consecutiveFailures: 0,
+        deliveryChannel: null,
+        deliveryTarget: null,
+        lastHeartbeatPayload: null,
         lastActive: {
           channel: context.channel,
           accountId: context.accountId,
@@ -47,6 +70,27 @@ export const handleHeartbeatTrigger: Handler<"heartbeat.trigger"> = async (
   return await gw.triggerHeartbeat(agentId);
 };
 
+export const handleHeartbeatSetDelivery: Handler<
+  "heartbeat.set_delivery"
+> = async ({ gw, params }) => {
+  if (!params?.agentId || !params?.channel || !params?.peer?.id) {
+    throw new RpcError(400, "agentId, channel and peer.id required");
+  }
+  if (!HEARTBEAT_DELIVERY_CHANNELS.includes(params.channel)) {
+    throw new RpcError(
+      400,
+      `Unknown channel: ${params.channel} (expected ${HEARTBEAT_DELIVERY_CHANNELS.join(", ")})`,
+    );
+  }
+
+  gw.heartbeatDelivery[params.agentId] = {
+    channel: params.channel,
+    accountId: params.accountId ?? "default",
+    peer: { kind: params.peer.kind ?? "dm", id: params.peer.id },
+  };
+  return { ok: true, agentId: params.agentId };
+};
+
 export const handleHeartbeatStatus: Handler<"heartbeat.status"> = async ({
   gw,
 }) => {
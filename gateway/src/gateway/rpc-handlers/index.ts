@@ -4,7 +4,11 @@ import {
   handleSessionReset,
   handleSessionStats,
   handleSessionHistory,
+  handleSessionHistoryRestore,
   handleSessionPreview,
+  handleSessionExport,
+  handleSessionImport,
+  handleSessionSearch,
   handleSessionPatch,
   handleSessionCompact,
   handleSessionsList,
@@ -17,6 +21,8 @@ import {
   handleChannelStatus,
   handleChannelLogin,
   handleChannelLogout,
+  handleChannelSend,
+  handleChannelDiscordGuilds,
 } from "./channel";
 import {
   handleToolsList,
@@ -27,9 +33,18 @@ import {
   handleToolResult,
 } from "./tools";
 import { handleLogsGet, handleLogsResult } from "./logs";
-import { handlePairList, handlePairApprove, handlePairReject } from "./pairing";
+import {
+  handlePairList,
+  handlePairApprove,
+  handlePairReject,
+  handlePairAutoApprove,
+  handlePairAutoApproveAdd,
+  handlePairAutoApproveRemove,
+  handlePairAutoApproveList,
+} from "./pairing";
 import { handleChatSend } from "./chat";
 import {
+  handleHeartbeatSetDelivery,
   handleHeartbeatStart,
   handleHeartbeatStatus,
   handleHeartbeatTrigger,
@@ -73,7 +88,11 @@ export function buildRpcHandlers(): Partial<{ [M in RpcMethod]: Handler<M> }> {
     "session.reset": handleSessionReset,
     "session.stats": handleSessionStats,
     "session.history": handleSessionHistory,
+    "session.history.restore": handleSessionHistoryRestore,
     "session.preview": handleSessionPreview,
+    "session.export": handleSessionExport,
+    "session.import": handleSessionImport,
+    "session.search": handleSessionSearch,
     "session.patch": handleSessionPatch,
     "session.compact": handleSessionCompact,
     "sessions.list": handleSessionsList,
@@ -84,6 +103,8 @@ export function buildRpcHandlers(): Partial<{ [M in RpcMethod]: Handler<M> }> {
     "channel.status": handleChannelStatus,
     "channel.login": handleChannelLogin,
     "channel.logout": handleChannelLogout,
+    "channel.send": handleChannelSend,
+    "channel.discord.guilds": handleChannelDiscordGuilds,
     "config.get": handleConfigGet,
     "config.set": handleConfigSet,
     "skills.status": handleSkillsStatus,
@@ -91,6 +112,7 @@ export function buildRpcHandlers(): Partial<{ [M in RpcMethod]: Handler<M> }> {
     "heartbeat.trigger": handleHeartbeatTrigger,
     "heartbeat.status": handleHeartbeatStatus,
     "heartbeat.start": handleHeartbeatStart,
+    "heartbeat.set_delivery": handleHeartbeatSetDelivery,
     "cron.status": handleCronStatus,
     "cron.list": handleCronList,
     "cron.add": handleCronAdd,
@@ -101,6 +123,10 @@ export function buildRpcHandlers(): Partial<{ [M in RpcMethod]: Handler<M> }> {
     "pair.list": handlePairList,
     "pair.approve": handlePairApprove,
     "pair.reject": handlePairReject,
+    "pair.auto_approve": handlePairAutoApprove,
+    "pair.auto_approve.add": handlePairAutoApproveAdd,
+    "pair.auto_approve.remove": handlePairAutoApproveRemove,
+    "pair.auto_approve.list": handlePairAutoApproveList,
     "tool.request": handleToolRequest,
     "chat.send": handleChatSend,
     "workspace.list": handleWorkspaceList,
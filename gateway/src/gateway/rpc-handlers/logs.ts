@@ -16,6 +16,16 @@ function resolveLogLineLimit(input: number | undefined): number {
   return Math.min(Math.floor(input), MAX_LOG_LINES);
 }
 
+function resolveLogFilter(input: unknown): string | undefined {
+  if (input === undefined) {
+    return undefined;
+  }
+  if (typeof input !== "string") {
+    throw new RpcError(400, "filter must be a string");
+  }
+  return input || undefined;
+}
+
 export const handleLogsGet: Handler<"logs.get"> = ({ gw, ws, frame, params }) => {
   const attachment = ws.deserializeAttachment();
   const clientId = attachment.clientId as string | undefined;
@@ -24,6 +34,7 @@ export const handleLogsGet: Handler<"logs.get"> = ({ gw, ws, frame, params }) =>
   }
 
   const lines = resolveLogLineLimit(params?.lines);
+  const filter = resolveLogFilter(params?.filter);
 
   const nodeId = params?.nodeId;
   let targetNodeId: string;
@@ -62,6 +73,7 @@ export const handleLogsGet: Handler<"logs.get"> = ({ gw, ws, frame, params }) =>
     payload: {
       callId,
       lines,
+      filter,
     },
   };
   nodeWs.send(JSON.stringify(evt));
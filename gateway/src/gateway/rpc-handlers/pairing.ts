@@ -1,4 +1,7 @@
-import { normalizeE164 } from "../../config/parsing";
+import {
+  compileAutoApproveRule,
+  normalizeE164,
+} from "../../config/parsing";
 import type { Handler } from "../../protocol/methods";
 import { RpcError } from "../../shared/utils";
 import type { Gateway } from "../do";
@@ -17,6 +20,9 @@ function findChannelForMessage(gw: Gateway, channel: string): string | null {
 
 export const handlePairList: Handler<"pair.list"> = ({ gw }) => ({
   pairs: { ...gw.pendingPairs },
+  autoApprove: Object.entries(gw.getFullConfig().channels)
+    .filter(([, channelConfig]) => channelConfig?.autoApprove)
+    .map(([channel]) => channel),
 });
 
 export const handlePairApprove: Handler<"pair.approve"> = ({ gw, params }) => {
@@ -68,6 +74,97 @@ export const handlePairApprove: Handler<"pair.approve"> = ({ gw, params }) => {
   };
 };
 
+export const handlePairAutoApprove: Handler<"pair.auto_approve"> = ({
+  gw,
+  params,
+}) => {
+  if (!params?.channel || typeof params.enable !== "boolean") {
+    throw new RpcError(400, "channel and enable required");
+  }
+
+  const config = gw.getFullConfig();
+  if (!config.channels[params.channel]) {
+    throw new RpcError(404, `Channel ${params.channel} is not configured`);
+  }
+
+  gw.setConfigPath(`channels.${params.channel}.autoApprove`, params.enable);
+
+  console.log(
+    `[Gateway] ${params.enable ? "Enabled" : "Disabled"} pairing auto-approve for ${params.channel}`,
+  );
+
+  return {
+    channel: params.channel,
+    autoApprove: params.enable,
+  };
+};
+
+export const handlePairAutoApproveAdd: Handler<"pair.auto_approve.add"> = ({
+  gw,
+  params,
+}) => {
+  if (!params?.channel || !params?.pattern) {
+    throw new RpcError(400, "channel and pattern required");
+  }
+  if (!compileAutoApproveRule(params.pattern)) {
+    throw new RpcError(400, `Invalid auto-approve pattern: ${params.pattern}`);
+  }
+
+  const config = gw.getFullConfig();
+  const channelConfig = config.channels[params.channel];
+  if (!channelConfig) {
+    throw new RpcError(404, `Channel ${params.channel} is not configured`);
+  }
+
+  let rules = channelConfig.autoApproveRules ?? [];
+  if (!rules.includes(params.pattern)) {
+    rules = [...rules, params.pattern];
+    gw.setConfigPath(`channels.${params.channel}.autoApproveRules`, rules);
+    console.log(
+      `[Gateway] Added pairing auto-approve rule ${params.pattern} for ${params.channel}`,
+    );
+  }
+
+  return { channel: params.channel, rules };
+};
+
+export const handlePairAutoApproveRemove: Handler<
+  "pair.auto_approve.remove"
+> = ({ gw, params }) => {
+  if (!params?.channel || !params?.pattern) {
+    throw new RpcError(400, "channel and pattern required");
+  }
+
+  const config = gw.getFullConfig();
+  const current = config.channels[params.channel]?.autoApproveRules ?? [];
+  const rules = current.filter((rule) => rule !== params.pattern);
+  const removed = rules.length !== current.length;
+  if (removed) {
+    gw.setConfigPath(`channels.${params.channel}.autoApproveRules`, rules);
+    console.log(
+      `[Gateway] Removed pairing auto-approve rule ${params.pattern} for ${params.channel}`,
+    );
+  }
+
+  return { channel: params.channel, removed, rules };
+};
+
+export const handlePairAutoApproveList: Handler<"pair.auto_approve.list"> = ({
+  gw,
+  params,
+}) => {
+  const rules: Record<string, string[]> = {};
+  for (const [channel, channelConfig] of Object.entries(
+    gw.getFullConfig().channels,
+  )) {
+    if (params?.channel && channel !== params.channel) continue;
+    if (channelConfig?.autoApproveRules?.length) {
+      rules[channel] = channelConfig.autoApproveRules;
+    }
+  }
+  return { rules };
+};
+
 export const handlePairReject: Handler<"pair.reject"> = ({ gw, params }) => {
   if (!params?.channel || !params?.senderId) {
     throw new RpcError(400, "channel and senderId required");
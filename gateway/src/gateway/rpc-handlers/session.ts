@@ -1,4 +1,5 @@
 import { env } from "cloudflare:workers";
+import type { Message } from "@mariozechner/pi-ai";
 import type { Handler } from "../../protocol/methods";
 import { RpcError } from "../../shared/utils";
 
@@ -45,7 +46,11 @@ export const handleSessionCompact: Handler<"session.compact"> = async ({
   const sessionKey = gw.canonicalizeSessionKey(params.sessionKey);
   const sessionStub = env.SESSION.getByName(sessionKey);
 
-  return await sessionStub.compact(params.keepMessages);
+  return await sessionStub.compact(
+    params.keepMessages,
+    params.strategy,
+    params.archiveTrimmed,
+  );
 };
 
 export const handleSessionStats: Handler<"session.stats"> = async ({
@@ -90,6 +95,22 @@ export const handleSessionHistory: Handler<"session.history"> = async ({
   return await sessionStub.history();
 };
 
+export const handleSessionHistoryRestore: Handler<
+  "session.history.restore"
+> = async ({ gw, params }) => {
+  if (!params?.sessionKey) {
+    throw new RpcError(400, "sessionKey required");
+  }
+  if (!params.sessionId) {
+    throw new RpcError(400, "sessionId required");
+  }
+
+  const sessionKey = gw.canonicalizeSessionKey(params.sessionKey);
+  const sessionStub = env.SESSION.getByName(sessionKey);
+
+  return await sessionStub.restoreHistory(params.sessionId);
+};
+
 export const handleSessionPreview: Handler<"session.preview"> = async ({
   gw,
   params,
@@ -104,6 +125,81 @@ export const handleSessionPreview: Handler<"session.preview"> = async ({
   return await sessionStub.preview(params.limit);
 };
 
+export const handleSessionExport: Handler<"session.export"> = async ({
+  gw,
+  params,
+}) => {
+  if (!params?.sessionKey) {
+    throw new RpcError(400, "sessionKey required");
+  }
+
+  const sessionKey = gw.canonicalizeSessionKey(params.sessionKey);
+  const sessionStub = env.SESSION.getByName(sessionKey);
+
+  return await sessionStub.export();
+};
+
+export const handleSessionImport: Handler<"session.import"> = async ({
+  gw,
+  params,
+}) => {
+  if (!params?.sessionKey) {
+    throw new RpcError(400, "sessionKey required");
+  }
+  if (!Array.isArray(params.messages)) {
+    throw new RpcError(400, "messages array required");
+  }
+  const roles = new Set(["user", "assistant", "toolResult"]);
+  const invalid = params.messages.findIndex(
+    (message) =>
+      typeof message !== "object" ||
+      message === null ||
+      !roles.has((message as { role?: unknown }).role as string),
+  );
+  if (invalid !== -1) {
+    throw new RpcError(
+      400,
+      `messages[${invalid}] is not a user, assistant or toolResult message`,
+    );
+  }
+
+  const sessionKey = gw.canonicalizeSessionKey(params.sessionKey);
+  const sessionStub = env.SESSION.getByName(sessionKey);
+
+  return await sessionStub.import(
+    params.messages as Message[],
+    params.tokens,
+    params.overwrite ?? false,
+  );
+};
+
+export const handleSessionSearch: Handler<"session.search"> = async ({
+  gw,
+  params,
+}) => {
+  const query = params?.query?.trim();
+  if (!query) {
+    throw new RpcError(400, "query required");
+  }
+  const limit = params?.limit ?? 20;
+
+  // Most recently active sessions first, so the limit keeps the freshest hits.
+  const sessionKeys = params?.sessionKey
+    ? [gw.canonicalizeSessionKey(params.sessionKey)]
+    : Object.values(gw.sessionRegistry)
+        .sort((a, b) => b.lastActiveAt - a.lastActiveAt)
+        .map((entry) => entry.sessionKey);
+
+  const results = [];
+  for (const sessionKey of sessionKeys) {
+    const sessionStub = env.SESSION.getByName(sessionKey);
+    results.push(...(await sessionStub.search(query, limit - results.length)));
+    if (results.length >= limit) break;
+  }
+
+  return { results, count: results.length };
+};
+
 export const handleSessionsList: Handler<"sessions.list"> = ({
   gw,
   params,
@@ -0,0 +1,31 @@
+import { describe, expect, it } from "vitest";
+import { toolInvokeReply } from "./tools";
+
+describe("toolInvokeReply", () => {
+  it("returns the bare result when no session record was requested", () => {
+    expect(toolInvokeReply({ ok: 1 }, undefined)).toEqual({ result: { ok: 1 } });
+  });
+
+  it("marks the result as recorded when the session accepted it", () => {
+    expect(toolInvokeReply("done", { ok: true })).toEqual({
+      result: "done",
+      recorded: true,
+    });
+  });
+
+  it("reports a record refused during an active run", () => {
+    expect(toolInvokeReply("done", { ok: false })).toEqual({
+      result: "done",
+      recorded: false,
+      recordError: "session has an active run",
+    });
+  });
+
+  it("reports a record that threw", () => {
+    expect(toolInvokeReply("done", { error: "Session unavailable" })).toEqual({
+      result: "done",
+      recorded: false,
+      recordError: "Session unavailable",
+    });
+  });
+});
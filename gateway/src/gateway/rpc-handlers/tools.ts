@@ -5,7 +5,11 @@ import {
   type Handler,
 } from "../../protocol/methods";
 import { RpcError } from "../../shared/utils";
-import type { ToolInvokePayload } from "../../protocol/tools";
+import type {
+  ToolDefinition,
+  ToolInvokePayload,
+  ToolProgressPayload,
+} from "../../protocol/tools";
 
 function extractRunningSessionId(result: unknown): string | undefined {
   if (!result || typeof result !== "object" || Array.isArray(result)) {
@@ -21,9 +25,46 @@ function extractRunningSessionId(result: unknown): string | undefined {
   return sessionId || undefined;
 }
 
-export const handleToolsList: Handler<"tools.list"> = ({ gw }) => ({
-  tools: gw.getAllTools(),
-});
+/**
+ * Reply for a `tool.invoke` that asked to be recorded in a session. A record
+ * the session refused (it does while a run is active) is reported back, so
+ * the caller doesn't assume the result landed in the transcript.
+ */
+export function toolInvokeReply(
+  result: unknown,
+  record: { ok: boolean } | { error: string } | undefined,
+): Record<string, unknown> {
+  if (!record) {
+    return { result };
+  }
+  if ("error" in record) {
+    return { result, recorded: false, recordError: record.error };
+  }
+  if (!record.ok) {
+    return {
+      result,
+      recorded: false,
+      recordError: "session has an active run",
+    };
+  }
+  return { result, recorded: true };
+}
+
+export const handleToolsList: Handler<"tools.list"> = ({ gw, params }) => {
+  // Node tools are namespaced "{nodeId}__{toolName}"; expose the node separately.
+  const tools: Array<ToolDefinition & { nodeId?: string }> = gw
+    .getAllTools()
+    .map((tool) => {
+      const separator = tool.name.indexOf("__");
+      const nodeId = separator > 0 ? tool.name.slice(0, separator) : undefined;
+      return nodeId && gw.nodes.has(nodeId) ? { ...tool, nodeId } : tool;
+    });
+  return {
+    tools: params?.nodeId
+      ? tools.filter((tool) => tool.nodeId === params.nodeId)
+      : tools,
+  };
+};
 
 export const handleToolRequest: Handler<"tool.request"> = ({ gw, params }) => {
   if (!params?.callId || !params?.tool || !params?.sessionKey) {
@@ -88,6 +129,13 @@ export const handleToolInvoke: Handler<"tool.invoke"> = (ctx) => {
     clientId,
     frameId: frame.id,
     createdAt: Date.now(),
+    record: params.sessionKey
+      ? {
+          sessionKey: gw.canonicalizeSessionKey(params.sessionKey),
+          tool: params.tool,
+          args: params.args ?? {},
+        }
+      : undefined,
   };
 
   const evt: EventFrame<ToolInvokePayload> = {
@@ -122,7 +170,44 @@ export const handleToolResult: Handler<"tool.result"> = async ({
     return { ok: true, dropped: true };
   }
 
+  // Partial results from streaming tools: forward to direct callers as
+  // tool.progress events and keep the call pending until the final result.
+  if (params.done === false) {
+    if (route.kind === "client") {
+      const clientWs = gw.clients.get(route.clientId);
+      if (clientWs && clientWs.readyState === WebSocket.OPEN) {
+        const evt: EventFrame<ToolProgressPayload> = {
+          type: "evt",
+          event: "tool.progress",
+          payload: { callId: params.callId, result: params.result },
+        };
+        clientWs.send(JSON.stringify(evt));
+      }
+    }
+    return { ok: true, partial: true };
+  }
+
   if (route.kind === "client") {
+    let record: { ok: boolean } | { error: string } | undefined;
+    if (route.record) {
+      try {
+        record = await env.SESSION.getByName(
+          route.record.sessionKey,
+        ).recordToolInvocation({
+          tool: route.record.tool,
+          args: route.record.args,
+          result: params.result,
+          error: params.error,
+        });
+      } catch (e) {
+        console.error(
+          `[Gateway] Failed to record tool call ${params.callId} in session ${route.record.sessionKey}:`,
+          e,
+        );
+        record = { error: e instanceof Error ? e.message : String(e) };
+      }
+    }
+
     const clientWs = gw.clients.get(route.clientId);
     if (!clientWs || clientWs.readyState !== WebSocket.OPEN) {
       console.log(
@@ -135,9 +220,7 @@ export const handleToolResult: Handler<"tool.result"> = async ({
     if (params.error) {
       gw.sendError(clientWs, route.frameId, 500, params.error);
     } else {
-      gw.sendOk(clientWs, route.frameId, {
-        result: params.result,
-      });
+      gw.sendOk(clientWs, route.frameId, toolInvokeReply(params.result, record));
     }
     delete gw.pendingToolCalls[params.callId];
     return { ok: true };
@@ -1,6 +1,8 @@
 export type ChannelId =
   | "whatsapp"
   | "discord"
+  | "telegram"
+  | "slack"
   | (string & {});
 
 export type ChatType = "dm" | "group" | "channel" | "thread";
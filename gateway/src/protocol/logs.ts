@@ -1,11 +1,14 @@
 export type LogsGetParams = {
   nodeId?: string;
   lines?: number;
+  /** Only return lines containing this substring. */
+  filter?: string;
 };
 
 export type LogsGetEventPayload = {
   callId: string;
   lines: number;
+  filter?: string;
 };
 
 export type LogsResultParams = {
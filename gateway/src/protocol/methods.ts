@@ -1,10 +1,15 @@
 import type { GsvConfig, PendingPair } from "../config";
-import type { ChannelAccountStatus } from "../channel-interface";
+import type {
+  ChannelAccountStatus,
+  ChannelGuild,
+  ChannelMedia,
+} from "../channel-interface";
 import type { Gateway } from "../gateway/do";
 import type {
   ResetPolicy,
   ResetResult,
   SessionPatchParams,
+  SessionSearchHit,
   SessionSettings,
   SessionStats,
   TokenUsage,
@@ -13,6 +18,7 @@ import {
   ChannelInboundParams,
   ChannelRegistryEntry,
   ChannelId,
+  PeerInfo,
 } from "./channel";
 import type { RequestFrame } from "./frames";
 import type { SessionRegistryEntry } from "./session";
@@ -81,6 +87,8 @@ export type ConnectResult = {
 export type ToolInvokeParams = {
   tool: string;
   args?: Record<string, unknown>;
+  /** Record the call and its result in this session's history. */
+  sessionKey?: string;
 };
 
 export type RpcMethods = {
@@ -96,7 +104,7 @@ export type RpcMethods = {
 
   "tool.result": {
     params: ToolResultParams;
-    result: { ok: true; dropped?: true };
+    result: { ok: true; dropped?: true; partial?: true };
   };
 
   "node.probe.result": {
@@ -120,14 +128,20 @@ export type RpcMethods = {
   };
 
   "tools.list": {
-    params: undefined;
+    params: { nodeId?: string } | undefined;
     result: {
-      tools: ToolDefinition[];
+      tools: Array<ToolDefinition & { nodeId?: string }>;
     };
   };
 
   "chat.send": {
-    params: { sessionKey: string; message: string; runId?: string };
+    params: {
+      sessionKey: string;
+      message: string;
+      runId?: string;
+      /** Text files carry a `charset=` content type and raw data; others are base64. */
+      attachment?: { name: string; content_type: string; data: string };
+    };
     result:
       | {
           status: "started";
@@ -222,12 +236,23 @@ export type RpcMethods = {
   };
 
   "session.compact": {
-    params: { sessionKey: string; keepMessages?: number };
+    params: {
+      sessionKey: string;
+      keepMessages?: number;
+      strategy?: "last-n" | "summarize";
+      /** Save trimmed messages as JSON in R2; fail instead of discarding them. */
+      archiveTrimmed?: boolean;
+      /** Summarize trimmed messages into a system message at the start of the kept history. */
+      summarize?: boolean;
+      /** Cap on the length of the `summarize` summary. */
+      summaryMaxTokens?: number;
+    };
     result: {
       ok: boolean;
       trimmedMessages: number;
       keptMessages: number;
       archivedTo?: string;
+      summary?: string;
     };
   };
 
@@ -240,6 +265,18 @@ export type RpcMethods = {
     };
   };
 
+  "session.history.restore": {
+    params: { sessionKey: string; sessionId: string };
+    result: {
+      ok: boolean;
+      sessionKey: string;
+      restoredFrom: string;
+      sessionId: string;
+      messageCount: number;
+      archivedTo?: string;
+    };
+  };
+
   "session.preview": {
     params: { sessionKey: string; limit?: number };
     result: {
@@ -250,6 +287,42 @@ export type RpcMethods = {
     };
   };
 
+  "session.export": {
+    params: { sessionKey: string };
+    result: {
+      sessionKey: string;
+      sessionId: string;
+      createdAt: number;
+      updatedAt: number;
+      messageCount: number;
+      tokens: TokenUsage;
+      label?: string;
+      messages: unknown[]; // TS2589
+    };
+  };
+
+  "session.search": {
+    params: { query: string; sessionKey?: string; limit?: number };
+    result: { results: SessionSearchHit[]; count: number };
+  };
+
+  "session.import": {
+    params: {
+      sessionKey: string;
+      messages: unknown[];
+      tokens?: Partial<TokenUsage>;
+      overwrite?: boolean;
+    };
+    result: {
+      ok: boolean;
+      sessionKey: string;
+      sessionId: string;
+      messageCount: number;
+      tokens: TokenUsage;
+      archivedTo?: string;
+    };
+  };
+
   "channels.list": {
     params: undefined;
     result: { channels: ChannelRegistryEntry[]; count: number };
@@ -299,6 +372,33 @@ export type RpcMethods = {
     result: { ok: true; channel: ChannelId; accountId: string };
   };
 
+  "channel.send": {
+    params: {
+      channel: string;
+      accountId?: string;
+      peer: PeerInfo;
+      text: string;
+      media?: ChannelMedia[];
+      /** Discord embed objects */
+      embeds?: Record<string, unknown>[];
+    };
+    result: {
+      ok: true;
+      channel: ChannelId;
+      accountId: string;
+      messageId?: string;
+    };
+  };
+
+  "channel.discord.guilds": {
+    params: { accountId?: string } | undefined;
+    result: {
+      channel: ChannelId;
+      accountId: string;
+      guilds: ChannelGuild[];
+    };
+  };
+
   "heartbeat.status": {
     params: undefined;
     result: { agents: Record<string, unknown> };
@@ -319,6 +419,16 @@ export type RpcMethods = {
     };
   };
 
+  "heartbeat.set_delivery": {
+    params: {
+      agentId: string;
+      channel: ChannelId;
+      accountId?: string;
+      peer: PeerInfo;
+    };
+    result: { ok: true; agentId: string };
+  };
+
   "cron.status": {
     params: undefined;
     result: {
@@ -382,7 +492,7 @@ export type RpcMethods = {
 
   "pair.list": {
     params: undefined;
-    result: { pairs: Record<string, PendingPair> };
+    result: { pairs: Record<string, PendingPair>; autoApprove: string[] };
   };
 
   "pair.approve": {
@@ -395,6 +505,26 @@ export type RpcMethods = {
     result: { rejected: true; senderId: string };
   };
 
+  "pair.auto_approve": {
+    params: { channel: string; enable: boolean };
+    result: { channel: string; autoApprove: boolean };
+  };
+
+  "pair.auto_approve.add": {
+    params: { channel: string; pattern: string };
+    result: { channel: string; rules: string[] };
+  };
+
+  "pair.auto_approve.remove": {
+    params: { channel: string; pattern: string };
+    result: { channel: string; removed: boolean; rules: string[] };
+  };
+
+  "pair.auto_approve.list": {
+    params: { channel?: string } | undefined;
+    result: { rules: Record<string, string[]> };
+  };
+
   "workspace.list": {
     params: { path?: string; agentId?: string };
     result: {
@@ -57,6 +57,13 @@ export type ToolResultParams = {
   callId: string;
   result?: unknown;
   error?: string;
+  /** `false` marks a partial (streamed) result; the call stays pending. */
+  done?: boolean;
+};
+
+export type ToolProgressPayload = {
+  callId: string;
+  result?: unknown;
 };
 
 export type ToolInvokePayload = {
@@ -21,11 +21,17 @@ import type {
 } from "@mariozechner/pi-ai";
 import { completeSimple, getModel } from "@mariozechner/pi-ai";
 import { isContextOverflow } from "@mariozechner/pi-ai/dist/utils/overflow.js";
-import { archivePartialMessages, archiveSession } from "../storage/archive";
+import {
+  archiveCompactedMessages,
+  archivePartialMessages,
+  archiveSession,
+  getArchivedTranscript,
+} from "../storage/archive";
 import {
   runCompaction,
   extractMemoriesFromMessages,
   type CompactionContext,
+  type CompactionResult,
 } from "./compaction";
 import { shouldCompact } from "./tokens";
 import { estimateContextTokens, estimateStringTokens } from "./tokens";
@@ -81,6 +87,13 @@ type ToolResultInput = {
   error?: string;
 };
 
+type ToolInvocationRecord = {
+  tool: string;
+  args: Record<string, unknown>;
+  result?: unknown;
+  error?: string;
+};
+
 export type SessionSettings = {
   model?: { provider: string; id: string };
   thinkingLevel?: "none" | "minimal" | "low" | "medium" | "high" | "xhigh";
@@ -178,6 +191,17 @@ export type SessionStats = {
   queueSize: number;
 };
 
+export type SessionSearchHit = {
+  sessionKey: string;
+  index: number;
+  role: string;
+  timestamp: number;
+  snippet: string;
+  // Offset and length of the match within `snippet`
+  matchStart: number;
+  matchLength: number;
+};
+
 export type ResetResult = {
   ok: boolean;
   sessionKey: string;
@@ -205,6 +229,26 @@ export type AbortResult = {
 // LRU cache for fetched media (in-memory, survives within request but not hibernation)
 const MEDIA_CACHE_MAX_SIZE = 50 * 1024 * 1024; // 50MB budget
 const DEFAULT_TOOL_TIMEOUT_MS = 60_000;
+const SEARCH_SNIPPET_CONTEXT = 60; // characters of context on each side of a hit
+
+/**
+ * Shallow-merge a patch onto `base`. Undefined keys are skipped and null keys
+ * are removed, so a null patch value resets that field to its default.
+ */
+function mergePatch<T extends object>(
+  base: T | undefined,
+  patch: Partial<Record<keyof T, unknown>>,
+): T {
+  const merged: Record<string, unknown> = { ...(base ?? {}) };
+  for (const [key, value] of Object.entries(patch)) {
+    if (value === null) {
+      delete merged[key];
+    } else if (value !== undefined) {
+      merged[key] = value;
+    }
+  }
+  return merged as T;
+}
 
 function isStructuredToolResult(
   result: unknown,
@@ -1214,6 +1258,38 @@ export class Session extends DurableObject<Env> {
     return { ok: true };
   }
 
+  /**
+   * Record a tool call made outside the agent loop (`tool.invoke` with a
+   * sessionKey) as a synthetic user message, so the result is part of the
+   * conversation context for the next run.
+   */
+  async recordToolInvocation(
+    input: ToolInvocationRecord,
+  ): Promise<{ ok: boolean }> {
+    if (this.currentRun) {
+      // Inserting mid-run would split the run's tool call/result pairs.
+      console.warn(
+        `[Session] Not recording ${input.tool} invocation while a run is active`,
+      );
+      return { ok: false };
+    }
+
+    const output = input.error
+      ? `Error: ${input.error}`
+      : typeof input.result === "string"
+        ? input.result
+        : JSON.stringify(input.result ?? null, null, 2);
+    const message: UserMessage = {
+      role: "user",
+      content: `[Tool call: ${input.tool}]\n\nArgs: ${JSON.stringify(input.args)}\n\n${output}`,
+      timestamp: Date.now(),
+    };
+    this.addMessage(message);
+    this.meta.updatedAt = Date.now();
+
+    return { ok: true };
+  }
+
   private allToolsResolved(): boolean {
     for (const callId of Object.keys(this.pendingToolCalls)) {
       const call = this.pendingToolCalls[callId];
@@ -1264,6 +1340,25 @@ export class Session extends DurableObject<Env> {
       return false;
     }
 
+    const result = await this.summarizeAndCompact(
+      config,
+      effectiveModel,
+      contextWindow,
+    );
+    return result.compacted;
+  }
+
+  /**
+   * Summarize old messages unconditionally (no size check). Shared by automatic
+   * compaction and the explicit `summarize` compaction strategy.
+   */
+  private async summarizeAndCompact(
+    config: GsvConfig,
+    effectiveModel: { provider: string; id: string },
+    contextWindow: number,
+  ): Promise<CompactionResult> {
+    const messages = this.getMessages();
+
     const provider = effectiveModel.provider;
     const apiKey = (config.apiKeys as Record<string, string | undefined>)[
       provider
@@ -1272,7 +1367,7 @@ export class Session extends DurableObject<Env> {
       console.error(
         `[Session] Cannot compact: no API key for provider ${provider}`,
       );
-      return false;
+      return { compacted: false };
     }
 
     // Load existing daily memory so the summarizer can see what's already
@@ -1312,7 +1407,7 @@ export class Session extends DurableObject<Env> {
 
     if (!result.compacted || !result.summaryMessage) {
       console.log(`[Session] Compaction decided nothing to compact`);
-      return false;
+      return { compacted: false };
     }
 
     // Archive old messages to R2
@@ -1363,7 +1458,7 @@ export class Session extends DurableObject<Env> {
       `[Session] Compaction complete: tier=${result.tier}, summarizationCalls=${result.summarizationCalls}, messages: ${messages.length} → ${this.getMessageCount()}, compactionCount=${this.meta.compactionCount}`,
     );
 
-    return true;
+    return result;
   }
 
   private async appendMemoriesToDailyFile(
@@ -2085,17 +2180,14 @@ export class Session extends DurableObject<Env> {
 
   async patch(params: SessionPatchParams): Promise<{ ok: boolean }> {
     if (params.settings) {
-      const mergedSettings: SessionSettings = {
-        ...this.meta.settings,
-        ...params.settings,
-      };
+      const mergedSettings = mergePatch<SessionSettings>(this.meta.settings, params.settings);
 
       // Preserve unspecified model fields for partial patches like settings.model.id.
       if (params.settings.model) {
-        mergedSettings.model = {
-          ...(this.meta.settings.model ?? {}),
-          ...params.settings.model,
-        } as SessionSettings["model"];
+        mergedSettings.model = mergePatch(
+          this.meta.settings.model,
+          params.settings.model,
+        ) as SessionSettings["model"];
       }
 
       this.meta.settings = mergedSettings;
@@ -2104,10 +2196,10 @@ export class Session extends DurableObject<Env> {
       this.meta.label = params.label;
     }
     if (params.resetPolicy !== undefined) {
-      const mergedPolicy = {
-        ...(this.meta.resetPolicy ?? {}),
-        ...params.resetPolicy,
-      } as Partial<ResetPolicy>;
+      const mergedPolicy = mergePatch<Partial<ResetPolicy>>(
+        this.meta.resetPolicy,
+        params.resetPolicy ?? {},
+      );
       if (!mergedPolicy.mode) {
         mergedPolicy.mode = "manual";
       }
@@ -2117,7 +2209,15 @@ export class Session extends DurableObject<Env> {
     return { ok: true };
   }
 
-  async compact(keepMessages: number = 20) {
+  async compact(
+    keepMessages: number = 20,
+    strategy?: string,
+    archiveTrimmed = false,
+  ) {
+    if (strategy === "summarize") {
+      return this.compactBySummary();
+    }
+
     const totalMessages = this.getMessageCount();
     if (totalMessages <= keepMessages) {
       return {
@@ -2133,7 +2233,19 @@ export class Session extends DurableObject<Env> {
     const messagesToKeep = messages.slice(trimCount);
 
     let archivedTo: string | undefined;
-    if (messagesToArchive.length > 0 && this.meta.sessionKey) {
+    if (archiveTrimmed) {
+      // Throws before anything is discarded if the archive can't be written.
+      archivedTo = await archiveCompactedMessages(
+        this.env.STORAGE,
+        this.meta.sessionKey || "unknown",
+        this.meta.sessionId,
+        messagesToArchive,
+        Date.now(),
+      );
+      console.log(
+        `[Session] Compacted: archived ${trimCount} messages to ${archivedTo}`,
+      );
+    } else if (messagesToArchive.length > 0 && this.meta.sessionKey) {
       try {
         const partNumber = Date.now();
         const agentId = this.getAgentId();
@@ -2168,6 +2280,37 @@ export class Session extends DurableObject<Env> {
     };
   }
 
+  private async compactBySummary() {
+    const gateway = this.env.GATEWAY.get(
+      this.env.GATEWAY.idFromName("singleton"),
+    );
+    const config: GsvConfig = await gateway.getConfig();
+    const effectiveModel = this.meta.settings.model || config.model;
+    const model = getModel(
+      effectiveModel.provider as any,
+      effectiveModel.id as any,
+    );
+    if (!model) {
+      throw new Error(
+        `Model not found: ${effectiveModel.provider}/${effectiveModel.id}`,
+      );
+    }
+
+    const result = await this.summarizeAndCompact(
+      config,
+      effectiveModel,
+      model.contextWindow,
+    );
+    const summary = result.summaryMessage?.content;
+
+    return {
+      ok: true,
+      trimmedMessages: result.archivedMessages?.length ?? 0,
+      keptMessages: this.getMessageCount(),
+      summary: typeof summary === "string" ? summary : undefined,
+    };
+  }
+
   async history(): Promise<{
     sessionKey: string;
     currentSessionId: string;
@@ -2180,6 +2323,106 @@ export class Session extends DurableObject<Env> {
     };
   }
 
+  /**
+   * Restore an archived session transcript as the active conversation.
+   * The current conversation is archived first (same as reset), then the
+   * archived messages are loaded under a fresh session ID.
+   */
+  async restoreHistory(sessionId: string): Promise<{
+    ok: boolean;
+    sessionKey: string;
+    restoredFrom: string;
+    sessionId: string;
+    messageCount: number;
+    archivedTo?: string;
+  }> {
+    if (this.currentRun) {
+      throw new Error("Cannot restore session history while a run is active");
+    }
+
+    const messages = await getArchivedTranscript(
+      this.env.STORAGE,
+      sessionId,
+      this.getAgentId(),
+    );
+    if (!messages) {
+      throw new Error(`No archived transcript found for session ${sessionId}`);
+    }
+
+    const resetResult = await this.doReset();
+    for (const message of messages) {
+      this.addMessage(message);
+    }
+    this.meta.updatedAt = Date.now();
+
+    return {
+      ok: true,
+      sessionKey: this.meta.sessionKey,
+      restoredFrom: sessionId,
+      sessionId: this.meta.sessionId,
+      messageCount: this.getMessageCount(),
+      archivedTo: resetResult.archivedTo,
+    };
+  }
+
+  async import(
+    messages: Message[],
+    tokens?: Partial<TokenUsage>,
+    overwrite = false,
+  ): Promise<{
+    ok: boolean;
+    sessionKey: string;
+    sessionId: string;
+    messageCount: number;
+    tokens: TokenUsage;
+    archivedTo?: string;
+  }> {
+    if (this.currentRun) {
+      throw new Error("Cannot import messages while a run is active");
+    }
+
+    const existing = this.getMessageCount();
+    if (existing > 0 && !overwrite) {
+      throw new Error(
+        `Session already has ${existing} messages; pass overwrite to replace them`,
+      );
+    }
+
+    const resetResult = await this.doReset();
+    for (const message of messages) {
+      this.addMessage(message);
+    }
+
+    // Token hints come from the export; fall back to per-message usage.
+    let input = 0;
+    let output = 0;
+    let total = 0;
+    for (const message of messages) {
+      if (message.role === "assistant" && message.usage) {
+        input += message.usage.input || 0;
+        output += message.usage.output || 0;
+        total += message.usage.totalTokens || 0;
+      }
+    }
+    this.meta.inputTokens = tokens?.input ?? input;
+    this.meta.outputTokens = tokens?.output ?? output;
+    this.meta.totalTokens = tokens?.total ?? total;
+    this.meta.updatedAt = Date.now();
+
+    return {
+      ok: true,
+      sessionKey: this.meta.sessionKey,
+      sessionId: this.meta.sessionId,
+      messageCount: this.getMessageCount(),
+      tokens: {
+        input: this.meta.inputTokens,
+        output: this.meta.outputTokens,
+        total: this.meta.totalTokens,
+      },
+      archivedTo: resetResult.archivedTo,
+    };
+  }
+
   async preview(limit?: number): Promise<{
     sessionKey: string;
     sessionId: string;
@@ -2197,6 +2440,83 @@ export class Session extends DurableObject<Env> {
     };
   }
 
+  /**
+   * Case-insensitive substring search over message text. Each hit carries a
+   * snippet of surrounding text and the match offsets within that snippet.
+   */
+  async search(
+    query: string,
+    limit = 20,
+  ): Promise<SessionSearchHit[]> {
+    const needle = query.toLowerCase();
+    if (!needle) return [];
+
+    // Case is folded in JS: SQLite's lower() and LIKE only fold ASCII
+    // letters. Rows are read through the cursor so the scan stops at `limit`
+    // instead of loading the whole transcript.
+    const rows = this.ctx.storage.sql.exec<StoredMessage>(
+      `SELECT idx, role, data, timestamp FROM messages ORDER BY idx`,
+    );
+
+    const hits: SessionSearchHit[] = [];
+    for (const row of rows) {
+      const message = JSON.parse(row.data) as Message;
+      const content = message.content as
+        | string
+        | Array<{ type: string; text?: string }>;
+      const text =
+        typeof content === "string"
+          ? content
+          : content
+              .filter((block) => block.type === "text" && block.text)
+              .map((block) => block.text!)
+              .join("\n");
+      const at = text.toLowerCase().indexOf(needle);
+      if (at < 0) continue;
+
+      const start = Math.max(0, at - SEARCH_SNIPPET_CONTEXT);
+      const end = Math.min(text.length, at + needle.length + SEARCH_SNIPPET_CONTEXT);
+      const prefix = start > 0 ? "…" : "";
+      hits.push({
+        sessionKey: this.meta.sessionKey,
+        index: row.idx,
+        role: row.role,
+        timestamp: row.timestamp,
+        snippet: `${prefix}${text.slice(start, end).replace(/\s/g, " ")}${end < text.length ? "…" : ""}`,
+        matchStart: prefix.length + (at - start),
+        matchLength: needle.length,
+      });
+      if (hits.length >= limit) break;
+    }
+    return hits;
+  }
+
+  async export(): Promise<{
+    sessionKey: string;
+    sessionId: string;
+    createdAt: number;
+    updatedAt: number;
+    messageCount: number;
+    tokens: TokenUsage;
+    label?: string;
+    messages: Message[];
+  }> {
+    return {
+      sessionKey: this.meta.sessionKey,
+      sessionId: this.meta.sessionId,
+      createdAt: this.meta.createdAt,
+      updatedAt: this.meta.updatedAt,
+      messageCount: this.getMessageCount(),
+      tokens: {
+        input: this.meta.inputTokens,
+        output: this.meta.outputTokens,
+        total: this.meta.totalTokens,
+      },
+      label: this.meta.label,
+      messages: JSON.parse(JSON.stringify(this.getMessages())),
+    };
+  }
+
   async fetch(request: Request): Promise<Response> {
     const url = new URL(request.url);
 
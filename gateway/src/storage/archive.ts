@@ -221,3 +221,28 @@ export async function archivePartialMessages(
 
   return key;
 }
+
+/**
+ * Archive messages trimmed by compaction as plain JSON (compact --archive-trimmed).
+ * Unlike partial archives this write is not best-effort: callers keep the
+ * messages if it throws.
+ */
+export async function archiveCompactedMessages(
+  storage: R2Bucket,
+  sessionKey: string,
+  sessionId: string,
+  messages: Message[],
+  timestamp: number,
+): Promise<string> {
+  const key = `sessions/${sessionKey}/archived/${sessionId}-compact-${timestamp}.json`;
+  await storage.put(key, JSON.stringify(messages), {
+    httpMetadata: { contentType: "application/json" },
+    customMetadata: {
+      sessionKey,
+      sessionId,
+      messageCount: messages.length.toString(),
+      archivedAt: timestamp.toString(),
+    },
+  });
+  return key;
+}